@@ -0,0 +1,208 @@
+//! Keeping track of the right answers, so that we notice when a solution stops getting
+//! them.
+//!
+//! The answers file has one answer per line, after the day and part it answers:
+//!
+//! ```text
+//! # Comments and blank lines are ignored.
+//! 1 1 3426169
+//! 1 2 5136386
+//! 8 2 \n#  # ###  \n# #  #  #
+//! ```
+//!
+//! Everything after the space following the part is the answer.  Some answers are drawn
+//! over several lines, so a newline is written as `\n` (and so a backslash as `\\`).
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc_core::Answer;
+
+use crate::input::{self, INPUTS_DIR_VAR};
+
+const HEADER: &str = "\
+# The right answers for each day and part, checked by the aoc runner.
+# Each line is DAY PART ANSWER, with newlines in answers written as \\n.
+";
+
+// How an answer compares to what we were expecting.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail(String),
+    Unknown,
+}
+
+// Where the right answers are: the file we were told about, else alongside the inputs.
+// An input file given on the command line could be anyone's, so there's nothing to check
+// its answers against unless we're told.
+pub fn locate(answers: Option<&str>, input: Option<&str>) -> Option<PathBuf> {
+    match (answers, input, env::var_os(INPUTS_DIR_VAR)) {
+        (Some(path), _, _) => Some(PathBuf::from(path)),
+        (None, Some(_), _) => None,
+        (None, None, Some(dir)) => Some(Path::new(&dir).join("answers.txt")),
+        (None, None, None) => Some(input::workspace().join("answers.txt")),
+    }
+}
+
+pub struct Answers {
+    path: PathBuf,
+    expected: BTreeMap<(u32, u32), String>,
+}
+
+impl Answers {
+    // Reads the answers file, if there is one yet.
+    pub fn load(path: PathBuf) -> Result<Self, String> {
+        let expected = match fs::read_to_string(&path) {
+            Ok(text) => parse(&text).map_err(|err| format!("{}: {}", path.display(), err))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(format!("Could not load {}: {}", path.display(), err)),
+        };
+        Ok(Self { path, expected })
+    }
+
+    pub fn check(&self, day: u32, part: u32, answer: &Answer) -> Check {
+        match self.expected.get(&(day, part)) {
+            Some(expected) if *expected == answer.to_string() => Check::Pass,
+            Some(expected) => Check::Fail(expected.clone()),
+            None => Check::Unknown,
+        }
+    }
+
+    // Remembers an answer we didn't know before.  Answers we did know are left alone - if
+    // one of those is wrong, that needs a human to decide which is right.
+    pub fn record(&mut self, day: u32, part: u32, answer: &Answer) -> bool {
+        if self.expected.contains_key(&(day, part)) {
+            return false;
+        }
+        self.expected.insert((day, part), answer.to_string());
+        true
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        fs::write(&self.path, self.to_text())
+            .map_err(|err| format!("Could not save {}: {}", self.path.display(), err))
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = String::from(HEADER);
+        for ((day, part), answer) in &self.expected {
+            text.push_str(&format!("{} {} {}\n", day, part, escape(answer)));
+        }
+        text
+    }
+}
+
+fn parse(text: &str) -> Result<BTreeMap<(u32, u32), String>, String> {
+    let mut expected = BTreeMap::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || format!("Line {} isn't DAY PART ANSWER", index + 1);
+        let mut fields = line.splitn(3, ' ');
+        let day = fields
+            .next()
+            .and_then(|day| day.parse().ok())
+            .ok_or_else(invalid)?;
+        let part = fields
+            .next()
+            .and_then(|part| part.parse().ok())
+            .ok_or_else(invalid)?;
+        let answer = fields.next().ok_or_else(invalid)?;
+        if expected.insert((day, part), unescape(answer)).is_some() {
+            return Err(format!(
+                "Line {} repeats the answer to day {} part {}",
+                index + 1,
+                day,
+                part
+            ));
+        }
+    }
+    Ok(expected)
+}
+
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(answer: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => unescaped.push('\n'),
+                Some(escaped) => unescaped.push(escaped),
+                None => unescaped.push(c),
+            }
+        } else {
+            unescaped.push(c);
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(text: &str) -> Answers {
+        Answers {
+            path: PathBuf::new(),
+            expected: parse(text).unwrap(),
+        }
+    }
+
+    #[test]
+    fn checking() {
+        let answers = answers("# A comment\n\n1 1 1234\n1 2 hello world\n8 2 \\n# #\\n # \n");
+        assert_eq!(answers.check(1, 1, &Answer::Number(1234)), Check::Pass);
+        assert_eq!(
+            answers.check(1, 1, &Answer::Number(1235)),
+            Check::Fail(String::from("1234"))
+        );
+        assert_eq!(
+            answers.check(1, 2, &Answer::from("hello world")),
+            Check::Pass
+        );
+        assert_eq!(
+            answers.check(8, 2, &Answer::from("\n# #\n # ")),
+            Check::Pass
+        );
+        assert_eq!(answers.check(2, 1, &Answer::Number(1234)), Check::Unknown);
+    }
+
+    #[test]
+    fn recording() {
+        let mut answers = answers("2 1 5\n");
+        assert!(!answers.record(2, 1, &Answer::Number(6)));
+        assert!(answers.record(1, 2, &Answer::from("a\\b\nc")));
+        assert!(answers.record(25, 2, &Answer::None));
+        let text = answers.to_text();
+        assert!(text.starts_with(HEADER));
+        assert_eq!(&text[HEADER.len()..], "1 2 a\\\\b\\nc\n2 1 5\n25 2 -\n");
+
+        // And it reads back in the same.
+        assert_eq!(parse(&text).unwrap(), answers.expected);
+    }
+
+    #[test]
+    fn bad_files() {
+        assert_eq!(
+            parse("1 1 5\n1 x 6"),
+            Err(String::from("Line 2 isn't DAY PART ANSWER"))
+        );
+        assert_eq!(
+            parse("1 1"),
+            Err(String::from("Line 1 isn't DAY PART ANSWER"))
+        );
+        assert_eq!(
+            parse("1 1 5\n1 1 6"),
+            Err(String::from("Line 2 repeats the answer to day 1 part 1"))
+        );
+    }
+}
@@ -0,0 +1,480 @@
+//! Working out what we've been asked to do.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::image::{ImageFormat, Palette};
+use crate::output::Format;
+
+pub const USAGE: &str = "\
+Usage: aoc [OPTIONS] [DAYS]...
+
+Runs the solutions for the given days, and reports their answers and how long they took.
+
+DAYS can be a single day (5), a range of days (3-7), or \"all\", which is the default.
+Day 25 needs someone to play it, so it's only run if it's asked for on its own.
+
+Each day's input is read from:
+  - FILE, if given with --input (or -i), which needs a single day.  \"-\" means stdin.
+  - Otherwise dayN.txt in the directory named by AOC_INPUTS, if that's set.
+  - Otherwise dayN/input.txt in the workspace.
+
+Each answer is checked against the answers file, and reported as a pass, a fail or
+unknown.  The answers file is FILE, if given with --answers, or else answers.txt in the
+AOC_INPUTS directory or the workspace.  With --input, answers are only checked if
+--answers is given too.
+
+  --record  Adds any unknown answers to the answers file.
+  --strict  Treats unknown answers as failures.
+
+  --format FORMAT    How to report the answers: text (the default), json or ndjson.  The
+                     JSON fields are described in aoc/src/output.rs.
+
+To benchmark the days rather than just run them once, use --bench:
+
+  --bench N          Runs each day N times after warming up, and reports the fastest,
+                     median and 95th percentile times for each step.
+  --warmup N         How many runs to warm up with first.  The default is 1.
+  --save FILE        Saves the results to FILE, as JSON.
+  --baseline FILE    Compares the results to some saved earlier, and fails if any median
+                     is slower by more than the threshold.
+  --threshold PCT    How much slower counts as a regression.  The default is 10%.
+
+Some days draw pictures (days 8, 11, 13, 15, 17 and 24).  To save them as image files too,
+use --images:
+
+  --images DIR       Saves each picture in DIR, as dayN-NAME.png or whichever format.
+  --image-format FMT png (the default), svg, pbm or pgm.
+  --scale N          How many pixels across each character of a picture becomes.  The
+                     default is 8.
+  --palette SPEC     The colours to draw characters in, like \"#=000000,o=ff0000\".
+                     Spaces and dots are white and # is black unless the palette says
+                     otherwise, and anything else gets a colour of its own.";
+
+// What was wrong with the command line.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ArgsError(String);
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Args {
+    // The days to run, in order, without duplicates.
+    pub days: Vec<u32>,
+
+    // Where to read the input from, if not the usual place - "-" for stdin.
+    pub input: Option<String>,
+
+    // Where to find the right answers, if not the usual place.
+    pub answers: Option<String>,
+
+    // Whether to add answers we didn't know to the answers file.
+    pub record: bool,
+
+    // Whether an answer we don't know is a failure.
+    pub strict: bool,
+
+    // How to report the answers.
+    pub format: Format,
+
+    // How to benchmark the days, if we're doing that instead of running them once.
+    pub bench: Option<Bench>,
+
+    // How to save the days' pictures, if we're doing that.
+    pub images: Option<Images>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Bench {
+    pub iterations: usize,
+    pub warmup: usize,
+    pub save: Option<String>,
+    pub baseline: Option<String>,
+    pub threshold: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Images {
+    pub dir: String,
+    pub format: ImageFormat,
+    pub scale: usize,
+    pub palette: Palette,
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, ArgsError> {
+        let mut days = Vec::new();
+        let mut input = None;
+        let mut answers = None;
+        let mut record = false;
+        let mut strict = false;
+        let mut format = Format::Text;
+        let (mut iterations, mut warmup, mut save, mut baseline, mut threshold) =
+            (None, None, None, None, None);
+        let (mut dir, mut image_format, mut scale, mut palette) = (None, None, None, None);
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Options take their values either as the next argument, or after an "=".
+            let (option, mut inline) = match arg.find('=') {
+                Some(index) if arg.starts_with("--") => (&arg[..index], Some(&arg[index + 1..])),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = |what: &str| {
+                inline
+                    .take()
+                    .map(str::to_string)
+                    .or_else(|| args.next())
+                    .ok_or_else(|| ArgsError(format!("{} needs {}", option, what)))
+            };
+            match option {
+                "--input" | "-i" => input = Some(value("a file")?),
+                "--answers" => answers = Some(value("a file")?),
+                "--record" => record = true,
+                "--strict" => strict = true,
+                "--format" => format = parse_format(&value("a format")?)?,
+                "--bench" => iterations = Some(number(option, &value("a number")?)?),
+                "--warmup" => warmup = Some(number(option, &value("a number")?)?),
+                "--save" => save = Some(value("a file")?),
+                "--baseline" => baseline = Some(value("a file")?),
+                "--threshold" => threshold = Some(number(option, &value("a number")?)?),
+                "--images" => dir = Some(value("a directory")?),
+                "--image-format" => image_format = Some(parse_image_format(&value("a format")?)?),
+                "--scale" => scale = Some(number(option, &value("a number")?)?),
+                "--palette" => {
+                    palette = Some(Palette::parse(&value("a palette")?).map_err(ArgsError)?);
+                }
+                _ if option.starts_with("--") => {
+                    return Err(ArgsError(format!("Unknown option: {}", option)));
+                }
+                _ => days.extend(parse_days(&arg)?),
+            }
+        }
+        if days.is_empty() {
+            days.extend(1..=25);
+        }
+        days.sort_unstable();
+        days.dedup();
+
+        // One input can't be right for more than one day.
+        if input.is_some() && days.len() != 1 {
+            return Err(ArgsError(String::from("--input needs a single day")));
+        }
+
+        let bench = match iterations {
+            Some(0) => return Err(ArgsError(String::from("--bench needs at least one run"))),
+            Some(iterations) => Some(Bench {
+                iterations,
+                warmup: warmup.unwrap_or(1),
+                save,
+                baseline,
+                threshold: threshold.unwrap_or(10),
+            }),
+            None if warmup.is_some()
+                || save.is_some()
+                || baseline.is_some()
+                || threshold.is_some() =>
+            {
+                return Err(ArgsError(String::from("Benchmarking options need --bench")));
+            }
+            None => None,
+        };
+
+        let images = match dir {
+            Some(dir) => Some(Images {
+                dir,
+                format: image_format.unwrap_or(ImageFormat::Png),
+                scale: match scale {
+                    Some(0) => {
+                        return Err(ArgsError(String::from("--scale needs to be at least 1")))
+                    }
+                    scale => scale.unwrap_or(8),
+                },
+                palette: palette.unwrap_or_default(),
+            }),
+            None if image_format.is_some() || scale.is_some() || palette.is_some() => {
+                return Err(ArgsError(String::from("Image options need --images")));
+            }
+            None => None,
+        };
+
+        // Benchmarks have their own way of saving results.
+        if bench.is_some() && format != Format::Text {
+            return Err(ArgsError(String::from(
+                "--format can't be used with --bench - use --save instead",
+            )));
+        }
+
+        Ok(Self {
+            days,
+            input,
+            answers,
+            record,
+            strict,
+            format,
+            bench,
+            images,
+        })
+    }
+}
+
+fn parse_format(format: &str) -> Result<Format, ArgsError> {
+    match format {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "ndjson" => Ok(Format::Ndjson),
+        _ => Err(ArgsError(format!("Not a format: {}", format))),
+    }
+}
+
+fn parse_image_format(format: &str) -> Result<ImageFormat, ArgsError> {
+    ImageFormat::parse(format).ok_or_else(|| ArgsError(format!("Not an image format: {}", format)))
+}
+
+fn number<T: FromStr>(option: &str, value: &str) -> Result<T, ArgsError> {
+    value
+        .parse()
+        .map_err(|_| ArgsError(format!("{} needs a number, not {}", option, value)))
+}
+
+fn parse_days(arg: &str) -> Result<Vec<u32>, ArgsError> {
+    if arg == "all" {
+        return Ok((1..=25).collect());
+    }
+    let (first, last) = match arg.find('-') {
+        Some(index) => (parse_day(&arg[..index])?, parse_day(&arg[index + 1..])?),
+        None => {
+            let day = parse_day(arg)?;
+            (day, day)
+        }
+    };
+    if first > last {
+        return Err(ArgsError(format!("Backwards range of days: {}", arg)));
+    }
+    Ok((first..=last).collect())
+}
+
+fn parse_day(day: &str) -> Result<u32, ArgsError> {
+    match day.parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(ArgsError(format!("Not a day between 1 and 25: {}", day))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Vec<u32>, ArgsError> {
+        Args::parse(args.iter().map(|arg| arg.to_string())).map(|args| args.days)
+    }
+
+    #[test]
+    fn days() {
+        assert_eq!(parse(&[]).unwrap(), (1..=25).collect::<Vec<_>>());
+        assert_eq!(parse(&["all"]).unwrap(), (1..=25).collect::<Vec<_>>());
+        assert_eq!(parse(&["5"]).unwrap(), vec![5]);
+        assert_eq!(parse(&["12", "3-5", "4"]).unwrap(), vec![3, 4, 5, 12]);
+    }
+
+    #[test]
+    fn bad_days() {
+        assert_eq!(
+            parse(&["0"]),
+            Err(ArgsError(String::from("Not a day between 1 and 25: 0")))
+        );
+        assert_eq!(
+            parse(&["26"]),
+            Err(ArgsError(String::from("Not a day between 1 and 25: 26")))
+        );
+        assert_eq!(
+            parse(&["x-3"]),
+            Err(ArgsError(String::from("Not a day between 1 and 25: x")))
+        );
+        assert_eq!(
+            parse(&["7-3"]),
+            Err(ArgsError(String::from("Backwards range of days: 7-3")))
+        );
+    }
+
+    #[test]
+    fn input() {
+        let input = |args: &[&str]| {
+            Args::parse(args.iter().map(|arg| arg.to_string())).map(|args| args.input)
+        };
+        assert_eq!(input(&["5"]), Ok(None));
+        assert_eq!(
+            input(&["--input", "in.txt", "5"]),
+            Ok(Some(String::from("in.txt")))
+        );
+        assert_eq!(input(&["5", "-i", "-"]), Ok(Some(String::from("-"))));
+        assert_eq!(
+            input(&["--input=in.txt", "5-5"]),
+            Ok(Some(String::from("in.txt")))
+        );
+        assert_eq!(
+            input(&["5", "--input"]),
+            Err(ArgsError(String::from("--input needs a file")))
+        );
+        assert_eq!(
+            input(&["--input", "in.txt", "5-6"]),
+            Err(ArgsError(String::from("--input needs a single day")))
+        );
+        assert_eq!(
+            input(&["--input", "in.txt"]),
+            Err(ArgsError(String::from("--input needs a single day")))
+        );
+    }
+
+    #[test]
+    fn answers() {
+        let args = Args::parse(vec![String::from("3")]).unwrap();
+        assert_eq!(
+            (args.answers, args.record, args.strict),
+            (None, false, false)
+        );
+
+        let args = ["--record", "--answers", "mine.txt", "--strict", "3"];
+        let args = Args::parse(args.iter().map(|arg| arg.to_string())).unwrap();
+        assert_eq!(args.answers, Some(String::from("mine.txt")));
+        assert!(args.record && args.strict);
+
+        let args = Args::parse(vec![String::from("--answers=mine.txt")]).unwrap();
+        assert_eq!(args.answers, Some(String::from("mine.txt")));
+        assert_eq!(
+            Args::parse(vec![String::from("--answers")]),
+            Err(ArgsError(String::from("--answers needs a file")))
+        );
+    }
+
+    #[test]
+    fn format() {
+        let format = |args: &[&str]| {
+            Args::parse(args.iter().map(|arg| arg.to_string())).map(|args| args.format)
+        };
+        assert_eq!(format(&[]), Ok(Format::Text));
+        assert_eq!(format(&["--format", "json"]), Ok(Format::Json));
+        assert_eq!(format(&["--format=ndjson"]), Ok(Format::Ndjson));
+        assert_eq!(
+            format(&["--format", "xml"]),
+            Err(ArgsError(String::from("Not a format: xml")))
+        );
+        assert_eq!(
+            format(&["--format", "json", "--bench", "5"]),
+            Err(ArgsError(String::from(
+                "--format can't be used with --bench - use --save instead"
+            )))
+        );
+    }
+
+    #[test]
+    fn bench() {
+        let bench = |args: &[&str]| {
+            Args::parse(args.iter().map(|arg| arg.to_string())).map(|args| args.bench)
+        };
+        assert_eq!(bench(&["3"]), Ok(None));
+        assert_eq!(
+            bench(&["--bench", "20", "3"]),
+            Ok(Some(Bench {
+                iterations: 20,
+                warmup: 1,
+                save: None,
+                baseline: None,
+                threshold: 10
+            }))
+        );
+        assert_eq!(
+            bench(&[
+                "--bench=5",
+                "--warmup=0",
+                "--save",
+                "new.json",
+                "--baseline",
+                "old.json",
+                "--threshold",
+                "25"
+            ]),
+            Ok(Some(Bench {
+                iterations: 5,
+                warmup: 0,
+                save: Some(String::from("new.json")),
+                baseline: Some(String::from("old.json")),
+                threshold: 25
+            }))
+        );
+
+        assert_eq!(
+            bench(&["--bench", "0"]),
+            Err(ArgsError(String::from("--bench needs at least one run")))
+        );
+        assert_eq!(
+            bench(&["--bench", "lots"]),
+            Err(ArgsError(String::from("--bench needs a number, not lots")))
+        );
+        assert_eq!(
+            bench(&["--save", "new.json"]),
+            Err(ArgsError(String::from("Benchmarking options need --bench")))
+        );
+        assert_eq!(
+            bench(&["--benchmark"]),
+            Err(ArgsError(String::from("Unknown option: --benchmark")))
+        );
+    }
+
+    #[test]
+    fn images() {
+        let images = |args: &[&str]| {
+            Args::parse(args.iter().map(|arg| arg.to_string())).map(|args| args.images)
+        };
+        assert_eq!(images(&["8"]), Ok(None));
+        assert_eq!(
+            images(&["--images", "pictures", "8"]),
+            Ok(Some(Images {
+                dir: String::from("pictures"),
+                format: ImageFormat::Png,
+                scale: 8,
+                palette: Palette::default()
+            }))
+        );
+        assert_eq!(
+            images(&[
+                "--images=pictures",
+                "--image-format",
+                "svg",
+                "--scale=2",
+                "--palette",
+                "#=ff0000"
+            ]),
+            Ok(Some(Images {
+                dir: String::from("pictures"),
+                format: ImageFormat::Svg,
+                scale: 2,
+                palette: Palette::parse("#=ff0000").unwrap()
+            }))
+        );
+
+        assert_eq!(
+            images(&["--images", "pictures", "--image-format", "gif"]),
+            Err(ArgsError(String::from("Not an image format: gif")))
+        );
+        assert_eq!(
+            images(&["--images", "pictures", "--scale", "0"]),
+            Err(ArgsError(String::from("--scale needs to be at least 1")))
+        );
+        assert_eq!(
+            images(&["--images", "pictures", "--palette", "#=black"]),
+            Err(ArgsError(String::from("Not a colour: black")))
+        );
+        assert_eq!(
+            images(&["--scale", "4"]),
+            Err(ArgsError(String::from("Image options need --images")))
+        );
+        assert_eq!(
+            images(&["--images"]),
+            Err(ArgsError(String::from("--images needs a directory")))
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Benchmarking: timing lots of runs of each day, rather than trusting just the one.
+//!
+//! Results are saved as JSON, with every time in milliseconds:
+//!
+//! ```text
+//! {
+//!   "iterations": 20,
+//!   "warmup": 1,
+//!   "days": {
+//!     "24": {
+//!       "parse": { "min": 0.002, "median": 0.002, "p95": 0.003 },
+//!       "part1": { "min": 0.301, "median": 0.312, "p95": 0.355 },
+//!       "part2": { "min": 3.981, "median": 4.020, "p95": 4.310 },
+//!       "total": { "min": 4.290, "median": 4.334, "p95": 4.668 }
+//!     }
+//!   }
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::time::Duration;
+
+use aoc_core::Timings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Results {
+    pub iterations: usize,
+    pub warmup: usize,
+    pub days: BTreeMap<u32, DayStats>,
+}
+
+impl Results {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let json =
+            fs::read_to_string(path).map_err(|err| format!("Could not load {}: {}", path, err))?;
+        serde_json::from_str(&json).map_err(|err| format!("Could not load {}: {}", path, err))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).unwrap();
+        fs::write(path, json + "\n").map_err(|err| format!("Could not save {}: {}", path, err))
+    }
+}
+
+// The stats for each step of solving one day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DayStats {
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+    pub total: Stats,
+}
+
+impl DayStats {
+    pub fn new(timings: &[Timings]) -> Self {
+        let stats = |step: fn(&Timings) -> Duration| Stats::new(timings.iter().map(step).collect());
+        Self {
+            parse: stats(|timings| timings.parse),
+            part1: stats(|timings| timings.part1),
+            part2: stats(|timings| timings.part2),
+            total: stats(Timings::total),
+        }
+    }
+
+    pub fn steps(&self) -> [(&'static str, Stats); 4] {
+        [
+            ("Parse", self.parse),
+            ("Part 1", self.part1),
+            ("Part 2", self.part2),
+            ("Total", self.total),
+        ]
+    }
+}
+
+// How long one step took over all the runs, in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub p95: f64,
+}
+
+impl Stats {
+    fn new(mut times: Vec<Duration>) -> Self {
+        times.sort_unstable();
+        let millis = |index: usize| times[index].as_secs_f64() * 1000.0;
+        let middle = times.len() / 2;
+        let median = if times.len() % 2 == 1 {
+            millis(middle)
+        } else {
+            (millis(middle - 1) + millis(middle)) / 2.0
+        };
+
+        // The nearest-rank percentile: the smallest time that at least 95% of the runs
+        // were no slower than.
+        let p95 = (times.len() * 95).div_ceil(100);
+        Self {
+            min: millis(0),
+            median,
+            p95: millis(p95 - 1),
+        }
+    }
+}
+
+// How much slower `new` is than `old`, as a percentage - negative if it's faster.
+pub fn slowdown(old: f64, new: f64) -> f64 {
+    if old == 0.0 {
+        0.0
+    } else {
+        (new - old) / old * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(times: &[u64]) -> Vec<Duration> {
+        times
+            .iter()
+            .map(|&time| Duration::from_millis(time))
+            .collect()
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::new(millis(&[5, 1, 4, 2, 3]));
+        assert_eq!((stats.min, stats.median, stats.p95), (1.0, 3.0, 5.0));
+
+        let stats = Stats::new(millis(&[4, 1, 3, 2]));
+        assert_eq!((stats.min, stats.median, stats.p95), (1.0, 2.5, 4.0));
+
+        let stats = Stats::new(millis(&[7]));
+        assert_eq!((stats.min, stats.median, stats.p95), (7.0, 7.0, 7.0));
+
+        // With a hundred runs, the p95 is the 95th fastest.
+        let stats = Stats::new(millis(&(1..=100).rev().collect::<Vec<_>>()));
+        assert_eq!((stats.min, stats.median, stats.p95), (1.0, 50.5, 95.0));
+    }
+
+    #[test]
+    fn day_stats() {
+        let timings = |parse, part1, part2| Timings {
+            parse: Duration::from_millis(parse),
+            part1: Duration::from_millis(part1),
+            part2: Duration::from_millis(part2),
+        };
+        let stats = DayStats::new(&[
+            timings(1, 10, 100),
+            timings(3, 30, 300),
+            timings(2, 20, 200),
+        ]);
+        assert_eq!(stats.parse.median, 2.0);
+        assert_eq!(stats.part1.min, 10.0);
+        assert_eq!(stats.part2.p95, 300.0);
+        assert_eq!(stats.total.median, 222.0);
+    }
+
+    #[test]
+    fn json() {
+        let stats = DayStats::new(&[Timings::default()]);
+        let mut results = Results {
+            iterations: 1,
+            warmup: 0,
+            days: BTreeMap::new(),
+        };
+        results.days.insert(24, stats);
+        let json = serde_json::to_string(&results).unwrap();
+        assert!(json.starts_with(r#"{"iterations":1,"warmup":0,"days":{"24":{"parse":{"min":0.0,"#));
+        assert_eq!(serde_json::from_str::<Results>(&json).unwrap(), results);
+    }
+
+    #[test]
+    fn slowdowns() {
+        assert_eq!(slowdown(10.0, 12.5), 25.0);
+        assert_eq!(slowdown(10.0, 5.0), -50.0);
+        assert_eq!(slowdown(0.0, 5.0), 0.0);
+    }
+}
@@ -0,0 +1,432 @@
+//! Saving the days' pictures as image files, for looking at properly.
+//!
+//! A picture is whatever a day gives as an `Answer::Image`: rows of characters.  Each
+//! character becomes a square of pixels, `scale` pixels across, in the colour the palette
+//! gives it.  There are no dependencies for any of the formats - PBM and PGM are barely
+//! more than the pixels themselves, SVG is just text, and PNG is written uncompressed.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::args::Images;
+
+// The colours for characters that the palette doesn't mention, other than the usual
+// background and foreground, in the order they're handed out.
+const OTHER_COLOURS: [Colour; 8] = [
+    Colour(0xe6, 0x19, 0x4b),
+    Colour(0x43, 0x63, 0xd8),
+    Colour(0x3c, 0xb4, 0x4b),
+    Colour(0xf5, 0x82, 0x31),
+    Colour(0x91, 0x1e, 0xb4),
+    Colour(0x42, 0xd4, 0xf4),
+    Colour(0xf0, 0x32, 0xe6),
+    Colour(0x80, 0x80, 0x00),
+];
+
+const WHITE: Colour = Colour(0xff, 0xff, 0xff);
+const BLACK: Colour = Colour(0, 0, 0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    // Black and white, one bit per pixel.
+    Pbm,
+    // Shades of grey, one byte per pixel.
+    Pgm,
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "pbm" => Some(Self::Pbm),
+            "pgm" => Some(Self::Pgm),
+            "png" => Some(Self::Png),
+            "svg" => Some(Self::Svg),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Pbm => "pbm",
+            Self::Pgm => "pgm",
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Colour(u8, u8, u8);
+
+impl Colour {
+    // Reads a colour written as six hex digits, with or without a # in front.
+    fn parse(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if hex.len() != 6 || !hex.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+        Some(Self(channel(0)?, channel(2)?, channel(4)?))
+    }
+
+    // How bright the colour looks, from 0 for black to 255 for white.
+    fn grey(self) -> u8 {
+        let Self(red, green, blue) = self;
+        ((299 * u32::from(red) + 587 * u32::from(green) + 114 * u32::from(blue)) / 1000) as u8
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+// Which colour each character of a picture is drawn in.  Spaces and dots are the
+// background, so they're white, and # is black, unless the palette says otherwise.  Any
+// other character gets a colour of its own.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Palette(BTreeMap<char, Colour>);
+
+impl Palette {
+    // Reads a palette written as comma-separated CHAR=COLOUR pairs, like "#=000000,o=ff0000".
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut colours = BTreeMap::new();
+        for entry in spec.split(',').filter(|entry| !entry.is_empty()) {
+            let mut chars = entry.chars();
+            let (c, equals) = (chars.next(), chars.next());
+            let colour = chars.as_str();
+            match (c, equals, Colour::parse(colour)) {
+                (Some(c), Some('='), Some(colour)) => {
+                    colours.insert(c, colour);
+                }
+                (Some(_), Some('='), None) => return Err(format!("Not a colour: {}", colour)),
+                _ => return Err(format!("Not a palette entry: {}", entry)),
+            }
+        }
+        Ok(Self(colours))
+    }
+
+    // The colour for every character in the picture.
+    fn colours(&self, rows: &[Vec<char>]) -> BTreeMap<char, Colour> {
+        let mut colours = BTreeMap::new();
+        let mut others = OTHER_COLOURS.iter().cycle();
+        for &c in rows.iter().flatten() {
+            if colours.contains_key(&c) {
+                continue;
+            }
+            let colour = match (self.0.get(&c), c) {
+                (Some(&colour), _) => colour,
+                (None, ' ') | (None, '.') => WHITE,
+                (None, '#') => BLACK,
+                (None, _) => *others.next().unwrap(),
+            };
+            colours.insert(c, colour);
+        }
+        colours
+    }
+}
+
+// Saves a picture at `path`, creating the directory it's in if need be.
+pub fn save(picture: &[String], path: &Path, images: &Images) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, encode(picture, images)?)
+}
+
+// Turns a picture into the bytes of an image file.
+pub fn encode(picture: &[String], images: &Images) -> io::Result<Vec<u8>> {
+    // Rows might not all be the same length, so the short ones get background added.
+    let mut rows: Vec<Vec<char>> = picture.iter().map(|row| row.chars().collect()).collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if width == 0 || images.scale == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "There's nothing to draw",
+        ));
+    }
+    rows.iter_mut().for_each(|row| row.resize(width, ' '));
+    let colours = images.palette.colours(&rows);
+
+    if images.format == ImageFormat::Svg {
+        return Ok(svg(&rows, &colours, images.scale).into_bytes());
+    }
+
+    // Every other format wants the pixels themselves, a row at a time.
+    let pixels: Vec<Vec<Colour>> = rows
+        .iter()
+        .flat_map(|row| {
+            let pixel_row: Vec<Colour> = row
+                .iter()
+                .flat_map(|c| {
+                    let colour = colours[c];
+                    (0..images.scale).map(move |_| colour)
+                })
+                .collect();
+            vec![pixel_row; images.scale]
+        })
+        .collect();
+    Ok(match images.format {
+        ImageFormat::Pbm => pbm(&pixels),
+        ImageFormat::Pgm => pgm(&pixels),
+        _ => png(&pixels),
+    })
+}
+
+// A 1 is a black pixel, and each row is packed into whole bytes, leftmost pixel first.
+fn pbm(pixels: &[Vec<Colour>]) -> Vec<u8> {
+    let mut image = format!("P4\n{} {}\n", pixels[0].len(), pixels.len()).into_bytes();
+    for row in pixels {
+        for byte in row.chunks(8) {
+            let bits = byte
+                .iter()
+                .enumerate()
+                .filter(|(_, colour)| colour.grey() < 128)
+                .fold(0, |bits, (index, _)| bits | 0x80 >> index);
+            image.push(bits);
+        }
+    }
+    image
+}
+
+fn pgm(pixels: &[Vec<Colour>]) -> Vec<u8> {
+    let mut image = format!("P5\n{} {}\n255\n", pixels[0].len(), pixels.len()).into_bytes();
+    image.extend(pixels.iter().flatten().map(|colour| colour.grey()));
+    image
+}
+
+// Draws each run of the same colour along a row as a single rectangle, one unit per
+// character, and scales the whole lot up.
+fn svg(rows: &[Vec<char>], colours: &BTreeMap<char, Colour>, scale: usize) -> String {
+    let (width, height) = (rows[0].len(), rows.len());
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n",
+        width * scale,
+        height * scale,
+        width,
+        height
+    );
+    for (y, row) in rows.iter().enumerate() {
+        let mut x = 0;
+        while x < width {
+            let colour = colours[&row[x]];
+            let run = row[x..].iter().take_while(|c| colours[c] == colour).count();
+            svg += &format!(
+                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"{}\"/>\n",
+                x,
+                y,
+                run,
+                colour.hex()
+            );
+            x += run;
+        }
+    }
+    svg + "</svg>\n"
+}
+
+// An 8-bit RGB PNG.  The pixel data is put in "stored" deflate blocks, which aren't
+// compressed at all, so there's no need for an implementation of deflate - just the
+// checksums.
+fn png(pixels: &[Vec<Colour>]) -> Vec<u8> {
+    let (width, height) = (pixels[0].len() as u32, pixels.len() as u32);
+    let mut header = Vec::new();
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // 8 bits per channel, RGB, and the standard compression, filtering and (lack of)
+    // interlacing.
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    // Each row starts with the filter it uses, which is none.
+    let mut data = Vec::new();
+    for row in pixels {
+        data.push(0);
+        for &Colour(red, green, blue) in row {
+            data.extend_from_slice(&[red, green, blue]);
+        }
+    }
+
+    let mut image = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_chunk(&mut image, b"IHDR", &header);
+    png_chunk(&mut image, b"IDAT", &zlib_stored(&data));
+    png_chunk(&mut image, b"IEND", &[]);
+    image
+}
+
+fn png_chunk(image: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    image.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = image.len();
+    image.extend_from_slice(kind);
+    image.extend_from_slice(data);
+    let crc = crc32(&image[start..]);
+    image.extend_from_slice(&crc.to_be_bytes());
+}
+
+// A zlib stream of uncompressed deflate blocks, which can each hold up to 65535 bytes.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = if data.is_empty() {
+        vec![data]
+    } else {
+        data.chunks(0xffff).collect()
+    };
+    for (index, block) in blocks.iter().enumerate() {
+        let last = index == blocks.len() - 1;
+        stream.push(u8::from(last));
+        let length = block.len() as u16;
+        stream.extend_from_slice(&length.to_le_bytes());
+        stream.extend_from_slice(&(!length).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0_u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ 0xedb8_8320
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for &byte in bytes {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    b << 16 | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picture(rows: &[&str]) -> Vec<String> {
+        rows.iter().map(|row| row.to_string()).collect()
+    }
+
+    fn options(format: ImageFormat, scale: usize) -> Images {
+        Images {
+            dir: String::from("pictures"),
+            format,
+            scale,
+            palette: Palette::default(),
+        }
+    }
+
+    #[test]
+    fn palettes() {
+        let palette = Palette::parse("#=ff0000,.=#000000").unwrap();
+        let rows = vec![" .#o@".chars().collect(), "@x".chars().collect()];
+        let colours = palette.colours(&rows);
+        assert_eq!(colours[&' '], WHITE);
+        assert_eq!(colours[&'.'], BLACK);
+        assert_eq!(colours[&'#'], Colour(0xff, 0, 0));
+        assert_eq!(
+            (colours[&'o'], colours[&'@'], colours[&'x']),
+            (OTHER_COLOURS[0], OTHER_COLOURS[1], OTHER_COLOURS[2])
+        );
+
+        assert_eq!(Palette::parse(""), Ok(Palette::default()));
+        assert_eq!(
+            Palette::parse("#=12345"),
+            Err(String::from("Not a colour: 12345"))
+        );
+        assert_eq!(
+            Palette::parse("#000000"),
+            Err(String::from("Not a palette entry: #000000"))
+        );
+    }
+
+    #[test]
+    fn bitmaps() {
+        let picture = picture(&["# #", " #"]);
+        assert_eq!(
+            encode(&picture, &options(ImageFormat::Pbm, 1)).unwrap(),
+            b"P4\n3 2\n\xa0\x40".to_vec()
+        );
+        assert_eq!(
+            encode(&picture, &options(ImageFormat::Pbm, 3)).unwrap(),
+            b"P4\n9 6\n\xe3\x80\xe3\x80\xe3\x80\x1c\x00\x1c\x00\x1c\x00".to_vec()
+        );
+        assert_eq!(
+            encode(&picture, &options(ImageFormat::Pgm, 1)).unwrap(),
+            b"P5\n3 2\n255\n\x00\xff\x00\xff\x00\xff".to_vec()
+        );
+        assert!(encode(&[], &options(ImageFormat::Pbm, 1)).is_err());
+        assert!(encode(&picture, &options(ImageFormat::Pbm, 0)).is_err());
+    }
+
+    #[test]
+    fn svgs() {
+        let svg = encode(&picture(&["##o", ". "]), &options(ImageFormat::Svg, 10)).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("width=\"30\" height=\"20\" viewBox=\"0 0 3 2\""));
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"2\" height=\"1\" fill=\"#000000\"/>"));
+        assert!(svg.contains("<rect x=\"2\" y=\"0\" width=\"1\" height=\"1\" fill=\"#e6194b\"/>"));
+        assert!(svg.contains("<rect x=\"0\" y=\"1\" width=\"3\" height=\"1\" fill=\"#ffffff\"/>"));
+        assert_eq!(svg.matches("<rect").count(), 3);
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn pngs() {
+        let png = encode(&picture(&["#o", " "]), &options(ImageFormat::Png, 2)).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        // Read the chunks back, checking their checksums as we go.
+        let mut chunks = Vec::new();
+        let mut rest = &png[8..];
+        while !rest.is_empty() {
+            let length = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+            let (kind_and_data, crc) = (&rest[4..8 + length], &rest[8 + length..12 + length]);
+            assert_eq!(crc, crc32(kind_and_data).to_be_bytes());
+            chunks.push((&kind_and_data[..4], &kind_and_data[4..]));
+            rest = &rest[12 + length..];
+        }
+        let kinds: Vec<&[u8]> = chunks.iter().map(|&(kind, _)| kind).collect();
+        assert_eq!(kinds, vec![&b"IHDR"[..], b"IDAT", b"IEND"]);
+        assert_eq!(chunks[0].1, &[0, 0, 0, 4, 0, 0, 0, 4, 8, 2, 0, 0, 0]);
+
+        // Four rows of a filter byte and four RGB pixels, in a single stored block.
+        let zlib = chunks[1].1;
+        let (red, green, blue) = (0xe6, 0x19, 0x4b);
+        let top_row = [0, 0, 0, 0, 0, 0, 0, red, green, blue, red, green, blue];
+        let bottom_row = [
+            0, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+        ];
+        let rows = [top_row, top_row, bottom_row, bottom_row].concat();
+        assert_eq!(&zlib[..7], &[0x78, 0x01, 1, 52, 0, !52, 0xff]);
+        assert_eq!(&zlib[7..zlib.len() - 4], &rows[..]);
+        assert_eq!(&zlib[zlib.len() - 4..], &adler32(&rows).to_be_bytes());
+    }
+
+    #[test]
+    fn big_pngs() {
+        // More than one stored block's worth.
+        let data = vec![7; 70_000];
+        let zlib = zlib_stored(&data);
+        assert_eq!(&zlib[2..7], &[0, 0xff, 0xff, 0, 0]);
+        assert_eq!(&zlib[65542..65547], &[1, 0x71, 0x11, 0x8e, 0xee]);
+        assert_eq!(zlib.len(), 2 + 5 + 65535 + 5 + 4465 + 4);
+    }
+}
@@ -0,0 +1,84 @@
+//! Finding each day's puzzle input.
+
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+// The environment variable naming a directory of everyone's own inputs.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS";
+
+// This crate lives in the workspace, alongside all the days.
+pub fn workspace() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    // Where to find a day's input: the file we were told about on the command line if
+    // any, else the inputs directory from the environment if set, else the input file
+    // that lives alongside the day's code.
+    pub fn for_day(day: u32, input: Option<&str>) -> Self {
+        Self::locate(day, input, env::var_os(INPUTS_DIR_VAR))
+    }
+
+    fn locate(day: u32, input: Option<&str>, inputs_dir: Option<OsString>) -> Self {
+        match (input, inputs_dir) {
+            (Some("-"), _) => Self::Stdin,
+            (Some(path), _) => Self::File(PathBuf::from(path)),
+            (None, Some(dir)) => Self::File(Path::new(&dir).join(format!("day{}.txt", day))),
+            (None, None) => Self::File(workspace().join(format!("day{}", day)).join("input.txt")),
+        }
+    }
+
+    pub fn read(&self) -> Result<String, String> {
+        let input = match self {
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map(|_| input)
+            }
+            Self::File(path) => fs::read_to_string(path),
+        };
+        input.map_err(|err| format!("Could not load {}: {}", self, err))
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Stdin => write!(f, "stdin"),
+            Self::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locations() {
+        let inputs = Some(OsString::from("inputs"));
+        assert_eq!(Source::locate(3, Some("-"), inputs.clone()), Source::Stdin);
+        assert_eq!(
+            Source::locate(3, Some("mine.txt"), inputs.clone()),
+            Source::File(PathBuf::from("mine.txt"))
+        );
+        assert_eq!(
+            Source::locate(3, None, inputs),
+            Source::File(Path::new("inputs").join("day3.txt"))
+        );
+
+        assert_eq!(
+            Source::locate(3, None, None),
+            Source::File(workspace().join("day3").join("input.txt"))
+        );
+    }
+}
@@ -0,0 +1,358 @@
+//! Runs any or all of the days' solutions, and reports their answers and timings in the
+//! same format.  Run with `--help` for the details.
+
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+use aoc_core::{Answer, Outcome, Puzzle};
+
+mod answers;
+mod args;
+mod bench;
+mod image;
+mod input;
+mod output;
+
+use answers::{Answers, Check};
+use args::{Args, Bench, Images};
+use bench::{DayStats, Results};
+use input::Source;
+use output::Output;
+
+// The smallest slowdown a benchmark will call a regression, however big it is in relative
+// terms.
+const NOTICEABLE_MS: f64 = 0.01;
+
+fn puzzles() -> Vec<Box<dyn Puzzle>> {
+    vec![
+        aoc_core::puzzle::<day1::Day1>(),
+        aoc_core::puzzle::<day2::Day2>(),
+        aoc_core::puzzle::<day3::Day3>(),
+        aoc_core::puzzle::<day4::Day4>(),
+        aoc_core::puzzle::<day5::Day5>(),
+        aoc_core::puzzle::<day6::Day6>(),
+        aoc_core::puzzle::<day7::Day7>(),
+        aoc_core::puzzle::<day8::Day8>(),
+        aoc_core::puzzle::<day9::Day9>(),
+        aoc_core::puzzle::<day10::Day10>(),
+        aoc_core::puzzle::<day11::Day11>(),
+        aoc_core::puzzle::<day12::Day12>(),
+        aoc_core::puzzle::<day13::Day13>(),
+        aoc_core::puzzle::<day14::Day14>(),
+        aoc_core::puzzle::<day15::Day15>(),
+        aoc_core::puzzle::<day16::Day16>(),
+        aoc_core::puzzle::<day17::Day17>(),
+        aoc_core::puzzle::<day18::Day18>(),
+        aoc_core::puzzle::<day19::Day19>(),
+        aoc_core::puzzle::<day20::Day20>(),
+        aoc_core::puzzle::<day21::Day21>(),
+        aoc_core::puzzle::<day22::Day22>(),
+        aoc_core::puzzle::<day23::Day23>(),
+        aoc_core::puzzle::<day24::Day24>(),
+        aoc_core::puzzle::<day25::Day25>(),
+    ]
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", args::USAGE);
+        return;
+    }
+    let args = Args::parse(args).unwrap_or_else(|err| {
+        eprintln!("{}\n\n{}", err, args::USAGE);
+        process::exit(2);
+    });
+
+    let mut answers = answers::locate(args.answers.as_deref(), args.input.as_deref()).map(|path| {
+        Answers::load(path).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            process::exit(2);
+        })
+    });
+
+    let baseline = args
+        .bench
+        .as_ref()
+        .and_then(|bench| bench.baseline.as_ref())
+        .map(|path| {
+            Results::load(path).unwrap_or_else(|err| {
+                eprintln!("{}", err);
+                process::exit(2);
+            })
+        });
+    let mut results = args.bench.as_ref().map(|bench| Results {
+        iterations: bench.iterations,
+        warmup: bench.warmup,
+        ..Results::default()
+    });
+
+    let puzzles = puzzles();
+    let mut output = Output::new(args.format, args.days.len() == 1);
+    let mut total_time = Duration::default();
+    let mut tally = Tally::default();
+    let mut regressions = 0;
+    let mut failed = false;
+    for day in &args.days {
+        let puzzle = &puzzles[*day as usize - 1];
+        output.start_day(*day);
+
+        // Day 25 would sit there waiting for someone to play it.
+        if puzzle.interactive() && args.days.len() > 1 {
+            output.skipped(*day, "it's interactive, so run it on its own");
+            continue;
+        }
+
+        let source = Source::for_day(*day, args.input.as_deref());
+        if puzzle.interactive() && source == Source::Stdin {
+            output.failed(
+                *day,
+                "Can't read the input from stdin - that's where the commands come from",
+            );
+            failed = true;
+            continue;
+        }
+
+        if puzzle.interactive() && args.bench.is_some() {
+            output.skipped(*day, "it's interactive, so can't be benchmarked");
+            continue;
+        }
+
+        let result = source.read().and_then(|input| match &args.bench {
+            Some(bench) => benchmark(puzzle.as_ref(), &input, bench)
+                .map(|(outcome, stats)| (outcome, Some(stats))),
+            None => solve(puzzle.as_ref(), &input).map(|outcome| (outcome, None)),
+        });
+        match result {
+            Ok((outcome, stats)) => {
+                let verdicts = [&outcome.part1, &outcome.part2]
+                    .iter()
+                    .zip(1..)
+                    .map(|(answer, part)| match answers.as_mut() {
+                        Some(answers) => tally.check(answers, &args, *day, part, answer),
+                        None => String::new(),
+                    })
+                    .collect::<Vec<_>>();
+                output.solved(*day, &outcome, &verdicts);
+                if let Some(images) = &args.images {
+                    failed |= !save_pictures(*day, &outcome, images, &output);
+                }
+                match stats {
+                    Some(stats) => {
+                        let baseline = baseline
+                            .as_ref()
+                            .and_then(|baseline| baseline.days.get(day));
+                        let threshold = args.bench.as_ref().map_or(0, |bench| bench.threshold);
+                        regressions += report_stats(&stats, baseline, threshold);
+                        total_time += Duration::from_secs_f64(stats.total.median / 1000.0);
+                        results.as_mut().unwrap().days.insert(*day, stats);
+                    }
+                    None if output.is_text() => {
+                        let timings = &outcome.timings;
+                        println!(
+                            "Time: {} (parse {}, part 1 {}, part 2 {})",
+                            millis(timings.total()),
+                            millis(timings.parse),
+                            millis(timings.part1),
+                            millis(timings.part2)
+                        );
+                        total_time += timings.total();
+                    }
+                    None => total_time += outcome.timings.total(),
+                }
+            }
+            Err(err) => {
+                output.failed(*day, &err);
+                failed = true;
+            }
+        }
+    }
+
+    output.finish();
+    if args.days.len() > 1 && output.is_text() {
+        println!("\nTotal time: {}", millis(total_time));
+    }
+    if let Some(answers) = answers {
+        output.note(&tally.to_string());
+        if tally.recorded > 0 {
+            if let Err(err) = answers.save() {
+                output.note(&err);
+                failed = true;
+            }
+        }
+    }
+    if let Some(save) = args.bench.as_ref().and_then(|bench| bench.save.as_ref()) {
+        if let Err(err) = results.unwrap().save(save) {
+            println!("{}", err);
+            failed = true;
+        }
+    }
+    if regressions > 0 {
+        let threshold = args.bench.as_ref().map_or(0, |bench| bench.threshold);
+        println!(
+            "\nRegressions: {} steps more than {}% slower than the baseline",
+            regressions, threshold
+        );
+    }
+    if failed || regressions > 0 || tally.failed(args.strict) {
+        process::exit(1);
+    }
+}
+
+fn solve(puzzle: &dyn Puzzle, input: &str) -> Result<Outcome, String> {
+    // A solution that panics has already said why on stderr, so there's no more to say
+    // here - just carry on with the next day.
+    match panic::catch_unwind(AssertUnwindSafe(|| puzzle.run(input))) {
+        Ok(outcome) => outcome.map_err(|err| err.to_string()),
+        Err(_) => Err(String::from("Panicked!")),
+    }
+}
+
+// Saves every picture a day drew as an image file, named after the day and the picture.
+// Returns whether they were all saved.
+fn save_pictures(day: u32, outcome: &Outcome, images: &Images, output: &Output) -> bool {
+    let answers = [("part1", &outcome.part1), ("part2", &outcome.part2)];
+    let extras = outcome.extra.iter().map(|(name, answer)| (*name, answer));
+    let mut saved = true;
+    for (name, answer) in answers.iter().copied().chain(extras) {
+        if let Answer::Image(picture) = answer {
+            let file = format!("day{}-{}.{}", day, name, images.format.extension());
+            let path = Path::new(&images.dir).join(file);
+            match image::save(picture, &path, images) {
+                Ok(()) => output.note(&format!("Saved {}", path.display())),
+                Err(err) => {
+                    output.note(&format!("Couldn't save {}: {}", path.display(), err));
+                    saved = false;
+                }
+            }
+        }
+    }
+    saved
+}
+
+// Solves the puzzle over and over.  Every run parses the input afresh, so that nothing one
+// run works out (and caches in the parsed input) can speed up the next.
+fn benchmark(
+    puzzle: &dyn Puzzle,
+    input: &str,
+    bench: &Bench,
+) -> Result<(Outcome, DayStats), String> {
+    for _ in 0..bench.warmup {
+        solve(puzzle, input)?;
+    }
+    let mut outcome = solve(puzzle, input)?;
+    let mut timings = vec![outcome.timings];
+    for _ in 1..bench.iterations {
+        outcome = solve(puzzle, input)?;
+        timings.push(outcome.timings);
+    }
+    Ok((outcome, DayStats::new(&timings)))
+}
+
+// How the answers compared with the right ones.
+#[derive(Default)]
+struct Tally {
+    passed: usize,
+    failed: usize,
+    unknown: usize,
+    recorded: usize,
+}
+
+impl Tally {
+    // Checks an answer, and says how it went.
+    fn check(
+        &mut self,
+        answers: &mut Answers,
+        args: &Args,
+        day: u32,
+        part: u32,
+        answer: &Answer,
+    ) -> String {
+        match answers.check(day, part, answer) {
+            Check::Pass => {
+                self.passed += 1;
+                String::from(" (pass)")
+            }
+            Check::Fail(expected) => {
+                self.failed += 1;
+                format!(" (FAIL - expected {})", expected)
+            }
+            Check::Unknown if args.record => {
+                answers.record(day, part, answer);
+                self.recorded += 1;
+                String::from(" (recorded)")
+            }
+            Check::Unknown => {
+                self.unknown += 1;
+                String::from(" (unknown)")
+            }
+        }
+    }
+
+    fn failed(&self, strict: bool) -> bool {
+        self.failed > 0 || (strict && self.unknown > 0)
+    }
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "\nAnswers: {} passed, {} failed, {} unknown",
+            self.passed, self.failed, self.unknown
+        )?;
+        if self.recorded > 0 {
+            write!(f, ", {} recorded", self.recorded)?;
+        }
+        Ok(())
+    }
+}
+
+// Prints the benchmark stats for a day, comparing them to the baseline if there is one,
+// and returns how many steps have got slower by more than the threshold.
+fn report_stats(stats: &DayStats, baseline: Option<&DayStats>, threshold: u32) -> usize {
+    let mut regressions = 0;
+    print!("{:<8}{:>12}{:>12}{:>12}", "", "min", "median", "p95");
+    println!(
+        "{}",
+        if baseline.is_some() {
+            "   vs baseline"
+        } else {
+            ""
+        }
+    );
+    let steps = stats.steps();
+    let baseline_steps = baseline.map(DayStats::steps);
+    for (index, (step, stats)) in steps.iter().enumerate() {
+        print!(
+            "{:<8}{:>12}{:>12}{:>12}",
+            step,
+            format!("{:.3}ms", stats.min),
+            format!("{:.3}ms", stats.median),
+            format!("{:.3}ms", stats.p95)
+        );
+        match baseline_steps {
+            Some(baseline_steps) => {
+                // Steps that take next to no time at all are all noise, so a big slowdown
+                // only counts if it's a noticeable amount of time too.
+                let old = baseline_steps[index].1.median;
+                let slowdown = bench::slowdown(old, stats.median);
+                if slowdown > f64::from(threshold) && stats.median - old > NOTICEABLE_MS {
+                    regressions += 1;
+                    println!("   {:+.1}% - REGRESSION", slowdown);
+                } else {
+                    println!("   {:+.1}%", slowdown);
+                }
+            }
+            None => println!(),
+        }
+    }
+    regressions
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
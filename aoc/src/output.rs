@@ -0,0 +1,211 @@
+//! Reporting what happened, either for people to read or for other programs to scrape.
+//!
+//! With `--format json` or `--format ndjson`, each day is reported as a JSON object:
+//!
+//! ```text
+//! {
+//!   "day": 8,
+//!   "part1": 1905,
+//!   "part2": "ZBJAB",
+//!   "timings": { "parse_ms": 0.041, "part1_ms": 0.052, "part2_ms": 0.024, "total_ms": 0.117 },
+//!   "extra": { "message": ["####  ###    ##  ##  ###  ", "   #  #  #    # #  # #  # ", ...] }
+//! }
+//! ```
+//!
+//! - `day` is the day's number.
+//! - `part1` and `part2` are the answers.  An answer is a number, a string, an array of
+//!   strings for a picture (one per row, top to bottom), or null if there's no answer.
+//! - `timings` says how long each step took, in milliseconds.
+//! - `extra` is anything else the day has to report, by name, each written the same way as
+//!   an answer.  Most days have nothing extra, so it's usually empty.
+//!
+//! A day that couldn't be solved has `day` and `error` - why not - instead of the rest, and
+//! a day that wasn't run has `day` and `skipped`, saying why.
+//!
+//! `json` writes the object for a single day on its own, or an array of them for several
+//! days.  `ndjson` writes each object on a line of its own, as soon as each day is done.
+//! Anything else the runner has to say goes to stderr, so the output is only ever JSON.
+
+use std::time::Duration;
+
+use aoc_core::{Answer, Outcome};
+use serde_json::{json, Map, Value};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Ndjson,
+}
+
+pub struct Output {
+    format: Format,
+
+    // Whether we're reporting a single day, rather than some number of them.
+    single: bool,
+
+    // How many days we've reported so far.
+    days: usize,
+
+    // For JSON, the days we've reported so far, to be written out when we're done.
+    json: Vec<Value>,
+}
+
+impl Output {
+    pub fn new(format: Format, single: bool) -> Self {
+        Self {
+            format,
+            single,
+            days: 0,
+            json: Vec::new(),
+        }
+    }
+
+    pub fn is_text(&self) -> bool {
+        self.format == Format::Text
+    }
+
+    pub fn start_day(&mut self, day: u32) {
+        if self.is_text() {
+            if self.days > 0 {
+                println!();
+            }
+            println!("Day {}", day);
+        }
+        self.days += 1;
+    }
+
+    pub fn skipped(&mut self, day: u32, why: &str) {
+        match self.format {
+            Format::Text => println!("Skipped - {}", why),
+            _ => self.write(json!({ "day": day, "skipped": why })),
+        }
+    }
+
+    pub fn failed(&mut self, day: u32, err: &str) {
+        match self.format {
+            Format::Text => println!("{}", err),
+            _ => self.write(json!({ "day": day, "error": err })),
+        }
+    }
+
+    // Reports the answers, with the verdicts on them from the answers file, if any.  The
+    // timings are only included for JSON - as text, they're reported separately, since
+    // there might be a lot more to say about them.
+    pub fn solved(&mut self, day: u32, outcome: &Outcome, verdicts: &[String]) {
+        match self.format {
+            Format::Text => {
+                println!("Part 1: {}{}", outcome.part1, verdicts[0]);
+                println!("Part 2: {}{}", outcome.part2, verdicts[1]);
+                for (name, extra) in &outcome.extra {
+                    println!("{}: {}", name, extra);
+                }
+            }
+            _ => self.write(day_json(day, outcome)),
+        }
+    }
+
+    // Anything more to say besides the answers.  That's for people, so when the output
+    // is for programs, it goes to stderr instead.
+    pub fn note(&self, note: &str) {
+        if self.is_text() {
+            println!("{}", note);
+        } else {
+            eprintln!("{}", note);
+        }
+    }
+
+    pub fn finish(&mut self) {
+        if self.format == Format::Json {
+            let days = std::mem::take(&mut self.json);
+            let json = match days.as_slice() {
+                [day] if self.single => day.clone(),
+                _ => Value::Array(days),
+            };
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        }
+    }
+
+    fn write(&mut self, day: Value) {
+        match self.format {
+            Format::Ndjson => println!("{}", day),
+            _ => self.json.push(day),
+        }
+    }
+}
+
+fn day_json(day: u32, outcome: &Outcome) -> Value {
+    let timings = &outcome.timings;
+    let extra: Map<String, Value> = outcome
+        .extra
+        .iter()
+        .map(|(name, extra)| ((*name).to_string(), answer(extra)))
+        .collect();
+    json!({
+        "day": day,
+        "part1": answer(&outcome.part1),
+        "part2": answer(&outcome.part2),
+        "timings": {
+            "parse_ms": ms(timings.parse),
+            "part1_ms": ms(timings.part1),
+            "part2_ms": ms(timings.part2),
+            "total_ms": ms(timings.total()),
+        },
+        "extra": extra,
+    })
+}
+
+fn answer(answer: &Answer) -> Value {
+    match answer {
+        Answer::Number(number) => json!(number),
+        Answer::Text(text) => json!(text),
+        Answer::Image(rows) => json!(rows),
+        Answer::None => Value::Null,
+    }
+}
+
+// Going via nanoseconds keeps the milliseconds free of floating point fuzz.
+fn ms(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::Timings;
+
+    #[test]
+    fn answers() {
+        assert_eq!(answer(&Answer::Number(-42)), json!(-42));
+        assert_eq!(answer(&Answer::from("ABC")), json!("ABC"));
+        assert_eq!(
+            answer(&Answer::Image(vec![String::from("# "), String::from(" #")])),
+            json!(["# ", " #"])
+        );
+        assert_eq!(answer(&Answer::None), Value::Null);
+    }
+
+    #[test]
+    fn days() {
+        let outcome = Outcome {
+            part1: Answer::Number(1),
+            part2: Answer::None,
+            extra: vec![("station", Answer::from("3,4"))],
+            timings: Timings {
+                parse: Duration::from_micros(500),
+                part1: Duration::from_millis(2),
+                part2: Duration::from_millis(3),
+            },
+        };
+        assert_eq!(
+            day_json(10, &outcome),
+            json!({
+                "day": 10,
+                "part1": 1,
+                "part2": null,
+                "timings": { "parse_ms": 0.5, "part1_ms": 2.0, "part2_ms": 3.0, "total_ms": 5.5 },
+                "extra": { "station": "3,4" },
+            })
+        );
+    }
+}
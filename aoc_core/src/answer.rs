@@ -0,0 +1,75 @@
+//! What a solution comes up with.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+/// The answer to one part of a day's puzzle.
+///
+/// Most answers are numbers, but some are words, and some are pictures drawn in text.  All the
+/// usual integer types, `String` and `&str` convert into an `Answer`, so a solution can
+/// usually just finish with `.into()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+
+    /// A picture, one string per row, with the answer drawn in it for a human to read.
+    Image(Vec<String>),
+
+    /// There's nothing to answer - day 25 has no part 2.
+    None,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{}", number),
+            Self::Text(text) => write!(f, "{}", text),
+
+            // Starting each row on a new line means the picture starts on a line of its
+            // own, rather than after whatever label it's given.
+            Self::Image(rows) => rows.iter().try_for_each(|row| write!(f, "\n{}", row)),
+            Self::None => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! from_small_integer {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(number: $int) -> Self {
+                    Self::Number(i64::from(number))
+                }
+            }
+        )*
+    };
+}
+
+// Anything that might not fit in an i64 is still an answer - it just isn't a Number.
+macro_rules! from_large_integer {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(number: $int) -> Self {
+                    i64::try_from(number).map_or_else(|_| Self::Text(number.to_string()), Self::Number)
+                }
+            }
+        )*
+    };
+}
+
+from_small_integer!(i8, i16, i32, i64, u8, u16, u32);
+from_large_integer!(i128, isize, u64, u128, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
@@ -0,0 +1,297 @@
+//! # AoC core
+//!
+//! `aoc_core` is what each day's solution plugs into, so that every day can be run, timed
+//! and tested the same way.
+//!
+//! Each day implements [`Solution`]: it turns its puzzle input into whatever form suits
+//! it in [`parse()`], then answers each part of the puzzle from that.  The `aoc` binary
+//! doesn't want to know about all those different forms, so it deals in [`Puzzle`]s
+//! instead - a [`Solution`] with its types erased, which takes text in and gives
+//! [`Answer`]s and timings out.
+//!
+//! # Examples
+//!
+//! ```
+//! use aoc_core::{Answer, ParseError, Solution};
+//!
+//! struct Day0;
+//!
+//! impl Solution for Day0 {
+//!     const DAY: u32 = 0;
+//!     type Input = Vec<i64>;
+//!
+//!     fn parse(input: &str) -> Result<Self::Input, ParseError> {
+//!         input.lines().map(|line| Ok(line.parse()?)).collect()
+//!     }
+//!
+//!     fn part1(numbers: &Self::Input) -> Answer {
+//!         numbers.iter().sum::<i64>().into()
+//!     }
+//!
+//!     fn part2(numbers: &Self::Input) -> Answer {
+//!         numbers.iter().product::<i64>().into()
+//!     }
+//! }
+//!
+//! let outcome = aoc_core::puzzle::<Day0>().run("2\n3\n4").unwrap();
+//! assert_eq!((outcome.part1, outcome.part2), (Answer::Number(9), Answer::Number(24)));
+//! ```
+//!
+//! [`Solution`]: trait.Solution.html
+//! [`parse()`]: trait.Solution.html#tymethod.parse
+//! [`Puzzle`]: trait.Puzzle.html
+//! [`Answer`]: enum.Answer.html
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+use std::num::ParseIntError;
+use std::time::{Duration, Instant};
+
+mod answer;
+
+pub use answer::Answer;
+
+/// A solution to one day's puzzle.
+///
+/// The two parts are answered independently, from the same parsed input, so that either
+/// can be run (and timed) on its own.  If both parts need the same expensive work doing,
+/// the input can hold a `OnceCell` for whichever part gets there first to fill in.
+pub trait Solution {
+    /// Which day of December this solves.
+    const DAY: u32;
+
+    /// Whether the solution needs someone at the keyboard, rather than working out the
+    /// answers by itself.
+    const INTERACTIVE: bool = false;
+
+    /// The puzzle input, in whatever form suits the solution.
+    type Input;
+
+    /// Turns the puzzle input into the form the solution wants.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input isn't valid.
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    /// Answers part 1 of the puzzle.
+    fn part1(input: &Self::Input) -> Answer;
+
+    /// Answers part 2 of the puzzle.
+    fn part2(input: &Self::Input) -> Answer;
+
+    /// Anything else worth reporting beyond the answers, such as a picture of how the
+    /// solution got there, each with a name.  This isn't timed, but if it needs the same
+    /// work as one of the parts, share it through a `OnceCell` in the input rather than
+    /// doing it all again.  Most days have nothing to add.
+    fn extra(_input: &Self::Input) -> Vec<(&'static str, Answer)> {
+        Vec::new()
+    }
+}
+
+/// Why a day's puzzle input couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid input: {}", self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+// Most days' parsing was written in terms of these, long before there was a ParseError.
+impl From<io::Error> for ParseError {
+    fn from(err: io::Error) -> Self {
+        Self::new(err.to_string())
+    }
+}
+
+impl From<ParseIntError> for ParseError {
+    fn from(err: ParseIntError) -> Self {
+        Self::new(err.to_string())
+    }
+}
+
+/// A [`Solution`] with its types erased, so that all the days can be handled alike.  Get
+/// one from [`puzzle()`].
+///
+/// [`Solution`]: trait.Solution.html
+/// [`puzzle()`]: fn.puzzle.html
+pub trait Puzzle: Send + Sync {
+    /// Which day of December this solves.
+    fn day(&self) -> u32;
+
+    /// Whether the solution needs someone at the keyboard - see
+    /// [`Solution::INTERACTIVE`].
+    ///
+    /// [`Solution::INTERACTIVE`]: trait.Solution.html#associatedconstant.INTERACTIVE
+    fn interactive(&self) -> bool;
+
+    /// Parses `input` and answers both parts, timing each step, then gathers any
+    /// [`extra`] information.
+    ///
+    /// [`extra`]: trait.Solution.html#method.extra
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input isn't valid.
+    fn run(&self, input: &str) -> Result<Outcome, ParseError>;
+}
+
+/// Erases `S`'s types, giving a [`Puzzle`].
+///
+/// [`Puzzle`]: trait.Puzzle.html
+#[must_use]
+pub fn puzzle<S: Solution + 'static>() -> Box<dyn Puzzle> {
+    Box::new(Erased::<S>(PhantomData))
+}
+
+struct Erased<S>(PhantomData<fn() -> S>);
+
+impl<S: Solution> Puzzle for Erased<S> {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn interactive(&self) -> bool {
+        S::INTERACTIVE
+    }
+
+    fn run(&self, input: &str) -> Result<Outcome, ParseError> {
+        let (input, parse) = timed(|| S::parse(input));
+        let input = input?;
+        let (part1, part1_time) = timed(|| S::part1(&input));
+        let (part2, part2_time) = timed(|| S::part2(&input));
+        Ok(Outcome {
+            part1,
+            part2,
+            extra: S::extra(&input),
+            timings: Timings {
+                parse,
+                part1: part1_time,
+                part2: part2_time,
+            },
+        })
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start_time = Instant::now();
+    let result = f();
+    (result, start_time.elapsed())
+}
+
+/// The answers to a day's puzzle, and how long they took.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub part1: Answer,
+    pub part2: Answer,
+    pub extra: Vec<(&'static str, Answer)>,
+    pub timings: Timings,
+}
+
+/// How long each step of solving a day's puzzle took.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    /// The time taken for the whole thing.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter;
+
+    impl Solution for Counter {
+        const DAY: u32 = 99;
+        const INTERACTIVE: bool = true;
+        type Input = String;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            if input.contains('!') {
+                return Err(ParseError::new("no shouting"));
+            }
+            Ok(input.to_string())
+        }
+
+        fn part1(input: &Self::Input) -> Answer {
+            input.len().into()
+        }
+
+        fn part2(input: &Self::Input) -> Answer {
+            input.to_uppercase().into()
+        }
+
+        fn extra(input: &Self::Input) -> Vec<(&'static str, Answer)> {
+            vec![("letters", Answer::Image(vec![input.to_string()]))]
+        }
+    }
+
+    #[test]
+    fn puzzles_run_solutions() {
+        let puzzle = puzzle::<Counter>();
+        assert_eq!((puzzle.day(), puzzle.interactive()), (99, true));
+
+        let outcome = puzzle.run("hello").unwrap();
+        assert_eq!(outcome.part1, Answer::Number(5));
+        assert_eq!(outcome.part2, Answer::Text(String::from("HELLO")));
+        assert_eq!(
+            outcome.extra,
+            vec![("letters", Answer::Image(vec![String::from("hello")]))]
+        );
+        let timings = outcome.timings;
+        assert_eq!(
+            timings.total(),
+            timings.parse + timings.part1 + timings.part2
+        );
+
+        assert_eq!(puzzle.run("hello!"), Err(ParseError::new("no shouting")));
+    }
+
+    #[test]
+    fn answers() {
+        assert_eq!(Answer::from(42_u32), Answer::Number(42));
+        assert_eq!(Answer::from(-7_i32), Answer::Number(-7));
+        assert_eq!(Answer::from(u64::MAX), Answer::Text(u64::MAX.to_string()));
+        assert_eq!(Answer::from("ABC"), Answer::Text(String::from("ABC")));
+        assert_eq!(Answer::from(123_usize).to_string(), "123");
+        assert_eq!(Answer::None.to_string(), "-");
+        let image = Answer::Image(vec![String::from("# #"), String::from(" # ")]);
+        assert_eq!(image.to_string(), "\n# #\n # ");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err: ParseError = "x".parse::<i64>().unwrap_err().into();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: invalid digit found in string"
+        );
+        let err: ParseError = io::Error::new(io::ErrorKind::InvalidInput, "bad line").into();
+        assert_eq!(err.to_string(), "Invalid input: bad line");
+    }
+}
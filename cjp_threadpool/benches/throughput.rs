@@ -0,0 +1,107 @@
+//! Compares how many jobs per second the work-stealing pool gets through against the
+//! original design, in which every worker pulled jobs from a single shared
+//! `Mutex<Receiver>`.  Run with `cargo bench -p cjp_threadpool`.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use cjp_threadpool::ThreadPool;
+
+// The pool as it was before work stealing, cut down to what's needed to run jobs.
+mod mutex_pool {
+    use super::*;
+
+    type Job = Box<dyn FnOnce() + Send + 'static>;
+
+    pub struct ThreadPool {
+        threads: Vec<JoinHandle<()>>,
+        sender: Sender<Job>,
+    }
+
+    impl ThreadPool {
+        pub fn new(num_threads: usize) -> Self {
+            let (sender, receiver) = mpsc::channel();
+            let receiver: Arc<Mutex<Receiver<Job>>> = Arc::new(Mutex::new(receiver));
+            let threads = (0..num_threads)
+                .map(|_| {
+                    let receiver = Arc::clone(&receiver);
+                    thread::spawn(move || loop {
+                        let job = match receiver.lock().unwrap().recv() {
+                            Ok(job) => job,
+                            Err(_) => break,
+                        };
+                        job();
+                    })
+                })
+                .collect();
+            Self { threads, sender }
+        }
+
+        pub fn schedule(&self, job: Job) {
+            self.sender.send(job).unwrap();
+        }
+
+        pub fn join(self) {
+            drop(self.sender);
+            for thread in self.threads {
+                thread.join().unwrap();
+            }
+        }
+    }
+}
+
+// Stand-in for a job that takes `duration` of CPU time.  Sleeping is far too coarse for
+// the shortest jobs, so spin instead.
+fn busy_wait(duration: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        std::hint::spin_loop();
+    }
+}
+
+fn jobs_per_second(num_jobs: u32, elapsed: Duration) -> f64 {
+    f64::from(num_jobs) / elapsed.as_secs_f64()
+}
+
+fn main() {
+    let num_threads = num_cpus::get();
+    println!("{} worker threads", num_threads);
+    println!(
+        "{:>10} {:>8} {:>16} {:>17} {:>8}",
+        "job", "jobs", "mutex (jobs/s)", "stealing (jobs/s)", "speedup"
+    );
+
+    let cases = [
+        (Duration::from_micros(1), 200_000),
+        (Duration::from_micros(100), 20_000),
+        (Duration::from_millis(10), 50 * num_threads as u32),
+    ];
+    for &(duration, num_jobs) in &cases {
+        let start = Instant::now();
+        let pool = mutex_pool::ThreadPool::new(num_threads);
+        for _ in 0..num_jobs {
+            pool.schedule(Box::new(move || busy_wait(duration)));
+        }
+        pool.join();
+        let mutex = jobs_per_second(num_jobs, start.elapsed());
+
+        let start = Instant::now();
+        let pool = ThreadPool::<()>::new(num_threads).unwrap();
+        for _ in 0..num_jobs {
+            pool.schedule(Box::new(move || busy_wait(duration)));
+        }
+        pool.join();
+        let stealing = jobs_per_second(num_jobs, start.elapsed());
+
+        println!(
+            "{:>10} {:>8} {:>16.0} {:>17.0} {:>7.2}x",
+            format!("{:?}", duration),
+            num_jobs,
+            mutex,
+            stealing,
+            stealing / mutex
+        );
+    }
+}
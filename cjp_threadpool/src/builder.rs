@@ -0,0 +1,126 @@
+//! Building thread pools whose size varies with demand.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use crate::workers::Workers;
+use crate::{PoolError, ThreadPool};
+
+/// Builds a [`ThreadPool`] with a minimum and maximum number of threads.  The pool
+/// starts with the minimum, and starts more threads when work arrives while all the
+/// threads it has are busy, up to the maximum.  If it's given an idle timeout, threads
+/// beyond the minimum that have been idle for that long exit.
+///
+/// By default, the maximum is the number of logical CPUs, the minimum is the same as the
+/// maximum, and there's no idle timeout - the same as
+/// [`ThreadPool::new_with_default_size()`].
+///
+/// Alternatively, it can build a [`deterministic()`] pool, for tests.
+///
+/// # Examples
+///
+/// A pool that keeps one thread around, and uses up to eight when it's busy:
+///
+/// ```
+/// use std::time::Duration;
+/// use cjp_threadpool::ThreadPool;
+///
+/// let pool = ThreadPool::<()>::builder()
+///     .min_threads(1)
+///     .max_threads(8)
+///     .idle_timeout(Duration::from_secs(5))
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// [`ThreadPool`]: ./struct.ThreadPool.html
+/// [`ThreadPool::new_with_default_size()`]: ./struct.ThreadPool.html#method.new_with_default_size
+/// [`deterministic()`]: #method.deterministic
+#[derive(Clone, Debug)]
+pub struct ThreadPoolBuilder<T> {
+    min_threads: Option<usize>,
+    max_threads: Option<usize>,
+    idle_timeout: Option<Duration>,
+    deterministic: bool,
+    results: PhantomData<fn() -> T>,
+}
+
+impl<T: Send + 'static> ThreadPoolBuilder<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { min_threads: None, max_threads: None, idle_timeout: None, deterministic: false, results: PhantomData }
+    }
+
+    /// Sets the number of threads that the pool always keeps running.
+    #[must_use]
+    pub fn min_threads(mut self, min_threads: usize) -> Self {
+        self.min_threads = Some(min_threads);
+        self
+    }
+
+    /// Sets the most threads that the pool will run at once.
+    #[must_use]
+    pub fn max_threads(mut self, max_threads: usize) -> Self {
+        self.max_threads = Some(max_threads);
+        self
+    }
+
+    /// Sets how long a thread beyond the minimum waits for work before exiting.
+    #[must_use]
+    pub fn idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Makes the pool deterministic.  Rather than starting threads, it runs its jobs one
+    /// at a time on whichever thread calls [`ThreadPool::run_until_idle()`] or
+    /// [`ThreadPool::advance()`], or waits on one of its [`JobHandle`]s - as if it had its
+    /// maximum number of threads, but with time measured by a virtual [`Clock`].  Jobs are
+    /// started in an order that depends only on what was scheduled when, with each going
+    /// to whichever virtual thread is free soonest, so tests built on one always see the
+    /// same thing happen.
+    ///
+    /// Jobs run to completion as soon as they start, so a job can't wait for something
+    /// that only happens after it's started (a job that waits on another job's handle is
+    /// fine - the other job is run there and then).
+    ///
+    /// The minimum number of threads and the idle timeout don't apply, though the minimum
+    /// still can't be more than the maximum.
+    ///
+    /// [`ThreadPool::run_until_idle()`]: ./struct.ThreadPool.html#method.run_until_idle
+    /// [`ThreadPool::advance()`]: ./struct.ThreadPool.html#method.advance
+    /// [`JobHandle`]: ./struct.JobHandle.html
+    /// [`Clock`]: ./struct.Clock.html
+    #[must_use]
+    pub fn deterministic(mut self) -> Self {
+        self.deterministic = true;
+        self
+    }
+
+    /// Creates the pool, starting its minimum number of threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PoolError::NoThreads` if the maximum number of threads is 0, or
+    /// `PoolError::MinExceedsMax` if the minimum is more than the maximum.
+    pub fn build(self) -> Result<ThreadPool<T>, PoolError> {
+        let max_threads = self.max_threads.unwrap_or_else(num_cpus::get);
+        let min_threads = self.min_threads.unwrap_or(max_threads);
+        if max_threads == 0 {
+            return Err(PoolError::NoThreads);
+        }
+        if min_threads > max_threads {
+            return Err(PoolError::MinExceedsMax { min: min_threads, max: max_threads });
+        }
+        if self.deterministic {
+            return Ok(ThreadPool::with_workers(Workers::start_deterministic(max_threads)));
+        }
+        Ok(ThreadPool::with_workers(Workers::start(min_threads, max_threads, self.idle_timeout)))
+    }
+}
+
+impl<T: Send + 'static> Default for ThreadPoolBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
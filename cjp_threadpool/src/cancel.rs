@@ -0,0 +1,60 @@
+//! Cooperative cancellation of jobs.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+struct TokenState {
+    cancelled: AtomicBool,
+    parent: Option<CancellationToken>,
+}
+
+/// A flag shared between whoever schedules work and the jobs doing it, used to ask the
+/// jobs to stop early.  Cancellation is cooperative: a queued job whose token has been
+/// cancelled is thrown away rather than run, but a job that's already running carries on
+/// unless it checks [`is_cancelled()`] and gives up.
+///
+/// Tokens form a tree - cancelling a token also cancels all of its children, but not its
+/// parent.  Cloning a token gives another reference to the same flag.
+///
+/// [`is_cancelled()`]: #method.is_cancelled
+#[derive(Clone)]
+pub struct CancellationToken {
+    state: Arc<TokenState>,
+}
+
+impl CancellationToken {
+    /// Creates a new token, not yet cancelled, with no parent.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_parent(None)
+    }
+
+    fn with_parent(parent: Option<Self>) -> Self {
+        Self { state: Arc::new(TokenState { cancelled: AtomicBool::new(false), parent }) }
+    }
+
+    /// Creates a token that will be cancelled when this one is, but that can also be
+    /// cancelled on its own without affecting this one.
+    #[must_use]
+    pub fn child_token(&self) -> Self {
+        Self::with_parent(Some(self.clone()))
+    }
+
+    /// Cancels this token and all of its children.
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Whether this token, or any of its ancestors, has been cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Acquire) ||
+            self.state.parent.as_ref().is_some_and(CancellationToken::is_cancelled)
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
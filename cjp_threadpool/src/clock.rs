@@ -0,0 +1,64 @@
+//! Telling the time, for real or otherwise.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+enum ClockState {
+    Real(Instant),
+
+    // The current reading.  The pool moves it on as it runs jobs, and jobs move it on when
+    // they sleep.
+    Virtual(Mutex<Duration>),
+}
+
+/// The time as far as a [`ThreadPool`] is concerned, from [`ThreadPool::clock()`].
+///
+/// For most pools this is just the time since the pool was created, and sleeping is the
+/// same as `std::thread::sleep()`.  For a deterministic pool (see
+/// [`ThreadPoolBuilder::deterministic()`]) the clock is virtual: it only moves on when the
+/// pool runs jobs, and a job that sleeps returns straight away, having moved the clock on
+/// by however long it asked for.  Jobs that want to take a predictable amount of time in
+/// tests should sleep on the pool's clock rather than the real one.
+///
+/// [`ThreadPool`]: ./struct.ThreadPool.html
+/// [`ThreadPool::clock()`]: ./struct.ThreadPool.html#method.clock
+/// [`ThreadPoolBuilder::deterministic()`]: ./struct.ThreadPoolBuilder.html#method.deterministic
+#[derive(Clone)]
+pub struct Clock {
+    state: Arc<ClockState>,
+}
+
+impl Clock {
+    pub(crate) fn real() -> Self {
+        Self { state: Arc::new(ClockState::Real(Instant::now())) }
+    }
+
+    pub(crate) fn virtual_clock() -> Self {
+        Self { state: Arc::new(ClockState::Virtual(Mutex::new(Duration::ZERO))) }
+    }
+
+    /// How long it's been since the pool was created.
+    #[must_use]
+    pub fn now(&self) -> Duration {
+        match &*self.state {
+            ClockState::Real(start) => start.elapsed(),
+            ClockState::Virtual(now) => *now.lock().unwrap(),
+        }
+    }
+
+    /// Waits for `duration` - or, on a virtual clock, moves the clock on by `duration`.
+    pub fn sleep(&self, duration: Duration) {
+        match &*self.state {
+            ClockState::Real(_) => thread::sleep(duration),
+            ClockState::Virtual(now) => *now.lock().unwrap() += duration,
+        }
+    }
+
+    // Set a virtual clock's reading.
+    pub(crate) fn set(&self, time: Duration) {
+        if let ClockState::Virtual(now) = &*self.state {
+            *now.lock().unwrap() = time;
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Running futures on the pool.
+//!
+//! A spawned future is polled by whichever worker picks it up.  When it returns `Pending`,
+//! it's put aside until it's woken, at which point it's queued up to be polled again,
+//! just like any other job.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, Wake, Waker};
+
+use crate::handle::Completer;
+use crate::workers::Workers;
+use crate::{run_job, CancellationToken, JobHandle, Priority, Task, ThreadPool};
+
+type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    // Waiting to be woken.
+    Idle,
+
+    // Queued up to be polled.
+    Scheduled,
+
+    // Being polled.
+    Running,
+
+    // Woken while being polled, so it needs polling again straight afterwards.
+    Notified,
+
+    // Finished, or thrown away.
+    Done,
+}
+
+// A spawned future, and what's needed to get it polled again when it's woken.
+struct FutureTask {
+    future: Mutex<Option<BoxFuture>>,
+    state: Mutex<State>,
+    token: CancellationToken,
+
+    // Weak, so that a future that's never woken doesn't keep the workers' queues alive.
+    workers: Weak<Workers>,
+}
+
+impl FutureTask {
+    // Queue the future up to be polled.
+    fn schedule(self: Arc<Self>) {
+        let workers = match self.workers.upgrade() {
+            Some(workers) => workers,
+            None => {
+                // The pool's gone, so this is never going to finish.
+                self.abandon();
+                return;
+            }
+        };
+        let token = self.token.clone();
+        let poll = PendingPoll(Some(self));
+        workers.push(Task::new(move || poll.run(), token), Priority::Normal);
+    }
+
+    // Throw the future away.  That drops its completer, so its handle reports that it was
+    // cancelled.
+    fn abandon(&self) {
+        *self.state.lock().unwrap() = State::Done;
+        self.future.lock().unwrap().take();
+    }
+
+    // Poll the future once, returning whether it panicked.
+    fn poll(self: Arc<Self>) -> bool {
+        {
+            let mut state = self.state.lock().unwrap();
+            if *state != State::Scheduled { return false; }
+            *state = State::Running;
+        }
+
+        let waker = Waker::from(Arc::clone(&self));
+        let mut context = Context::from_waker(&waker);
+        let mut future = self.future.lock().unwrap();
+        let result = match future.as_mut() {
+            Some(running) => run_job(|| running.as_mut().poll(&mut context)),
+            None => Ok(Poll::Ready(())),
+        };
+
+        let mut state = self.state.lock().unwrap();
+        match result {
+            Ok(Poll::Pending) => {
+                if *state == State::Notified {
+                    *state = State::Scheduled;
+                    drop(state);
+                    drop(future);
+                    self.schedule();
+                } else {
+                    *state = State::Idle;
+                }
+                false
+            }
+            Ok(Poll::Ready(())) => {
+                *state = State::Done;
+                future.take();
+                false
+            }
+            Err(_) => {
+                // The panic has already been reported through the future's handle.
+                *state = State::Done;
+                future.take();
+                true
+            }
+        }
+    }
+}
+
+impl Wake for FutureTask {
+    fn wake(self: Arc<Self>) {
+        let mut state = self.state.lock().unwrap();
+        match *state {
+            State::Idle => {
+                *state = State::Scheduled;
+                drop(state);
+                self.schedule();
+            }
+            State::Running => *state = State::Notified,
+            State::Scheduled | State::Notified | State::Done => {}
+        }
+    }
+}
+
+// A poll of a future that's waiting in the queue.  If it's thrown away without being run,
+// because the pool's been cancelled, the future goes with it.
+struct PendingPoll(Option<Arc<FutureTask>>);
+
+impl PendingPoll {
+    fn run(mut self) -> bool {
+        self.0.take().is_some_and(FutureTask::poll)
+    }
+}
+
+impl Drop for PendingPoll {
+    fn drop(&mut self) {
+        if let Some(task) = self.0.take() {
+            task.abandon();
+        }
+    }
+}
+
+// Wraps a spawned future so that its output, or its panic, goes to its handle.
+async fn complete<F: Future>(future: F, completer: Completer<F::Output>) {
+    let mut future = Box::pin(future);
+    let result = std::future::poll_fn(|cx| match run_job(|| future.as_mut().poll(cx)) {
+        Ok(Poll::Pending) => Poll::Pending,
+        Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+        Err(panic) => Poll::Ready(Err(panic)),
+    }).await;
+    let panicked = result.as_ref().err().cloned();
+    completer.complete(result);
+
+    // Let the worker know, so that it's replaced like any other that's run a panicking job.
+    if let Some(panic) = panicked {
+        std::panic::resume_unwind(Box::new(panic.message));
+    }
+}
+
+impl<T: Send + 'static> ThreadPool<T> {
+    /// Runs `future` on the pool, returning a [`JobHandle`] through which you can get its
+    /// output - by blocking, or by `await`ing it from another future.
+    ///
+    /// The future is polled by the pool's workers, in between their other jobs.  Each time
+    /// it's woken it's queued up to be polled again, so it should do only a little work
+    /// per poll, and hand anything heavy to [`spawn_blocking()`].  Like other jobs, it's
+    /// thrown away if [`cancel_all()`] is called, either straight away if it's waiting to be
+    /// polled or the next time it's woken, and its handle reports `JobError::Cancelled`.
+    /// The same happens if it's still waiting to be woken once the pool's gone.
+    /// In the pool's [`stats()`], each poll counts as a job.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cjp_threadpool::ThreadPool;
+    ///
+    /// let pool = ThreadPool::<()>::new(2).unwrap();
+    /// let sum = pool.spawn_blocking(|| (1..=100).sum::<u32>());
+    /// let handle = pool.spawn(async move { sum.await.unwrap() * 2 });
+    /// assert_eq!(handle.wait(), Ok(10100));
+    /// ```
+    ///
+    /// [`JobHandle`]: ./struct.JobHandle.html
+    /// [`spawn_blocking()`]: #method.spawn_blocking
+    /// [`cancel_all()`]: #method.cancel_all
+    /// [`stats()`]: #method.stats
+    pub fn spawn<F>(&self, future: F) -> JobHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let (handle, completer) = JobHandle::new(self.workers.driver());
+        let task = Arc::new(FutureTask {
+            future: Mutex::new(Some(Box::pin(complete(future, completer)))),
+            state: Mutex::new(State::Scheduled),
+            token: self.cancellation_token(),
+            workers: Arc::downgrade(&self.workers),
+        });
+        task.schedule();
+        handle
+    }
+
+    /// Runs `job` on the pool, returning a [`JobHandle`] that can be `await`ed.  This is
+    /// for async code that has some heavy lifting to do: rather than holding up the
+    /// future that's doing it (and any others waiting for the same worker), hand it off
+    /// to the pool and `await` the result.
+    ///
+    /// This is [`schedule_with_handle()`] without the need to box the job.
+    ///
+    /// [`JobHandle`]: ./struct.JobHandle.html
+    /// [`schedule_with_handle()`]: #method.schedule_with_handle
+    pub fn spawn_blocking<F, R>(&self, job: F) -> JobHandle<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        self.schedule_with_handle(Box::new(job))
+    }
+}
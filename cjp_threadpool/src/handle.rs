@@ -0,0 +1,184 @@
+//! Handles for waiting on the result of an individual job.
+
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use crate::JobPanic;
+use crate::workers::Workers;
+
+/// Why a [`JobHandle`] couldn't produce the job's return value.
+///
+/// [`JobHandle`]: ./struct.JobHandle.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JobError {
+    /// The job panicked.
+    Panicked(JobPanic),
+
+    /// The job was thrown away without being run - or, for a future, before it finished.
+    /// That's what happens to any job still queued when [`ThreadPool::cancel_all()`] or
+    /// [`ThreadPool::terminate()`] is called, which includes scoped jobs and futures, and
+    /// to any job that's dropped before it gets to run for some other reason.
+    ///
+    /// [`ThreadPool::cancel_all()`]: ./struct.ThreadPool.html#method.cancel_all
+    /// [`ThreadPool::terminate()`]: ./struct.ThreadPool.html#method.terminate
+    Cancelled,
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JobError::Panicked(panic) => panic.fmt(f),
+            JobError::Cancelled => write!(f, "job was cancelled"),
+        }
+    }
+}
+
+impl Error for JobError {}
+
+impl From<JobPanic> for JobError {
+    fn from(panic: JobPanic) -> Self {
+        JobError::Panicked(panic)
+    }
+}
+
+struct SlotState<T> {
+    result: Option<Result<T, JobError>>,
+    finished: bool,
+    waker: Option<Waker>,
+}
+
+// Where a job's result is left for its handle to pick up.
+struct Slot<T> {
+    state: Mutex<SlotState<T>>,
+    finished: Condvar,
+}
+
+impl<T> Slot<T> {
+    fn fill(&self, result: Result<T, JobError>) {
+        let mut state = self.state.lock().unwrap();
+        state.result = Some(result);
+        state.finished = true;
+        self.finished.notify_all();
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Fills in a job's slot with its result - or, if it's dropped without that happening
+/// because the job never got to run, with `JobError::Cancelled`.
+pub(crate) struct Completer<T> {
+    slot: Option<Arc<Slot<T>>>,
+}
+
+impl<T> Completer<T> {
+    pub(crate) fn complete(mut self, result: Result<T, JobPanic>) {
+        if let Some(slot) = self.slot.take() {
+            slot.fill(result.map_err(JobError::from));
+        }
+    }
+}
+
+impl<T> Drop for Completer<T> {
+    fn drop(&mut self) {
+        if let Some(slot) = self.slot.take() {
+            slot.fill(Err(JobError::Cancelled));
+        }
+    }
+}
+
+/// A handle on a single job scheduled with [`ThreadPool::schedule_with_handle()`], from
+/// which you can get the job's return value.  You can block waiting for it, check whether
+/// it's ready yet, or `await` it - `JobHandle` is a `Future`.
+///
+/// [`ThreadPool::schedule_with_handle()`]: ./struct.ThreadPool.html#method.schedule_with_handle
+pub struct JobHandle<T> {
+    slot: Arc<Slot<T>>,
+
+    // The deterministic pool that the job's on, if it is, which needs us to run its jobs.
+    driver: Option<Arc<Workers>>,
+}
+
+impl<T> JobHandle<T> {
+    pub(crate) fn new(driver: Option<Arc<Workers>>) -> (Self, Completer<T>) {
+        let slot = Arc::new(Slot {
+            state: Mutex::new(SlotState {
+                result: None,
+                finished: false,
+                waker: None,
+            }),
+            finished: Condvar::new(),
+        });
+        (
+            Self {
+                slot: Arc::clone(&slot),
+                driver,
+            },
+            Completer { slot: Some(slot) },
+        )
+    }
+
+    /// Block the current thread until the job has finished, and return its result.  If
+    /// the job's on a deterministic pool, this runs the pool's jobs until it's done.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the job panicked or was cancelled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result has already been taken by [`try_wait()`].
+    ///
+    /// [`try_wait()`]: #method.try_wait
+    pub fn wait(self) -> Result<T, JobError> {
+        let mut state = self.slot.state.lock().unwrap();
+        while !state.finished {
+            state = match &self.driver {
+                // Nobody else is going to run the job, so run the pool's jobs until it's
+                // done.  If there's nothing to run, something outside the pool might still
+                // queue a job up (by waking one of its futures), so keep checking.
+                Some(workers) => {
+                    drop(state);
+                    let ran = workers.step(None);
+                    let state = self.slot.state.lock().unwrap();
+                    if ran { state } else { self.slot.finished.wait_timeout(state, Duration::from_millis(1)).unwrap().0 }
+                }
+                None => self.slot.finished.wait(state).unwrap(),
+            };
+        }
+        state.result.take().expect("Job result has already been taken")
+    }
+
+    /// Return the job's result if it's finished, without blocking.  Once this has
+    /// returned the result, the handle is spent, and subsequent calls return `None`.
+    pub fn try_wait(&mut self) -> Option<Result<T, JobError>> {
+        self.slot.state.lock().unwrap().result.take()
+    }
+
+    /// Whether the job has finished (successfully or otherwise).
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.slot.state.lock().unwrap().finished
+    }
+}
+
+impl<T> Future for JobHandle<T> {
+    type Output = Result<T, JobError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let mut state = self.slot.state.lock().unwrap();
+        if let Some(result) = state.result.take() {
+            Poll::Ready(result)
+        } else if state.finished {
+            panic!("Job result has already been taken");
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
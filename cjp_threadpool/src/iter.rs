@@ -0,0 +1,194 @@
+//! Parallel versions of everyday iterator operations, built on top of the pool.
+
+use std::sync::Arc;
+
+use crate::{run_job, JobHandle, ThreadPool};
+
+// Aim for this many chunks per worker, so that a worker that gets through its share
+// quickly can pick up the slack from one that doesn't.
+const CHUNKS_PER_WORKER: usize = 4;
+
+// Chunks never get bigger than this, so that searches don't overshoot by too much.
+const MAX_CHUNK_SIZE: usize = 1024;
+
+impl<T: Send + 'static> ThreadPool<T> {
+    /// Runs `f` on every item of `inputs` across the pool, and returns the results in the
+    /// same order as the inputs.
+    ///
+    /// The inputs are split into chunks, with a job per chunk rather than per item, so
+    /// that the cost of scheduling is spread across many items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cjp_threadpool::ThreadPool;
+    ///
+    /// let pool = ThreadPool::<()>::new(4).unwrap();
+    /// assert_eq!(pool.map(1..=5, |n| n * n), vec![1, 4, 9, 16, 25]);
+    /// ```
+    pub fn map<A, R, I, F>(&self, inputs: I, f: F) -> Vec<R>
+    where
+        A: Send + 'static,
+        R: Send + 'static,
+        I: IntoIterator<Item = A>,
+        F: Fn(A) -> R + Send + Sync + 'static,
+    {
+        self.run_chunks(inputs, move |chunk| chunk.into_iter().map(&f).collect::<Vec<R>>())
+            .into_iter()
+            .flat_map(wait)
+            .collect()
+    }
+
+    /// Runs `f` on every item of `inputs` across the pool, returning once they've all
+    /// been done.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` panics.
+    pub fn for_each<A, I, F>(&self, inputs: I, f: F)
+    where
+        A: Send + 'static,
+        I: IntoIterator<Item = A>,
+        F: Fn(A) + Send + Sync + 'static,
+    {
+        self.run_chunks(inputs, move |chunk| chunk.into_iter().for_each(&f))
+            .into_iter()
+            .for_each(wait);
+    }
+
+    /// Like [`map()`], but keeps only the results that `f` returns as `Some`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cjp_threadpool::ThreadPool;
+    ///
+    /// let pool = ThreadPool::<()>::new(4).unwrap();
+    /// let odd_squares = pool.filter_map(1..=5, |n| if n % 2 == 1 { Some(n * n) } else { None });
+    /// assert_eq!(odd_squares, vec![1, 9, 25]);
+    /// ```
+    ///
+    /// [`map()`]: #method.map
+    pub fn filter_map<A, R, I, F>(&self, inputs: I, f: F) -> Vec<R>
+    where
+        A: Send + 'static,
+        R: Send + 'static,
+        I: IntoIterator<Item = A>,
+        F: Fn(A) -> Option<R> + Send + Sync + 'static,
+    {
+        self.run_chunks(inputs, move |chunk| chunk.into_iter().filter_map(&f).collect::<Vec<R>>())
+            .into_iter()
+            .flat_map(wait)
+            .collect()
+    }
+
+    /// Runs `map` on every item of `inputs` across the pool, and folds the results
+    /// together with `combine`, returning `None` if there weren't any inputs.  `combine`
+    /// is applied within each chunk and then across chunks, so it needs to be
+    /// associative, but needn't be commutative.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `map` or `combine` panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cjp_threadpool::ThreadPool;
+    ///
+    /// let pool = ThreadPool::<()>::new(4).unwrap();
+    /// assert_eq!(pool.reduce(1..=100, |n| n, |a, b| a + b), Some(5050));
+    /// assert_eq!(pool.reduce(0..0, |n| n, |a, b| a + b), None);
+    /// ```
+    pub fn reduce<A, R, I, M, C>(&self, inputs: I, map: M, combine: C) -> Option<R>
+    where
+        A: Send + 'static,
+        R: Send + 'static,
+        I: IntoIterator<Item = A>,
+        M: Fn(A) -> R + Send + Sync + 'static,
+        C: Fn(R, R) -> R + Send + Sync + 'static,
+    {
+        let combine = Arc::new(combine);
+        let job_combine = Arc::clone(&combine);
+        self.run_chunks(inputs, move |chunk| chunk.into_iter().map(&map).reduce(|a, b| job_combine(a, b)))
+            .into_iter()
+            .filter_map(wait)
+            .reduce(|a, b| combine(a, b))
+    }
+
+    /// Returns an item from `inputs` that satisfies `predicate`, or `None` if none do.
+    /// Works like [`find_first()`], so the inputs are consumed lazily, the search stops as
+    /// soon as a match is found, and if several items match, which one you get is down
+    /// to timing.  An item for which `predicate` panics is treated as not matching.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cjp_threadpool::ThreadPool;
+    ///
+    /// let pool = ThreadPool::<()>::new(4).unwrap();
+    /// assert_eq!(pool.find_any(1.., |n| n * n == 144), Some(12));
+    /// ```
+    ///
+    /// [`find_first()`]: #method.find_first
+    pub fn find_any<A, I, P>(&self, inputs: I, predicate: P) -> Option<A>
+    where
+        A: Send + 'static,
+        I: IntoIterator<Item = A>,
+        P: Fn(&A) -> bool + Send + Sync + 'static,
+    {
+        let mut inputs = inputs.into_iter();
+        let chunk_size = self.chunk_size(inputs.size_hint().0);
+        let chunks = std::iter::from_fn(move || {
+            let chunk: Vec<A> = inputs.by_ref().take(chunk_size).collect();
+            if chunk.is_empty() { None } else { Some(chunk) }
+        });
+        self.find_first(chunks, move |chunk, token| {
+            for item in chunk {
+                if token.is_cancelled() { return None; }
+                if run_job(|| predicate(&item)).unwrap_or(false) { return Some(item); }
+            }
+            None
+        })
+    }
+
+    fn chunk_size(&self, num_items: usize) -> usize {
+        (num_items / (self.workers.max_threads() * CHUNKS_PER_WORKER)).clamp(1, MAX_CHUNK_SIZE)
+    }
+
+    // Split `inputs` into chunks, and schedule a job to run `process` on each one.
+    fn run_chunks<A, R, I, F>(&self, inputs: I, process: F) -> Vec<JobHandle<R>>
+    where
+        A: Send + 'static,
+        R: Send + 'static,
+        I: IntoIterator<Item = A>,
+        F: Fn(Vec<A>) -> R + Send + Sync + 'static,
+    {
+        // We're going to consume all of the inputs anyway, so collect them up front to
+        // find out how many there are.
+        let inputs: Vec<A> = inputs.into_iter().collect();
+        let chunk_size = self.chunk_size(inputs.len());
+        let process = Arc::new(process);
+        let mut inputs = inputs.into_iter();
+        let mut handles = Vec::new();
+        loop {
+            let chunk: Vec<A> = inputs.by_ref().take(chunk_size).collect();
+            if chunk.is_empty() { break handles; }
+            let process = Arc::clone(&process);
+            handles.push(self.schedule_with_handle(Box::new(move || process(chunk))));
+        }
+    }
+}
+
+// Wait for a chunk to finish, passing on any panic.
+fn wait<R>(handle: JobHandle<R>) -> R {
+    handle.wait().unwrap_or_else(|err| panic!("{}", err))
+}
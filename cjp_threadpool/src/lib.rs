@@ -1,11 +1,55 @@
 //! A crate containing my practice implementation of a variable size thread pool
 //! for executing functions in parallel.  Spawns a specified number of worker
-//! threads, but will panic if any of them panic.
+//! threads.
 //! 
 //! After creating the [`ThreadPool`], give it work by passing boxed closures to 
 //! [`schedule()`]. If your closures return a value, you can access the returned 
 //! values through the [`results`] channel on the [`ThreadPool`].
 //! 
+//! The pool needn't stay the same size: [`resize()`] it, or create it with a
+//! [`ThreadPoolBuilder`] to have it start and stop threads as the amount of work
+//! varies.  Jobs that are more or less urgent than the rest can be given a [`Priority`]
+//! with [`schedule_with_priority()`].
+//! 
+//! Jobs are dealt out to the workers in turn, each of which has its own queue, so that
+//! they aren't all fighting over one lock to get their next job.  A worker that runs out
+//! of work steals half of another's queue.
+//! 
+//! If you'd rather deal with each job's result individually, [`schedule_with_handle()`]
+//! gives you a [`JobHandle`] for it, which you can wait on or `await`.  Alternatively,
+//! [`schedule_tagged()`] gives each job an ID, which accompanies its result on the
+//! [`tagged_results`] channel.
+//! 
+//! A job that panics doesn't take the pool down with it: the panic is caught, and
+//! reported on the [`results`] channel as a [`JobPanic`] in place of the job's
+//! return value.  The worker thread it was running on is replaced with a fresh one.
+//! 
+//! Jobs can be cancelled cooperatively through a [`CancellationToken`]: [`cancel_all()`]
+//! throws away everything still queued and tells running jobs to give up.  [`find_first()`]
+//! builds on that to search a set of inputs in parallel, stopping as soon as one of them
+//! turns up an answer.
+//! 
+//! For the common cases, you needn't box up closures or count results yourself: [`map()`],
+//! [`for_each()`], [`filter_map()`], [`reduce()`] and [`find_any()`] do the same as their
+//! `Iterator` namesakes, but spread across the pool.
+//! 
+//! Jobs normally have to be `'static`, so anything they need has to be moved or cloned
+//! into them.  Within a [`scope()`], jobs can borrow from the caller instead - the scope
+//! doesn't end until they've all finished.
+//! 
+//! The pool can also run futures: [`spawn()`] one, and the workers poll it in between
+//! their other jobs.  Async code with heavy lifting to do can hand it to
+//! [`spawn_blocking()`] and `await` the result, without needing a separate runtime.
+//! 
+//! To see how busy the pool is, take a [`stats()`] snapshot of how many jobs are queued,
+//! running and done, how long they've waited and taken, and how much work each worker
+//! has done - or have one reported to you regularly with [`report_stats()`].
+//! 
+//! For tests, a [`deterministic()`] pool has no threads of its own.  It runs its jobs on
+//! whichever thread asks it to, one at a time, in an order that only depends on what was
+//! scheduled when, against a virtual [`Clock`] - so tests can check what happens when
+//! without sleeping and hoping.
+//! 
 //! # Examples
 //! 
 //! ```
@@ -15,41 +59,216 @@
 //! let job = Box::new(move || 1 + 1);
 //! for _ in 0..24 { pool.schedule(job.clone()); }
 //! 
-//! for _ in 0..24 { assert_eq!(Ok(2), pool.results.recv()); }
+//! for _ in 0..24 { assert_eq!(Ok(Ok(2)), pool.results.recv()); }
 //! ```
 //! 
 //! [`ThreadPool`]: ./struct.ThreadPool.html
+//! [`JobPanic`]: ./struct.JobPanic.html
 //! [`schedule()`]: ./struct.ThreadPool.html#method.schedule
 //! [`results`]: ./struct.ThreadPool.html#structfield.results
+//! [`schedule_with_handle()`]: ./struct.ThreadPool.html#method.schedule_with_handle
+//! [`JobHandle`]: ./struct.JobHandle.html
+//! [`schedule_tagged()`]: ./struct.ThreadPool.html#method.schedule_tagged
+//! [`tagged_results`]: ./struct.ThreadPool.html#structfield.tagged_results
+//! [`CancellationToken`]: ./struct.CancellationToken.html
+//! [`cancel_all()`]: ./struct.ThreadPool.html#method.cancel_all
+//! [`find_first()`]: ./struct.ThreadPool.html#method.find_first
+//! [`map()`]: ./struct.ThreadPool.html#method.map
+//! [`for_each()`]: ./struct.ThreadPool.html#method.for_each
+//! [`filter_map()`]: ./struct.ThreadPool.html#method.filter_map
+//! [`reduce()`]: ./struct.ThreadPool.html#method.reduce
+//! [`find_any()`]: ./struct.ThreadPool.html#method.find_any
+//! [`scope()`]: ./struct.ThreadPool.html#method.scope
+//! [`resize()`]: ./struct.ThreadPool.html#method.resize
+//! [`ThreadPoolBuilder`]: ./struct.ThreadPoolBuilder.html
+//! [`Priority`]: ./enum.Priority.html
+//! [`schedule_with_priority()`]: ./struct.ThreadPool.html#method.schedule_with_priority
+//! [`stats()`]: ./struct.ThreadPool.html#method.stats
+//! [`spawn()`]: ./struct.ThreadPool.html#method.spawn
+//! [`spawn_blocking()`]: ./struct.ThreadPool.html#method.spawn_blocking
+//! [`report_stats()`]: ./struct.ThreadPool.html#method.report_stats
+//! [`deterministic()`]: ./struct.ThreadPoolBuilder.html#method.deterministic
+//! [`Clock`]: ./struct.Clock.html
 
 #![crate_name = "cjp_threadpool"]
 #![crate_type = "lib"]
 
 extern crate num_cpus;
 
-use std::thread::{self, JoinHandle};
+mod builder;
+mod cancel;
+mod clock;
+mod executor;
+mod handle;
+mod iter;
+mod scope;
+mod stats;
+mod workers;
+
+pub use builder::ThreadPoolBuilder;
+pub use cancel::CancellationToken;
+pub use clock::Clock;
+pub use handle::{JobError, JobHandle};
+pub use scope::Scope;
+pub use stats::{Histogram, PoolStats, WorkerStats};
+
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::{Arc, Mutex, mpsc::{self, Sender, Receiver}};
+use stats::Reporter;
+use workers::Workers;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
 type Job<T> = Box<dyn FnOnce() -> T + Send + 'static>;
 
+// What the workers actually execute: a job, wrapped up with whatever needs to happen to its
+// result, plus the token saying whether it's still wanted, and when it was queued (by the
+// pool's clock).  Running it returns whether the job panicked.
+pub(crate) struct Task {
+    run: Box<dyn FnOnce() -> bool + Send + 'static>,
+    token: CancellationToken,
+    queued_at: Duration,
+}
+
+impl Task {
+    fn new(run: impl FnOnce() -> bool + Send + 'static, token: CancellationToken) -> Self {
+        Self { run: Box::new(run), token, queued_at: Duration::ZERO }
+    }
+}
+
+/// Identifies a job scheduled with [`ThreadPool::schedule_tagged()`].
+/// 
+/// [`ThreadPool::schedule_tagged()`]: ./struct.ThreadPool.html#method.schedule_tagged
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JobId(u64);
+
+/// Reported on the [`results`] channel, in place of a job's return value, when the
+/// job panics.
+/// 
+/// [`results`]: ./struct.ThreadPool.html#structfield.results
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JobPanic {
+    /// The message the job panicked with.
+    pub message: String,
+}
+
+impl JobPanic {
+    fn from_payload(payload: &(dyn Any + Send)) -> Self {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "<non-string panic payload>".to_string()
+        };
+        Self { message }
+    }
+}
+
+impl fmt::Display for JobPanic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "job panicked: {}", self.message)
+    }
+}
+
+impl Error for JobPanic {}
+
+/// Why a [`ThreadPool`] couldn't be created or resized.
+/// 
+/// [`ThreadPool`]: ./struct.ThreadPool.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PoolError {
+    /// The pool was asked to have no threads at all, so it would never do anything.
+    NoThreads,
+
+    /// The pool's minimum number of threads was more than its maximum.
+    MinExceedsMax { min: usize, max: usize },
+}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PoolError::NoThreads => write!(f, "a thread pool needs at least one thread"),
+            PoolError::MinExceedsMax { min, max } => {
+                write!(f, "minimum of {} threads is more than the maximum of {}", min, max)
+            }
+        }
+    }
+}
+
+impl Error for PoolError {}
+
+/// How urgent a job is.  Workers pick up high priority jobs before anything else, and low
+/// priority jobs only when there's nothing else to do.  Jobs of the same priority are run
+/// roughly in the order that they were scheduled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+// Run a job, catching any panic.
+fn run_job<R>(job: impl FnOnce() -> R) -> Result<R, JobPanic> {
+    panic::catch_unwind(AssertUnwindSafe(job)).map_err(|payload| JobPanic::from_payload(payload.as_ref()))
+}
+
+// How a job run by `find_first()` reports back.  If the job is dropped without being run,
+// this reports `None` on its behalf, so that `find_first()` isn't left waiting for it.
+struct Reply<R> {
+    sender: Option<Sender<Option<R>>>,
+}
+
+impl<R> Reply<R> {
+    fn send(mut self, result: Option<R>) {
+        if let Some(sender) = self.sender.take() {
+            let _ = sender.send(result);
+        }
+    }
+}
+
+impl<R> Drop for Reply<R> {
+    fn drop(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            let _ = sender.send(None);
+        }
+    }
+}
+
 /// A thread pool that owns a number of worker threads and can schedule work across
 /// them.
 pub struct ThreadPool<T> {
-    threads: Vec<Worker>,
-    inbound_work_sender: Sender<Job<T>>,
-    inbound_work_receiver: Arc<Mutex<Receiver<Job<T>>>>,
-    /// The receive half of a channel on which the return values of scheduled jobs
-    /// will be sent.
-    pub results: Receiver<T>,
+    workers: Arc<Workers>,
+    cancellation: Mutex<CancellationToken>,
+    result_sender: Sender<Result<T, JobPanic>>,
+    tagged_result_sender: Sender<(JobId, Result<T, JobPanic>)>,
+    next_job_id: AtomicU64,
+    reporters: Mutex<Vec<Reporter>>,
+    /// The receive half of a channel on which the return values of jobs passed to
+    /// [`schedule()`] will be sent - or, for jobs that panicked, what they panicked with.
+    /// 
+    /// [`schedule()`]: #method.schedule
+    pub results: Receiver<Result<T, JobPanic>>,
+    /// Like [`results`], but for jobs passed to [`schedule_tagged()`], with each result
+    /// accompanied by the ID of the job that produced it.
+    /// 
+    /// [`results`]: #structfield.results
+    /// [`schedule_tagged()`]: #method.schedule_tagged
+    pub tagged_results: Receiver<(JobId, Result<T, JobPanic>)>,
 }
 
 impl<T: Send + 'static> ThreadPool<T> {
     /// Creates a new thread pool capable of executing `num_threads` number of jobs
-    /// concurrently.
+    /// concurrently.  For more control over how many threads there are, see
+    /// [`builder()`].
     /// 
-    /// # Panics
+    /// # Errors
     /// 
-    /// This function will panic if `num_threads` is 0.
+    /// Returns `PoolError::NoThreads` if `num_threads` is 0.
     /// 
     /// # Examples
     /// 
@@ -59,23 +278,36 @@ impl<T: Send + 'static> ThreadPool<T> {
     /// ```
     /// use cjp_threadpool::ThreadPool;
     /// 
-    /// let pool = ThreadPool::<()>::new(4);
+    /// let pool = ThreadPool::<()>::new(4).unwrap();
     /// ```
+    /// 
+    /// [`builder()`]: #method.builder
+    pub fn new(num_threads: usize) -> Result<Self, PoolError> {
+        ThreadPoolBuilder::new().max_threads(num_threads).build()
+    }
+
+    /// Returns a [`ThreadPoolBuilder`], through which you can create a pool whose number
+    /// of threads varies with how much work there is.
+    /// 
+    /// [`ThreadPoolBuilder`]: ./struct.ThreadPoolBuilder.html
     #[must_use]
-    pub fn new(num_threads: usize) -> Self {
-        let (inbound_work_sender, inbound_work_receiver) = mpsc::channel();
+    pub fn builder() -> ThreadPoolBuilder<T> {
+        ThreadPoolBuilder::new()
+    }
+
+    fn with_workers(workers: Arc<Workers>) -> Self {
         let (result_sender, results) = mpsc::channel();
-        let receiver = Arc::new(Mutex::new(inbound_work_receiver));
-        let mut pool = Self {
-            threads: Vec::with_capacity(num_threads),
-            inbound_work_sender,
-            inbound_work_receiver: Arc::clone(&receiver),
+        let (tagged_result_sender, tagged_results) = mpsc::channel();
+        Self {
+            workers,
+            cancellation: Mutex::new(CancellationToken::new()),
+            result_sender,
+            tagged_result_sender,
+            next_job_id: AtomicU64::new(0),
+            reporters: Mutex::new(Vec::new()),
             results,
-        };
-        for _ in 0..num_threads {
-            pool.threads.push(Worker::new(Arc::clone(&receiver), result_sender.clone()));
+            tagged_results,
         }
-        pool
     }
 
     /// Creates a new thread pool with capacity equal to the number of logical
@@ -93,7 +325,38 @@ impl<T: Send + 'static> ThreadPool<T> {
     /// ```
     #[must_use]
     pub fn new_with_default_size() -> Self {
-        Self::new(num_cpus::get())
+        Self::new(num_cpus::get()).expect("There's always at least one CPU")
+    }
+
+    /// The number of threads currently in the pool.
+    #[must_use]
+    pub fn num_threads(&self) -> usize {
+        self.workers.live_threads()
+    }
+
+    /// Changes the number of threads in the pool to `num_threads`, replacing any minimum
+    /// and maximum that it was built with.  New threads start straight away; surplus
+    /// threads finish the job they're running, if any, and then exit.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns `PoolError::NoThreads`, leaving the pool as it was, if `num_threads` is 0.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use cjp_threadpool::ThreadPool;
+    /// 
+    /// let pool = ThreadPool::<()>::new(2).unwrap();
+    /// pool.resize(8).unwrap();
+    /// assert!(pool.resize(0).is_err());
+    /// ```
+    pub fn resize(&self, num_threads: usize) -> Result<(), PoolError> {
+        if num_threads == 0 {
+            return Err(PoolError::NoThreads);
+        }
+        self.workers.resize(num_threads, num_threads);
+        Ok(())
     }
 
     /// Queues the function `job` for execution on a thread in the pool.
@@ -105,119 +368,1089 @@ impl<T: Send + 'static> ThreadPool<T> {
     /// ```
     /// use cjp_threadpool::ThreadPool;
     /// 
-    /// let pool = ThreadPool::new(2);
+    /// let pool = ThreadPool::new(2).unwrap();
     /// let job = Box::new(move || 1 + 1);
     /// for _ in 0..4 { pool.schedule(job.clone()); }
     /// pool.join();
     /// ```
     pub fn schedule(&self, job: Job<T>) {
-        self.inbound_work_sender.send(job).unwrap();
+        self.schedule_with_priority(job, Priority::Normal);
+    }
+
+    /// Like [`schedule()`], but with the given [`Priority`] rather than the default of
+    /// `Priority::Normal`.
+    /// 
+    /// # Examples
+    /// 
+    /// Jump the queue:
+    /// 
+    /// ```
+    /// use cjp_threadpool::{Priority, ThreadPool};
+    /// 
+    /// let pool = ThreadPool::new(1).unwrap();
+    /// let (started_tx, started_rx) = std::sync::mpsc::channel();
+    /// pool.schedule(Box::new(move || {
+    ///     started_tx.send(()).unwrap();
+    ///     std::thread::sleep(std::time::Duration::from_millis(50));
+    ///     "first"
+    /// }));
+    /// started_rx.recv().unwrap();
+    /// pool.schedule(Box::new(|| "normal"));
+    /// pool.schedule_with_priority(Box::new(|| "urgent"), Priority::High);
+    /// let results: Vec<_> = (0..3).map(|_| pool.results.recv().unwrap().unwrap()).collect();
+    /// assert_eq!(results, vec!["first", "urgent", "normal"]);
+    /// ```
+    /// 
+    /// [`schedule()`]: #method.schedule
+    /// [`Priority`]: ./enum.Priority.html
+    pub fn schedule_with_priority(&self, job: Job<T>, priority: Priority) {
+        let result_sender = self.result_sender.clone();
+        self.submit_with_priority(priority, move || {
+            let result = run_job(job);
+            let panicked = result.is_err();
+            let _ = result_sender.send(result);
+            panicked
+        });
     }
 
-    /// Block the current thread until all jobs in the pool have been executed.
-    pub fn join(self) {
-        drop(self.inbound_work_sender);
+    /// Like [`schedule()`], but the job's result is sent on the [`tagged_results`]
+    /// channel along with the returned ID, so that you can tell which job it came from.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use cjp_threadpool::ThreadPool;
+    /// 
+    /// let pool = ThreadPool::new(2).unwrap();
+    /// let small = pool.schedule_tagged(Box::new(|| 1 + 1));
+    /// let large = pool.schedule_tagged(Box::new(|| 100 + 100));
+    /// for _ in 0..2 {
+    ///     let (id, result) = pool.tagged_results.recv().unwrap();
+    ///     assert_eq!(result, Ok(if id == small { 2 } else { 200 }));
+    /// }
+    /// # let _ = large;
+    /// ```
+    /// 
+    /// [`schedule()`]: #method.schedule
+    /// [`tagged_results`]: #structfield.tagged_results
+    pub fn schedule_tagged(&self, job: Job<T>) -> JobId {
+        let id = JobId(self.next_job_id.fetch_add(1, Ordering::Relaxed));
+        let result_sender = self.tagged_result_sender.clone();
+        self.submit(move || {
+            let result = run_job(job);
+            let panicked = result.is_err();
+            let _ = result_sender.send((id, result));
+            panicked
+        });
+        id
+    }
 
-        // Join all worker threads.
-        for worker in self.threads {
-            worker.thread.join().unwrap();
+    /// Queues the function `job` for execution on a thread in the pool, returning a
+    /// [`JobHandle`] through which you can get its result.  The result doesn't go on
+    /// the [`results`] channel, so it needn't be the same type as the pool's other jobs.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use cjp_threadpool::ThreadPool;
+    /// 
+    /// let pool = ThreadPool::<()>::new(2).unwrap();
+    /// let handle = pool.schedule_with_handle(Box::new(|| "hello".len()));
+    /// assert_eq!(handle.wait(), Ok(5));
+    /// ```
+    /// 
+    /// [`JobHandle`]: ./struct.JobHandle.html
+    /// [`results`]: #structfield.results
+    pub fn schedule_with_handle<R: Send + 'static>(&self, job: Box<dyn FnOnce() -> R + Send + 'static>) -> JobHandle<R> {
+        let (handle, completer) = JobHandle::new(self.workers.driver());
+        self.submit(move || {
+            let result = run_job(job);
+            let panicked = result.is_err();
+            completer.complete(result);
+            panicked
+        });
+        handle
+    }
+
+    /// Returns the token that jobs scheduled from now on are bound to.  Jobs can check it
+    /// to find out whether [`cancel_all()`] has been called since they were scheduled, and
+    /// stop early if so.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use cjp_threadpool::ThreadPool;
+    /// 
+    /// let pool = ThreadPool::<()>::new(1).unwrap();
+    /// let token = pool.cancellation_token();
+    /// let (started_tx, started_rx) = std::sync::mpsc::channel();
+    /// let handle = pool.schedule_with_handle(Box::new(move || {
+    ///     started_tx.send(()).unwrap();
+    ///     while !token.is_cancelled() { std::thread::yield_now(); }
+    /// }));
+    /// started_rx.recv().unwrap();
+    /// pool.cancel_all();
+    /// assert!(handle.wait().is_ok());
+    /// ```
+    /// 
+    /// [`cancel_all()`]: #method.cancel_all
+    #[must_use]
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.lock().unwrap().clone()
+    }
+
+    /// Cancels every job scheduled so far.  Those still in the queue are thrown away
+    /// without being run (so they produce no result, and their handles report
+    /// `JobError::Cancelled`); those already running see their [`cancellation_token()`]
+    /// become cancelled.  Jobs scheduled afterwards aren't affected.
+    /// 
+    /// [`cancellation_token()`]: #method.cancellation_token
+    pub fn cancel_all(&self) {
+        let mut token = self.cancellation.lock().unwrap();
+        token.cancel();
+        *token = CancellationToken::new();
+    }
+
+    /// Runs `f` on each of `inputs` across the pool, and returns the first `Some` that any
+    /// of them produces, or `None` if none do.  Inputs are only handed to the pool a few at
+    /// a time, and as soon as an answer turns up no more are scheduled, any that are queued
+    /// are dropped, and the token passed to `f` is cancelled so that those still running
+    /// can give up.  An `f` that panics is treated as having returned `None`.
+    /// 
+    /// "First" means first to finish, so if several inputs produce `Some`, which one
+    /// you get back is down to timing.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use cjp_threadpool::ThreadPool;
+    /// 
+    /// let pool = ThreadPool::<()>::new(4).unwrap();
+    /// let root = pool.find_first(0..1_000_000_u64, |n, _token| if n * n == 144 { Some(n) } else { None });
+    /// assert_eq!(root, Some(12));
+    /// ```
+    pub fn find_first<A, R, I, F>(&self, inputs: I, f: F) -> Option<R>
+    where
+        A: Send + 'static,
+        R: Send + 'static,
+        I: IntoIterator<Item = A>,
+        F: Fn(A, &CancellationToken) -> Option<R> + Send + Sync + 'static,
+    {
+        let token = self.cancellation_token().child_token();
+        let f = Arc::new(f);
+        let (sender, receiver) = mpsc::channel();
+        let max_in_flight = self.workers.max_threads() * 2;
+        let mut inputs = inputs.into_iter();
+        let mut in_flight = 0;
+
+        loop {
+            // Keep every worker busy, with something queued behind it, but no more than
+            // that - there's no point queueing work that's about to be cancelled.
+            while in_flight < max_in_flight {
+                let input = match inputs.next() {
+                    Some(input) => input,
+                    None => break,
+                };
+                let f = Arc::clone(&f);
+                let job_token = token.clone();
+                let reply = Reply { sender: Some(sender.clone()) };
+                self.submit_with_token(token.clone(), move || {
+                    let result = run_job(|| f(input, &job_token));
+                    let panicked = result.is_err();
+                    reply.send(result.unwrap_or(None));
+                    panicked
+                });
+                in_flight += 1;
+            }
+
+            if in_flight == 0 {
+                return None;
+            }
+            in_flight -= 1;
+            if let Some(answer) = self.receive(&receiver) {
+                token.cancel();
+                return Some(answer);
+            }
         }
     }
 
-    /// Like [`join()`], but drops any pending jobs that aren't already mid-execution.
+    // Wait for a message from a job - running jobs until it turns up, if this is a
+    // deterministic pool.
+    fn receive<M>(&self, receiver: &Receiver<M>) -> M {
+        loop {
+            if let Ok(message) = receiver.try_recv() {
+                return message;
+            }
+            if !self.workers.is_deterministic() {
+                return receiver.recv().unwrap();
+            }
+            // As in `JobHandle::wait()`, something outside the pool might still queue a job.
+            if !self.workers.step(None) {
+                if let Ok(message) = receiver.recv_timeout(Duration::from_millis(1)) {
+                    return message;
+                }
+            }
+        }
+    }
+
+    fn submit(&self, run: impl FnOnce() -> bool + Send + 'static) {
+        self.submit_with_priority(Priority::Normal, run);
+    }
+
+    fn submit_with_priority(&self, priority: Priority, run: impl FnOnce() -> bool + Send + 'static) {
+        self.workers.push(Task::new(run, self.cancellation_token()), priority);
+    }
+
+    fn submit_with_token(&self, token: CancellationToken, run: impl FnOnce() -> bool + Send + 'static) {
+        self.workers.push(Task::new(run, token), Priority::Normal);
+    }
+
+    /// Returns the pool's [`Clock`], which is virtual if the pool is deterministic.
+    /// 
+    /// [`Clock`]: ./struct.Clock.html
+    #[must_use]
+    pub fn clock(&self) -> Clock {
+        self.workers.clock().clone()
+    }
+
+    /// Runs a deterministic pool's jobs until there are none left, including any that
+    /// they schedule, and moves its clock on to when the last of them finishes.  A job
+    /// doesn't produce its result until it's been run like this (or by [`advance()`], or by
+    /// waiting on its handle).
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the pool isn't deterministic.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use std::time::Duration;
+    /// use cjp_threadpool::ThreadPool;
+    /// 
+    /// let pool = ThreadPool::builder().max_threads(2).deterministic().build().unwrap();
+    /// for i in 0..4 {
+    ///     let clock = pool.clock();
+    ///     pool.schedule(Box::new(move || {
+    ///         clock.sleep(Duration::from_millis(100));
+    ///         i
+    ///     }));
+    /// }
+    /// assert!(pool.results.try_recv().is_err());
+    /// 
+    /// pool.run_until_idle();
+    /// let results: Vec<_> = pool.results.try_iter().map(Result::unwrap).collect();
+    /// assert_eq!(results, vec![0, 1, 2, 3]);
+    /// assert_eq!(pool.clock().now(), Duration::from_millis(200));
+    /// ```
+    /// 
+    /// [`advance()`]: #method.advance
+    pub fn run_until_idle(&self) {
+        assert!(self.workers.is_deterministic(), "Only deterministic pools can be run by hand");
+        self.workers.run_until_idle();
+    }
+
+    /// Runs the jobs that a deterministic pool would start within the next `duration`,
+    /// and moves its clock on by that much.  Jobs run to completion as soon as they've
+    /// started, even if they take longer than that, so jobs that are still "running" by
+    /// the end have already produced their results - but their workers aren't free again
+    /// until they've finished.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the pool isn't deterministic.
+    /// 
+    /// # Examples
+    /// 
+    /// Two workers, so the third job has to wait for one of the first two to finish:
+    /// 
+    /// ```
+    /// use std::time::Duration;
+    /// use cjp_threadpool::ThreadPool;
+    /// 
+    /// let pool = ThreadPool::builder().max_threads(2).deterministic().build().unwrap();
+    /// let clock = pool.clock();
+    /// for i in 0..3 {
+    ///     let clock = clock.clone();
+    ///     pool.schedule(Box::new(move || {
+    ///         clock.sleep(Duration::from_millis(100));
+    ///         i
+    ///     }));
+    /// }
+    /// pool.advance(Duration::from_millis(50));
+    /// assert_eq!(pool.results.try_iter().count(), 2);
+    /// assert_eq!(clock.now(), Duration::from_millis(50));
+    /// 
+    /// pool.advance(Duration::from_millis(50));
+    /// assert_eq!(pool.results.try_recv(), Ok(Ok(2)));
+    /// ```
+    pub fn advance(&self, duration: Duration) {
+        assert!(self.workers.is_deterministic(), "Only deterministic pools can be run by hand");
+        self.workers.advance(duration);
+    }
+
+    /// Returns a snapshot of what the pool is doing, and has done.  The figures are
+    /// gathered without stopping the workers, so while jobs are running they needn't
+    /// quite add up: a job's result can arrive before it's counted as completed, for
+    /// instance.  The snapshot returned by [`join()`] is exact.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use cjp_threadpool::ThreadPool;
+    /// 
+    /// let pool = ThreadPool::new(2).unwrap();
+    /// pool.schedule(Box::new(|| 1 + 1));
+    /// pool.schedule(Box::new(|| panic!("oops")));
+    /// assert_eq!(pool.stats().threads, 2);
+    /// 
+    /// let stats = pool.join();
+    /// assert_eq!((stats.completed, stats.panicked, stats.queued, stats.running), (1, 1, 0, 0));
+    /// assert_eq!(stats.execution.count(), 2);
+    /// ```
     /// 
     /// [`join()`]: #method.join
-    pub fn terminate(self) {
-        drop(self.inbound_work_sender);
-        {
-            // Consume all the remaining scheduled work.
-            let receiver = self.inbound_work_receiver.lock().unwrap();
-            while let Ok(job) = receiver.recv() { drop(job); }
+    #[must_use]
+    pub fn stats(&self) -> PoolStats {
+        self.workers.stats()
+    }
+
+    /// Calls `report` with a [`stats()`] snapshot every `interval`, on a thread of its
+    /// own, until the pool is joined or dropped.
+    /// 
+    /// A deterministic pool reports on its virtual clock instead: whenever [`advance()`]
+    /// or [`run_until_idle()`] takes the clock past a multiple of `interval`, `report` is
+    /// called there and then, seeing the jobs that started before that point.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the pool is deterministic and `interval` is zero.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use std::time::Duration;
+    /// use cjp_threadpool::ThreadPool;
+    /// 
+    /// let pool = ThreadPool::<()>::new(2).unwrap();
+    /// pool.report_stats(Duration::from_millis(10), |stats| {
+    ///     println!("{} queued, {} running, {} done", stats.queued, stats.running, stats.completed);
+    /// });
+    /// pool.for_each(0..4, |_| std::thread::sleep(Duration::from_millis(20)));
+    /// pool.join();
+    /// ```
+    /// 
+    /// [`stats()`]: #method.stats
+    /// [`advance()`]: #method.advance
+    /// [`run_until_idle()`]: #method.run_until_idle
+    pub fn report_stats(&self, interval: Duration, report: impl FnMut(&PoolStats) + Send + 'static) {
+        if self.workers.is_deterministic() {
+            self.workers.report_on_ticks(interval, Box::new(report));
+            return;
         }
+        let reporter = Reporter::start(Arc::clone(&self.workers), interval, report);
+        self.reporters.lock().unwrap().push(reporter);
+    }
 
-        // Join all worker threads.
-        for worker in self.threads {
-            worker.thread.join().unwrap();
+    fn stop_reporters(&self) {
+        for reporter in self.reporters.lock().unwrap().drain(..) {
+            reporter.stop();
         }
+        self.workers.stop_ticking();
     }
-}
 
-struct Worker {
-    thread: JoinHandle<()>,
+    /// Block the current thread until all jobs in the pool have been executed, and
+    /// return the pool's final [`stats()`].
+    /// 
+    /// [`stats()`]: #method.stats
+    pub fn join(self) -> PoolStats {
+        self.workers.join();
+        self.stop_reporters();
+        self.workers.stats()
+    }
+
+    /// Like [`join()`], but drops any pending jobs that aren't already mid-execution.
+    /// Running jobs also have their [`cancellation_token()`] cancelled.
+    /// 
+    /// [`join()`]: #method.join
+    /// [`cancellation_token()`]: #method.cancellation_token
+    pub fn terminate(self) -> PoolStats {
+        // The workers will throw away everything that's left in the queue.
+        self.cancel_all();
+        self.join()
+    }
 }
 
-impl Worker {
-    fn new<T: Send + 'static>(receiver: Arc<Mutex<Receiver<Job<T>>>>, result_sender: Sender<T>) -> Self {
-        Self {
-            thread: thread::spawn(move || {
-                #[allow(clippy::while_let_loop)]
-                loop {
-                    let job = match receiver.lock().unwrap().recv() {
-                        Ok(job) => job,
-                        Err(_) => break,
-                    };
-                    let result = job();
-                    result_sender.send(result).unwrap();
-                }
-            })
+impl<T> Drop for ThreadPool<T> {
+    // Let the workers exit once they've finished what's queued, without waiting for them.
+    fn drop(&mut self) {
+        self.workers.shut_down();
+        for reporter in self.reporters.get_mut().unwrap().drain(..) {
+            reporter.stop();
         }
+        self.workers.stop_ticking();
     }
 }
 
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::{Duration, Instant};
-    use std::sync::{Arc, Mutex};
+    use std::future::Future;
+    use std::thread;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::time::Duration;
+    use std::sync::{Arc, Barrier, Mutex};
+
+    // Most of these tests use a deterministic pool, so that they can check what happens
+    // when without depending on how quickly real threads get round to things.
+    fn deterministic<T: Send + 'static>(num_threads: usize) -> ThreadPool<T> {
+        ThreadPool::builder().max_threads(num_threads).deterministic().build().unwrap()
+    }
+
+    // A job that takes `millis` milliseconds, by the pool's clock.
+    fn sleeper<T: Send + 'static>(pool: &ThreadPool<T>, millis: u64, result: T) -> Job<T> {
+        let clock = pool.clock();
+        Box::new(move || {
+            clock.sleep(Duration::from_millis(millis));
+            result
+        })
+    }
 
     #[test]
     fn two_sequential_jobs() {
-        let pool = ThreadPool::new(1);
+        let pool = deterministic(1);
         let job = Box::new(move || 1 + 1);
         pool.schedule(job.clone());
         pool.schedule(job);
-        assert_eq!(Ok(2), pool.results.recv());
-        assert_eq!(Ok(2), pool.results.recv());
+        assert!(pool.results.try_recv().is_err());
+        pool.run_until_idle();
+        assert_eq!(Ok(Ok(2)), pool.results.try_recv());
+        assert_eq!(Ok(Ok(2)), pool.results.try_recv());
         assert!(pool.results.try_recv().is_err());
         pool.join();
     }
 
     #[test]
     fn highly_parallel() {
-        let pool = ThreadPool::new(8);
-        let job = Box::new(move || {
-            thread::sleep(Duration::from_millis(100));
-            1 + 1
-        });
-        let now = Instant::now();
+        let pool = deterministic(8);
         for _ in 0..24 {
-            pool.schedule(job.clone());
+            pool.schedule(sleeper(&pool, 100, 2));
         }
+        pool.run_until_idle();
         for _ in 0..24 {
-            assert_eq!(Ok(2), pool.results.recv());
+            assert_eq!(Ok(Ok(2)), pool.results.try_recv());
+        }
+
+        // Three rounds of eight jobs at once, with every worker pitching in.
+        assert_eq!(pool.clock().now(), Duration::from_millis(300));
+        let stats = pool.join();
+        assert_eq!(stats.completed, 24);
+        assert!(stats.workers.iter().all(|worker| worker.jobs == 3 && worker.busy == Duration::from_millis(300)));
+        assert_eq!(stats.queue_wait.max(), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn panicking_job_is_reported() {
+        let pool = deterministic(1);
+        pool.schedule(Box::new(|| panic!("bad candidate")));
+        pool.schedule(Box::new(|| panic!("{} bad candidates", 2)));
+        pool.schedule(Box::new(|| 1 + 1));
+        pool.run_until_idle();
+        assert_eq!(Err(JobPanic { message: "bad candidate".to_string() }), pool.results.try_recv().unwrap());
+        assert_eq!(Err(JobPanic { message: "2 bad candidates".to_string() }), pool.results.try_recv().unwrap());
+        assert_eq!(Ok(Ok(2)), pool.results.try_recv());
+        pool.join();
+    }
+
+    #[test]
+    fn panicking_workers_are_replaced() {
+        let pool = ThreadPool::new(4).unwrap();
+        for i in 0..40 {
+            pool.schedule(Box::new(move || {
+                if i % 2 == 0 { panic!("even"); }
+                i
+            }));
+        }
+        let (successes, panics): (Vec<_>, Vec<_>) = (0..40).map(|_| pool.results.recv().unwrap()).partition(Result::is_ok);
+        assert_eq!(successes.len(), 20);
+        assert_eq!(panics.len(), 20);
+        let stats = pool.join();
+        assert_eq!((stats.completed, stats.panicked), (20, 20));
+    }
+
+    #[test]
+    fn work_is_stolen_from_busy_workers() {
+        let pool = ThreadPool::new(2).unwrap();
+
+        // The quick jobs are dealt out to both workers' deques, but whichever worker picks
+        // up the slow job is stuck on it until we let it go.  So the quick jobs queued
+        // behind it only get done if the other worker steals them.
+        let (release, released) = mpsc::channel::<()>();
+        pool.schedule(Box::new(move || {
+            released.recv().unwrap();
+            0
+        }));
+        for i in 1..=20 {
+            pool.schedule(Box::new(move || i));
+        }
+        let mut quick: Vec<u32> = (0..20).map(|_| pool.results.recv().unwrap().unwrap()).collect();
+        quick.sort_unstable();
+        assert_eq!(quick, (1..=20).collect::<Vec<_>>());
+        assert!(pool.results.try_recv().is_err());
+
+        release.send(()).unwrap();
+        assert_eq!(Ok(Ok(0)), pool.results.recv());
+        let stats = pool.join();
+        assert_eq!(stats.workers.iter().map(|worker| worker.jobs).sum::<u64>(), 21);
+    }
+
+    #[test]
+    fn dropped_pool_finishes_its_work() {
+        // The workers carry on after the pool's gone.  Once they've run every job, there's
+        // nothing left holding a sender, and the receiver runs dry.
+        let (sender, receiver) = mpsc::channel();
+        {
+            let pool = ThreadPool::<()>::new(4).unwrap();
+            for _ in 0..100 {
+                let sender = sender.clone();
+                pool.schedule(Box::new(move || sender.send(()).unwrap()));
+            }
+        }
+        drop(sender);
+        assert_eq!(receiver.iter().count(), 100);
+
+        // A deterministic pool has nobody to leave the work to, so does it when it's dropped.
+        let value = Arc::new(Mutex::new(0));
+        {
+            let pool = deterministic::<()>(4);
+            for _ in 0..100 {
+                let value = Arc::clone(&value);
+                pool.schedule(Box::new(move || *value.lock().unwrap() += 1));
+            }
         }
-        assert!(now.elapsed().as_millis() < 350);
+        assert_eq!(*value.lock().unwrap(), 100);
+    }
+
+    // Just enough of an executor to run a future to completion.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        struct ThreadWaker(thread::Thread);
+        impl Wake for ThreadWaker {
+            fn wake(self: Arc<Self>) { self.0.unpark(); }
+        }
+
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut context = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => break output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn handles() {
+        let pool = deterministic::<()>(1);
+        let slow = pool.schedule_with_handle(sleeper(&pool, 100, ()));
+        let mut quick = pool.schedule_with_handle(Box::new(|| 1 + 1));
+        let panicky = pool.schedule_with_handle(Box::new(|| -> u8 { panic!("oops") }));
+        assert!(!slow.is_finished());
+
+        // Only the slow job starts straight away, and the rest wait until it's done.
+        pool.advance(Duration::ZERO);
+        assert!(slow.is_finished());
+        assert_eq!(quick.try_wait(), None);
+        pool.advance(Duration::from_millis(100));
+        assert_eq!(quick.try_wait(), Some(Ok(2)));
+        assert_eq!(quick.try_wait(), None);
+        assert_eq!(panicky.wait(), Err(JobError::Panicked(JobPanic { message: "oops".to_string() })));
+        assert_eq!(block_on(slow), Ok(()));
         pool.join();
     }
 
+    #[test]
+    fn cancelled_handles() {
+        let pool = deterministic::<()>(1);
+        pool.schedule_with_handle(sleeper(&pool, 100, ()));
+        let handle = pool.schedule_with_handle(Box::new(|| 1 + 1));
+        pool.advance(Duration::from_millis(50));
+        let stats = pool.terminate();
+        assert_eq!(handle.wait(), Err(JobError::Cancelled));
+        assert_eq!((stats.completed, stats.cancelled), (1, 1));
+    }
+
+    #[test]
+    fn tagged_results() {
+        let pool = deterministic(4);
+        let ids: Vec<(JobId, u64)> = (0..20).map(|i| (pool.schedule_tagged(Box::new(move || i * i)), i)).collect();
+        pool.run_until_idle();
+        let mut results: Vec<(JobId, u64)> = (0..20)
+            .map(|_| {
+                let (id, result) = pool.tagged_results.recv().unwrap();
+                (id, result.unwrap())
+            })
+            .collect();
+        results.sort();
+        for ((id, input), (result_id, output)) in ids.into_iter().zip(results) {
+            assert_eq!(id, result_id);
+            assert_eq!(input * input, output);
+        }
+        assert!(pool.results.try_recv().is_err());
+        pool.join();
+    }
+
+    #[test]
+    fn cancel_all_drops_queued_jobs() {
+        let pool = deterministic::<()>(2);
+        let running: Vec<_> = (0..2).map(|_| pool.schedule_with_handle(sleeper(&pool, 100, ()))).collect();
+        let queued = pool.schedule_with_handle(Box::new(|| ()));
+        pool.advance(Duration::from_millis(50));
+        pool.cancel_all();
+        for handle in running { assert_eq!(handle.wait(), Ok(())); }
+        assert_eq!(queued.wait(), Err(JobError::Cancelled));
+
+        // The pool's still usable, and new jobs aren't cancelled.
+        assert!(!pool.cancellation_token().is_cancelled());
+        assert_eq!(pool.schedule_with_handle(Box::new(|| 1 + 1)).wait(), Ok(2));
+        pool.join();
+    }
+
+    #[test]
+    fn child_tokens() {
+        let parent = CancellationToken::new();
+        let child = parent.child_token();
+        let sibling = parent.child_token();
+        child.cancel();
+        assert!(child.is_cancelled());
+        assert!(!parent.is_cancelled());
+        assert!(!sibling.is_cancelled());
+        parent.cancel();
+        assert!(sibling.is_cancelled());
+    }
+
+    #[test]
+    fn find_first_stops_promptly() {
+        let pool = deterministic::<()>(8);
+        let clock = pool.clock();
+        let job_token = Arc::new(Mutex::new(None));
+        let job_token_clone = Arc::clone(&job_token);
+        let answer = pool.find_first(0..10_000, move |i, token| {
+            *job_token_clone.lock().unwrap() = Some(token.clone());
+            if i == 20 { return Some(i); }
+            clock.sleep(Duration::from_millis(100));
+            None
+        });
+        assert_eq!(answer, Some(20));
+
+        // Input 20 is picked up once the first two rounds of jobs are done.  Nothing's
+        // started after that: the jobs still running are told to give up, and the handful
+        // that were queued behind them are thrown away.
+        assert_eq!(pool.clock().now(), Duration::from_millis(200));
+        assert!(job_token.lock().unwrap().as_ref().unwrap().is_cancelled());
+        let stats = pool.join();
+        assert_eq!((stats.completed, stats.cancelled), (21, 15));
+    }
+
+    #[test]
+    fn find_first_without_answer() {
+        let pool = deterministic::<()>(4);
+        let answer: Option<u32> = pool.find_first(0..100, |i, _| if i % 2 == 0 { panic!("even") } else { None });
+        assert_eq!(answer, None);
+        let stats = pool.join();
+        assert_eq!((stats.completed, stats.panicked), (50, 50));
+    }
+
+    #[test]
+    fn map_keeps_input_order() {
+        let pool = deterministic::<()>(4);
+        let clock = pool.clock();
+        let squares = pool.map(0..10_000_u64, move |n| {
+            if n % 1000 == 0 { clock.sleep(Duration::from_millis(10)); }
+            n * n
+        });
+        assert_eq!(squares, (0..10_000).map(|n| n * n).collect::<Vec<_>>());
+        assert!(pool.map(Vec::<u64>::new(), |n| n).is_empty());
+        pool.join();
+    }
+
+    #[test]
+    fn for_each_filter_map_and_reduce() {
+        let pool = deterministic::<()>(4);
+        let total = Arc::new(Mutex::new(0));
+        let total_clone = Arc::clone(&total);
+        pool.for_each(1..=100, move |n| *total_clone.lock().unwrap() += n);
+        assert_eq!(*total.lock().unwrap(), 5050);
+
+        let evens = pool.filter_map(1..=100, |n| if n % 2 == 0 { Some(n / 2) } else { None });
+        assert_eq!(evens, (1..=50).collect::<Vec<_>>());
+
+        // Combining isn't required to be commutative, so order must be preserved.
+        let digits = pool.reduce(0..1000, |n| (n % 10).to_string(), |a, b| a + &b).unwrap();
+        assert_eq!(digits, "0123456789".repeat(100));
+        pool.join();
+    }
+
+    #[test]
+    #[should_panic(expected = "job panicked: bad item")]
+    fn map_passes_on_panics() {
+        let pool = deterministic::<()>(2);
+        pool.map(0..100, |n| if n == 50 { panic!("bad item") } else { n });
+    }
+
+    #[test]
+    fn find_any_skips_panics() {
+        let pool = deterministic::<()>(4);
+        let found = pool.find_any(0..100_000, |&n| {
+            if n % 3 == 0 { panic!("multiple of three"); }
+            n > 500 && n % 1000 == 0
+        });
+        assert!(found.is_some_and(|n| n % 1000 == 0 && n % 3 != 0));
+        assert_eq!(pool.find_any(0..100, |&n| n > 100), None);
+        pool.join();
+    }
+
+    #[test]
+    fn scoped_jobs_borrow_and_finish() {
+        let pool = deterministic::<()>(4);
+        let clock = pool.clock();
+        let numbers: Vec<u64> = (1..=100).collect();
+        let total = Mutex::new(0);
+        pool.scope(|s| {
+            for chunk in numbers.chunks(10) {
+                let total = &total;
+                let clock = &clock;
+                s.spawn(move || {
+                    clock.sleep(Duration::from_millis(10));
+                    *total.lock().unwrap() += chunk.iter().sum::<u64>();
+                });
+            }
+        });
+        assert_eq!(total.into_inner().unwrap(), 5050);
+        pool.join();
+    }
+
+    #[test]
+    fn scope_waits_for_jobs_when_it_panics() {
+        let pool = deterministic::<()>(2);
+        let finished = Mutex::new(false);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            pool.scope(|s| {
+                s.spawn(|| *finished.lock().unwrap() = true);
+                panic!("scope body failed");
+            })
+        }));
+        assert!(result.is_err());
+        assert!(*finished.lock().unwrap());
+        pool.join();
+    }
+
+    #[test]
+    fn scoped_jobs_can_be_cancelled() {
+        let pool = deterministic::<()>(1);
+        let clock = pool.clock();
+        let ran = Mutex::new(0);
+        let (first, second) = pool.scope(|s| {
+            let first = s.spawn(|| {
+                clock.sleep(Duration::from_millis(100));
+                *ran.lock().unwrap() += 1;
+            });
+            let second = s.spawn(|| *ran.lock().unwrap() += 1);
+            pool.advance(Duration::from_millis(50));
+            pool.cancel_all();
+            (first, second)
+        });
+        assert_eq!(first.wait(), Ok(()));
+        assert_eq!(second.wait(), Err(JobError::Cancelled));
+        assert_eq!(*ran.lock().unwrap(), 1);
+        pool.join();
+    }
+
+    #[test]
+    fn scoped_searches_borrow() {
+        let pool = deterministic::<()>(4);
+        let squares: Vec<u64> = (0..1000).map(|n| n * n).collect();
+        let checked = Mutex::new(0);
+        let found = pool.scope(|s| {
+            s.find_any(0..squares.len(), |&n| {
+                *checked.lock().unwrap() += 1;
+                squares[n] == 144
+            })
+        });
+        assert_eq!(found, Some(12));
+        assert!(*checked.lock().unwrap() < squares.len());
+        assert_eq!(pool.scope(|s| s.find_any(0..squares.len(), |&n| squares[n] == 145)), None);
+        pool.join();
+    }
+
+    #[test]
+    fn bad_sizes_are_errors() {
+        assert_eq!(ThreadPool::<()>::new(0).err(), Some(PoolError::NoThreads));
+        assert_eq!(ThreadPool::<()>::builder().max_threads(0).build().err(), Some(PoolError::NoThreads));
+        assert_eq!(ThreadPool::<()>::builder().max_threads(0).deterministic().build().err(), Some(PoolError::NoThreads));
+        assert_eq!(
+            ThreadPool::<()>::builder().min_threads(3).max_threads(2).build().err(),
+            Some(PoolError::MinExceedsMax { min: 3, max: 2 })
+        );
+        assert_eq!(
+            ThreadPool::<()>::builder().min_threads(3).max_threads(2).deterministic().build().err(),
+            Some(PoolError::MinExceedsMax { min: 3, max: 2 })
+        );
+        let pool = ThreadPool::<()>::new(2).unwrap();
+        assert_eq!(pool.resize(0), Err(PoolError::NoThreads));
+        assert_eq!(pool.num_threads(), 2);
+        pool.join();
+    }
+
+    // Wait for the pool to end up with the given number of threads.
+    fn wait_for_threads<T: Send + 'static>(pool: &ThreadPool<T>, num_threads: usize) {
+        pool.workers.wait_for_live_threads(num_threads);
+        assert_eq!(pool.num_threads(), num_threads);
+    }
+
+    #[test]
+    fn resize() {
+        let pool = ThreadPool::new(1).unwrap();
+        pool.resize(4).unwrap();
+        assert_eq!(pool.num_threads(), 4);
+
+        // The jobs can only get past the barrier four at a time, so they only finish if all
+        // four threads are taking part.
+        let barrier = Arc::new(Barrier::new(4));
+        for _ in 0..8 {
+            let barrier = Arc::clone(&barrier);
+            pool.schedule(Box::new(move || { barrier.wait(); }));
+        }
+        for _ in 0..8 {
+            assert_eq!(Ok(Ok(())), pool.results.recv());
+        }
+
+        // Shrinking lets the surplus workers finish what they're doing first.
+        pool.resize(1).unwrap();
+        wait_for_threads(&pool, 1);
+        for _ in 0..4 {
+            pool.schedule(Box::new(|| ()));
+        }
+        for _ in 0..4 {
+            assert_eq!(Ok(Ok(())), pool.results.recv());
+        }
+        pool.join();
+
+        // A deterministic pool just gets more (or fewer) virtual workers.
+        let pool = deterministic(1);
+        pool.resize(2).unwrap();
+        for _ in 0..4 {
+            pool.schedule(sleeper(&pool, 100, ()));
+        }
+        pool.run_until_idle();
+        assert_eq!(pool.clock().now(), Duration::from_millis(200));
+        assert_eq!(pool.num_threads(), 2);
+        pool.join();
+    }
+
+    #[test]
+    fn idle_threads_are_reaped() {
+        let pool = ThreadPool::builder()
+            .min_threads(1)
+            .max_threads(4)
+            .idle_timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        assert_eq!(pool.num_threads(), 1);
+
+        // The pool grows to meet demand - the jobs can only get past the barrier once all
+        // four of them are running at once...
+        let barrier = Arc::new(Barrier::new(5));
+        for _ in 0..4 {
+            let barrier = Arc::clone(&barrier);
+            pool.schedule(Box::new(move || { barrier.wait(); }));
+        }
+        barrier.wait();
+        assert_eq!(pool.num_threads(), 4);
+        for _ in 0..4 {
+            assert_eq!(Ok(Ok(())), pool.results.recv());
+        }
+
+        // ...and shrinks back once it's quiet.
+        wait_for_threads(&pool, 1);
+        pool.join();
+    }
+
+    #[test]
+    fn priorities() {
+        let pool = deterministic(1);
+        pool.schedule(Box::new(|| "blocker"));
+        pool.advance(Duration::ZERO);
+        pool.schedule_with_priority(Box::new(|| "low"), Priority::Low);
+        pool.schedule(Box::new(|| "normal"));
+        pool.schedule_with_priority(Box::new(|| "high"), Priority::High);
+        pool.schedule(Box::new(|| "normal again"));
+        pool.run_until_idle();
+        let order: Vec<_> = pool.results.try_iter().map(Result::unwrap).collect();
+        assert_eq!(order, vec!["blocker", "high", "normal", "normal again", "low"]);
+        pool.join();
+    }
+
+    #[test]
+    fn terminate_is_prompt() {
+        let pool = deterministic(8);
+        let clock = pool.clock();
+        for _ in 0..100_000 {
+            pool.schedule(sleeper(&pool, 10, ()));
+        }
+        pool.advance(Duration::from_millis(5));
+        let stats = pool.terminate();
+
+        // Only the jobs that had already started got to finish.
+        assert_eq!((stats.completed, stats.cancelled), (8, 99_992));
+        assert_eq!(clock.now(), Duration::from_millis(10));
+    }
+
     #[test]
     fn terminate_early() {
-        let pool = ThreadPool::new(8);
+        let pool = deterministic(8);
+        let clock = pool.clock();
         let value = Arc::new(Mutex::new(0));
         for _ in 0..24 {
             let value_clone = Arc::clone(&value);
+            let clock = clock.clone();
             let job = Box::new(move || {
-                thread::sleep(Duration::from_millis(100));
+                clock.sleep(Duration::from_millis(100));
                 let mut data = value_clone.lock().unwrap();
                 *data += 1;
                 *data
             });
             pool.schedule(job);
         }
-        thread::sleep(Duration::from_millis(50));
-        pool.terminate();
-        thread::sleep(Duration::from_secs(1));
-        let data = value.lock().unwrap();
-        assert_eq!(*data, 8);
+        pool.advance(Duration::from_millis(50));
+        let stats = pool.terminate();
+        assert_eq!((stats.completed, stats.cancelled), (8, 16));
+        assert_eq!(*value.lock().unwrap(), 8);
+    }
+
+    #[test]
+    fn deterministic_runs_are_reproducible() {
+        fn run() -> (Vec<u64>, PoolStats) {
+            let pool = deterministic(3);
+            for i in 0..30 {
+                let priority = [Priority::Low, Priority::Normal, Priority::High][i as usize % 3];
+                pool.schedule_with_priority(sleeper(&pool, i % 7, i), priority);
+            }
+            pool.run_until_idle();
+            let order = pool.results.try_iter().map(Result::unwrap).collect();
+            (order, pool.join())
+        }
+        let (order, stats) = run();
+        assert_eq!(run(), (order.clone(), stats));
+        // Everything's queued before anything starts, so it all runs in priority order.
+        let expected: Vec<u64> = (2..30).step_by(3).chain((1..30).step_by(3)).chain((0..30).step_by(3)).collect();
+        assert_eq!(order, expected);
+    }
+
+    #[test]
+    fn stats_and_reporting() {
+        let pool = deterministic(2);
+        let (report_tx, report_rx) = mpsc::channel();
+        let clock = pool.clock();
+        pool.report_stats(Duration::from_millis(5), move |stats| {
+            let _ = report_tx.send((clock.now().as_millis(), stats.queued, stats.completed));
+        });
+        for _ in 0..3 {
+            pool.schedule(sleeper(&pool, 20, ()));
+        }
+        let stats = pool.stats();
+        assert_eq!((stats.threads, stats.running, stats.queued, stats.completed), (2, 0, 3, 0));
+
+        // Two workers, so the third job waits until one of the first two is done.
+        pool.advance(Duration::from_millis(10));
+        let stats = pool.stats();
+        assert_eq!((stats.queued, stats.completed), (1, 2));
+        assert_eq!(report_rx.try_iter().collect::<Vec<_>>(), vec![(5, 1, 2), (10, 1, 2)]);
+
+        let stats = pool.join();
+        assert_eq!((stats.running, stats.queued, stats.completed, stats.panicked), (0, 0, 3, 0));
+        let workers: Vec<_> = stats.workers.iter().map(|worker| (worker.jobs, worker.busy)).collect();
+        assert_eq!(workers, vec![(2, Duration::from_millis(40)), (1, Duration::from_millis(20))]);
+        assert_eq!(stats.execution.count(), 3);
+        assert_eq!(stats.execution.mean(), Some(Duration::from_millis(20)));
+        assert_eq!(stats.execution.percentile(50.0), Some(Duration::from_millis(20)));
+        assert_eq!(stats.queue_wait.max(), Duration::from_millis(20));
+        assert_eq!(stats.queue_wait.percentile(50.0), Some(Duration::from_micros(1)));
+        assert_eq!(stats.queue_wait.buckets().collect::<Vec<_>>(), vec![(Duration::from_micros(1), 2), (Duration::from_micros(32768), 1)]);
+
+        // Reports carry on as the pool finishes up, with the third job still queued until it
+        // starts at 20ms, and then the reporter stops with the pool, letting go of the sender.
+        let reports: Vec<_> = report_rx.iter().collect();
+        assert_eq!(reports, vec![(15, 1, 2), (20, 1, 2), (25, 0, 3), (30, 0, 3), (35, 0, 3), (40, 0, 3)]);
+    }
+
+    // A future that's pending the first few times it's polled, waking itself each time.
+    struct YieldNow(u32);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+            if self.0 == 0 { return Poll::Ready(()); }
+            self.0 -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn futures() {
+        let pool = deterministic::<()>(2);
+        let handles: Vec<_> = (0..10_u64).map(|i| pool.spawn(async move {
+            YieldNow(3).await;
+            i * i
+        })).collect();
+        let squares = pool.spawn(async move {
+            let mut total = 0;
+            for handle in handles { total += handle.await.unwrap(); }
+            total
+        });
+        let heavy = pool.spawn(async {
+            YieldNow(1).await;
+            pool_free_sum(1_000).await
+        });
+        assert_eq!(squares.wait(), Ok(285));
+        assert_eq!(heavy.wait(), Ok(500_500));
+
+        let panicky = pool.spawn(async {
+            YieldNow(1).await;
+            panic!("async oops")
+        });
+        assert_eq!(panicky.wait(), Err::<(), _>(JobError::Panicked(JobPanic { message: "async oops".to_string() })));
+        pool.join();
+    }
+
+    // Sums numbers on another pool, to check that futures from one pool can wait on jobs
+    // from another.
+    async fn pool_free_sum(n: u64) -> u64 {
+        let pool = ThreadPool::<()>::new(1).unwrap();
+        let handle = pool.spawn_blocking(move || (1..=n).sum());
+        drop(pool);
+        handle.await.unwrap()
+    }
+
+    #[test]
+    fn futures_can_be_cancelled() {
+        let pool = deterministic::<()>(1);
+        let other_pool = deterministic::<()>(1);
+        let blocking = other_pool.spawn_blocking(|| 1);
+        let waiting = pool.spawn(async move { blocking.await.unwrap() + 1 });
+
+        // The future's waiting to be woken when it's cancelled, and goes once it is.
+        pool.advance(Duration::ZERO);
+        pool.cancel_all();
+        other_pool.run_until_idle();
+        pool.run_until_idle();
+        assert_eq!(block_on(waiting), Err(JobError::Cancelled));
+
+        // Futures spawned afterwards aren't affected.
+        assert_eq!(pool.spawn(async { 2 }).wait(), Ok(2));
+        pool.join();
     }
-}
\ No newline at end of file
+}
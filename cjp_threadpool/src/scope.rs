@@ -0,0 +1,176 @@
+//! Scoped jobs, which can borrow from whoever schedules them.
+
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex};
+
+use crate::{run_job, JobHandle, ThreadPool};
+
+// Keeps count of how many of a scope's jobs haven't finished yet.
+struct ScopeState {
+    pending: Mutex<usize>,
+    all_done: Condvar,
+}
+
+impl ScopeState {
+    fn wait(&self) {
+        let mut pending = self.pending.lock().unwrap();
+        while *pending > 0 {
+            pending = self.all_done.wait(pending).unwrap();
+        }
+    }
+}
+
+// Marks one of a scope's jobs as pending until it's dropped.
+struct Pending(Arc<ScopeState>);
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        let mut pending = self.0.pending.lock().unwrap();
+        *pending -= 1;
+        if *pending == 0 {
+            self.0.all_done.notify_all();
+        }
+    }
+}
+
+// A scoped job, and the marker that says it's pending.  Struct fields are dropped in order,
+// so even if the job is thrown away without being run, it (and everything it borrows) is
+// gone before the scope is told that it's finished.
+struct ScopedJob<F> {
+    job: F,
+    _pending: Pending,
+}
+
+/// Lets you schedule jobs that borrow from outside the scope - see [`ThreadPool::scope()`].
+///
+/// Jobs can't borrow anything that only lives as long as the function passed to
+/// [`ThreadPool::scope()`], since they might still be running once that's returned:
+///
+/// ```compile_fail
+/// use cjp_threadpool::ThreadPool;
+///
+/// let pool = ThreadPool::<()>::new(1).unwrap();
+/// pool.scope(|s| {
+///     let local = vec![1, 2, 3];
+///     s.spawn(|| local.len());
+/// });
+/// ```
+///
+/// [`ThreadPool::scope()`]: ./struct.ThreadPool.html#method.scope
+pub struct Scope<'env, T> {
+    pool: &'env ThreadPool<T>,
+    state: Arc<ScopeState>,
+
+    // `'env` must be invariant - if a `Scope<'env, T>` could pass for a `Scope<'a, T>` with
+    // some shorter `'a`, jobs could borrow things that don't live as long as the scope.
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'env, T: Send + 'static> Scope<'env, T> {
+    /// Queues the function `job` for execution on a thread in the pool, returning a
+    /// [`JobHandle`] through which you can get its result.  Unlike with
+    /// [`ThreadPool::schedule_with_handle()`], the job can borrow anything that outlives
+    /// the scope, and needn't be boxed.
+    ///
+    /// [`JobHandle`]: ./struct.JobHandle.html
+    /// [`ThreadPool::schedule_with_handle()`]: ./struct.ThreadPool.html#method.schedule_with_handle
+    pub fn spawn<F, R>(&self, job: F) -> JobHandle<R>
+    where
+        F: FnOnce() -> R + Send + 'env,
+        R: Send + 'static,
+    {
+        *self.state.pending.lock().unwrap() += 1;
+        let (handle, completer) = JobHandle::new(self.pool.workers.driver());
+        let scoped = ScopedJob { job, _pending: Pending(Arc::clone(&self.state)) };
+        let run: Box<dyn FnOnce() -> bool + Send + 'env> = Box::new(move || {
+            let ScopedJob { job, _pending } = scoped;
+            let result = run_job(job);
+            let panicked = result.is_err();
+            completer.complete(result);
+            panicked
+        });
+
+        // SAFETY: the pool requires jobs to be `'static`, but this one's only good for `'env`.
+        // That's fine, because `ThreadPool::scope()` doesn't return (which would end `'env`)
+        // until every job spawned in the scope has either been run or been dropped.
+        let run: Box<dyn FnOnce() -> bool + Send + 'static> = unsafe { std::mem::transmute(run) };
+        self.pool.submit(run);
+        handle
+    }
+
+    /// Like [`ThreadPool::find_any()`], but `predicate` can borrow anything that outlives
+    /// the scope.  Returns as soon as a match is found, though the scope still waits for
+    /// any jobs that were running at the time to give up.
+    ///
+    /// [`ThreadPool::find_any()`]: ./struct.ThreadPool.html#method.find_any
+    pub fn find_any<A, I, P>(&self, inputs: I, predicate: P) -> Option<A>
+    where
+        A: Send + 'static,
+        I: IntoIterator<Item = A>,
+        P: Fn(&A) -> bool + Send + Sync + 'env,
+    {
+        // Every job the search schedules shares the predicate, so the predicate (and the
+        // marker that goes with it) is only dropped once they've all been run or dropped.
+        *self.state.pending.lock().unwrap() += 1;
+        let scoped = ScopedJob { job: predicate, _pending: Pending(Arc::clone(&self.state)) };
+        let predicate: Box<dyn Fn(&A) -> bool + Send + Sync + 'env> = Box::new(move |item| {
+            let ScopedJob { job, _pending } = &scoped;
+            job(item)
+        });
+
+        // SAFETY: as for `spawn()` - the scope doesn't end until the predicate's been dropped.
+        let predicate: Box<dyn Fn(&A) -> bool + Send + Sync + 'static> = unsafe { std::mem::transmute(predicate) };
+        self.pool.find_any(inputs, predicate)
+    }
+}
+
+impl<T: Send + 'static> ThreadPool<T> {
+    /// Runs `f`, passing it a [`Scope`] through which it can schedule jobs that borrow
+    /// data from outside - which, unlike the jobs passed to [`schedule()`], needn't be
+    /// `'static`.  Doesn't return until all of the jobs spawned in the scope have finished,
+    /// even if `f` panics.
+    ///
+    /// Jobs that panic, or that are cancelled with [`cancel_all()`], report that through
+    /// their [`JobHandle`]s as usual, without affecting the scope.
+    ///
+    /// Don't call this from within a job running on the same pool: the worker will block
+    /// waiting for the scope's jobs, which could be queued behind it.
+    ///
+    /// # Panics
+    ///
+    /// Panics, once all the jobs have finished, if `f` panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cjp_threadpool::ThreadPool;
+    ///
+    /// let pool = ThreadPool::<()>::new(4).unwrap();
+    /// let words = vec!["scoped".to_string(), "jobs".to_string()];
+    /// let lengths: Vec<usize> = pool.scope(|s| {
+    ///     let handles: Vec<_> = words.iter().map(|word| s.spawn(move || word.len())).collect();
+    ///     handles.into_iter().map(|handle| handle.wait().unwrap()).collect()
+    /// });
+    /// assert_eq!(lengths, vec![6, 4]);
+    /// ```
+    ///
+    /// [`Scope`]: ./struct.Scope.html
+    /// [`schedule()`]: #method.schedule
+    /// [`cancel_all()`]: #method.cancel_all
+    /// [`JobHandle`]: ./struct.JobHandle.html
+    pub fn scope<'env, F, R>(&'env self, f: F) -> R
+    where
+        F: FnOnce(&Scope<'env, T>) -> R,
+    {
+        let scope = Scope {
+            pool: self,
+            state: Arc::new(ScopeState { pending: Mutex::new(0), all_done: Condvar::new() }),
+            env: PhantomData,
+        };
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(&scope)));
+        while *scope.state.pending.lock().unwrap() > 0 && self.workers.step(None) {}
+        scope.state.wait();
+        result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+    }
+}
@@ -0,0 +1,279 @@
+//! Counting what the pool gets up to.
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::workers::Workers;
+
+// Bucket `i` of a histogram counts durations of less than 2^i microseconds (and at least
+// 2^(i-1), for `i` > 0).  The last bucket also counts anything longer.
+const BUCKETS: usize = 36;
+
+/// A snapshot of what a [`ThreadPool`] is doing, and has done, returned by
+/// [`ThreadPool::stats()`].
+///
+/// [`ThreadPool`]: ./struct.ThreadPool.html
+/// [`ThreadPool::stats()`]: ./struct.ThreadPool.html#method.stats
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolStats {
+    /// How many threads are in the pool.
+    pub threads: usize,
+
+    /// How many jobs are waiting to be run.
+    pub queued: usize,
+
+    /// How many jobs are running.
+    pub running: usize,
+
+    /// How many jobs have run to completion.
+    pub completed: u64,
+
+    /// How many jobs have panicked.
+    pub panicked: u64,
+
+    /// How many jobs were cancelled before they got to run.
+    pub cancelled: u64,
+
+    /// Per-worker statistics.  Workers that have exited leave their entry behind, to be
+    /// taken over by the next worker to start.
+    pub workers: Vec<WorkerStats>,
+
+    /// How long jobs spent waiting between being scheduled and starting to run.
+    pub queue_wait: Histogram,
+
+    /// How long jobs took to run.
+    pub execution: Histogram,
+}
+
+/// Statistics for one of a [`ThreadPool`]'s workers.
+///
+/// [`ThreadPool`]: ./struct.ThreadPool.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkerStats {
+    /// Whether there's a worker running in this slot at the moment.
+    pub active: bool,
+
+    /// How many jobs have been run in this slot.
+    pub jobs: u64,
+
+    /// How long has been spent running jobs in this slot.
+    pub busy: Duration,
+}
+
+/// A distribution of durations, in buckets whose bounds are powers of two microseconds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Histogram {
+    buckets: Vec<u64>,
+    total: Duration,
+    max: Duration,
+}
+
+impl Histogram {
+    /// How many durations have been recorded.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().sum()
+    }
+
+    /// The mean duration, or `None` if nothing's been recorded.
+    #[must_use]
+    pub fn mean(&self) -> Option<Duration> {
+        match self.count() {
+            0 => None,
+            count => Some(Duration::from_nanos((self.total.as_nanos() / u128::from(count)) as u64)),
+        }
+    }
+
+    /// The longest duration recorded.
+    #[must_use]
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// An upper bound on the given percentile (between 0 and 100) of the durations
+    /// recorded, or `None` if nothing's been recorded.  This is the upper bound of the
+    /// bucket that the percentile falls in, or the longest duration recorded if that's
+    /// less.
+    #[must_use]
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+        let rank = ((percentile / 100.0) * count as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (index, bucket) in self.buckets.iter().enumerate() {
+            seen += bucket;
+            if seen >= rank {
+                return Some(std::cmp::min(bucket_limit(index), self.max));
+            }
+        }
+        Some(self.max)
+    }
+
+    /// The non-empty buckets, as pairs of the bucket's (exclusive) upper bound and how many
+    /// durations fell into it.
+    pub fn buckets(&self) -> impl Iterator<Item = (Duration, u64)> + '_ {
+        self.buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(index, &count)| (bucket_limit(index), count))
+    }
+}
+
+fn bucket_limit(index: usize) -> Duration {
+    Duration::from_micros(1 << index)
+}
+
+// A histogram that can be recorded into from any thread.
+pub(crate) struct AtomicHistogram {
+    buckets: Vec<AtomicU64>,
+    total_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+}
+
+impl AtomicHistogram {
+    fn new() -> Self {
+        Self {
+            buckets: (0..BUCKETS).map(|_| AtomicU64::new(0)).collect(),
+            total_nanos: AtomicU64::new(0),
+            max_nanos: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, duration: Duration) {
+        let micros = duration.as_micros();
+        let index = std::cmp::min((128 - micros.leading_zeros()) as usize, BUCKETS - 1);
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        let nanos = duration.as_nanos() as u64;
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> Histogram {
+        Histogram {
+            buckets: self.buckets.iter().map(|bucket| bucket.load(Ordering::Relaxed)).collect(),
+            total: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            max: Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+// The pool-wide counters.  Everything that's per-worker lives in the workers' slots.
+pub(crate) struct Metrics {
+    running: AtomicUsize,
+    completed: AtomicU64,
+    panicked: AtomicU64,
+    cancelled: AtomicU64,
+    queue_wait: AtomicHistogram,
+    execution: AtomicHistogram,
+}
+
+impl Metrics {
+    pub(crate) fn new() -> Self {
+        Self {
+            running: AtomicUsize::new(0),
+            completed: AtomicU64::new(0),
+            panicked: AtomicU64::new(0),
+            cancelled: AtomicU64::new(0),
+            queue_wait: AtomicHistogram::new(),
+            execution: AtomicHistogram::new(),
+        }
+    }
+
+    pub(crate) fn job_cancelled(&self) {
+        self.cancelled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn job_started(&self, queue_wait: Duration) {
+        self.running.fetch_add(1, Ordering::SeqCst);
+        self.queue_wait.record(queue_wait);
+    }
+
+    pub(crate) fn job_finished(&self, execution: Duration, panicked: bool) {
+        self.execution.record(execution);
+        if panicked {
+            self.panicked.fetch_add(1, Ordering::SeqCst);
+        } else {
+            self.completed.fetch_add(1, Ordering::SeqCst);
+        }
+        self.running.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    pub(crate) fn snapshot(&self, threads: usize, queued: usize, workers: Vec<WorkerStats>) -> PoolStats {
+        PoolStats {
+            threads,
+            queued,
+            running: self.running.load(Ordering::SeqCst),
+            completed: self.completed.load(Ordering::SeqCst),
+            panicked: self.panicked.load(Ordering::SeqCst),
+            cancelled: self.cancelled.load(Ordering::Relaxed),
+            workers,
+            queue_wait: self.queue_wait.snapshot(),
+            execution: self.execution.snapshot(),
+        }
+    }
+}
+
+// A thread that reports the pool's statistics every so often, until it's stopped.
+pub(crate) struct Reporter {
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    thread: JoinHandle<()>,
+}
+
+impl Reporter {
+    pub(crate) fn start(workers: Arc<Workers>, interval: Duration, mut report: impl FnMut(&PoolStats) + Send + 'static) -> Self {
+        let stop = Arc::new((Mutex::new(false), Condvar::new()));
+        let thread_stop = Arc::clone(&stop);
+        let thread = thread::spawn(move || {
+            let (stopped, wake) = &*thread_stop;
+            let mut stopped = stopped.lock().unwrap();
+            loop {
+                let (guard, result) = wake.wait_timeout(stopped, interval).unwrap();
+                stopped = guard;
+                if *stopped {
+                    break;
+                }
+                if result.timed_out() {
+                    report(&workers.stats());
+                }
+            }
+        });
+        Self { stop, thread }
+    }
+
+    pub(crate) fn stop(self) {
+        *self.stop.0.lock().unwrap() = true;
+        self.stop.1.notify_all();
+        let _ = self.thread.join();
+    }
+}
+
+// Reports a deterministic pool's statistics each time its virtual clock reaches a multiple of
+// `interval`.  There's no thread to do that in the background, so the pool calls `report`
+// itself as it's run by hand.
+pub(crate) struct Ticker {
+    interval: Duration,
+    next: Duration,
+    report: Box<dyn FnMut(&PoolStats) + Send>,
+}
+
+impl Ticker {
+    pub(crate) fn new(now: Duration, interval: Duration, report: Box<dyn FnMut(&PoolStats) + Send>) -> Self {
+        assert!(interval > Duration::ZERO, "A deterministic pool can't report stats with no interval between reports");
+        Self { interval, next: now + interval, report }
+    }
+
+    // When the next report is due.
+    pub(crate) fn next(&self) -> Duration {
+        self.next
+    }
+
+    pub(crate) fn report(&mut self, stats: &PoolStats) {
+        (self.report)(stats);
+        self.next += self.interval;
+    }
+}
@@ -0,0 +1,684 @@
+//! The pool's worker threads, and the queues they take work from.
+//!
+//! Each worker has its own deque, and normal priority jobs are dealt out to those in turn.
+//! A worker that runs out of work steals half of another's deque.  There are also shared
+//! queues for each priority: high priority jobs are taken before anything else, low
+//! priority ones only when there's nothing else to do, and the normal priority one catches
+//! anything that can't go on a worker's deque.
+//!
+//! The number of workers can change over time.  Workers are started when the pool is
+//! resized, or when work arrives and all of them are busy (as long as there are fewer than
+//! the maximum); and they retire when the pool is resized, or when they've been idle for a
+//! while (as long as there are more than the minimum).
+//!
+//! A deterministic pool has no threads at all.  Its jobs are run on whichever thread asks
+//! for them to be, one at a time, as if by a number of virtual workers, against a virtual
+//! clock.  Each virtual worker is free from the time its last job finished, and the next
+//! job always goes to the worker that's free soonest, so the order in which jobs run (and
+//! what the clock says while they're running) depends only on what's scheduled when.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::{Priority, Task};
+use crate::clock::Clock;
+use crate::stats::{Metrics, PoolStats, Ticker, WorkerStats};
+
+// The order in which a deterministic pool's virtual workers look through the shared queues.
+const PRIORITY_ORDER: [Priority; 3] = [Priority::High, Priority::Normal, Priority::Low];
+
+// A worker's deque.  Slots outlive the workers using them, and are reused.
+struct Slot {
+    queue: Mutex<VecDeque<Task>>,
+
+    // Whether there's a worker taking work from this slot.  Only changed with `queue`
+    // locked, so that nothing can be pushed onto the slot after its worker has left.
+    active: AtomicBool,
+
+    // How many jobs have been run from this slot, and how long they took in all.
+    jobs: AtomicU64,
+    busy_nanos: AtomicU64,
+}
+
+impl Slot {
+    fn new() -> Self {
+        Self {
+            queue: Mutex::new(VecDeque::new()),
+            active: AtomicBool::new(true),
+            jobs: AtomicU64::new(0),
+            busy_nanos: AtomicU64::new(0),
+        }
+    }
+
+    fn stats(&self) -> WorkerStats {
+        WorkerStats {
+            active: self.active.load(Ordering::SeqCst),
+            jobs: self.jobs.load(Ordering::Relaxed),
+            busy: Duration::from_nanos(self.busy_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+// What a deterministic pool's virtual workers are up to.
+struct Simulation {
+    // When the most recent job started.  Nothing can start before this.
+    now: Duration,
+
+    // When each virtual worker is next free, or `None` if it's in the middle of a job.
+    free_at: Vec<Option<Duration>>,
+
+    // How many jobs are running - more than one when a job waits on another.
+    depth: usize,
+}
+
+impl Simulation {
+    // When the virtual worker that's free soonest is free, and which one it is, or `None`
+    // if they're all busy.
+    fn soonest_free(&self) -> Option<(Duration, usize)> {
+        self.free_at.iter().enumerate().filter_map(|(index, free_at)| free_at.map(|free_at| (free_at, index))).min()
+    }
+}
+
+pub(crate) struct Workers {
+    slots: RwLock<Vec<Arc<Slot>>>,
+
+    // Which slot the next normal priority job goes on.
+    next_slot: AtomicUsize,
+
+    // The shared queues, indexed by priority, and how many tasks are on each.
+    shared: [Mutex<VecDeque<Task>>; 3],
+    shared_queued: [AtomicUsize; 3],
+
+    // How many tasks there are across all the queues (or about to be - this goes up just
+    // before a task is pushed).
+    queued: AtomicUsize,
+
+    // How many workers there are, and how many of them are asleep (or on their way to
+    // sleep) waiting for work.
+    live: AtomicUsize,
+    sleepers: AtomicUsize,
+
+    min_threads: AtomicUsize,
+    max_threads: AtomicUsize,
+    idle_timeout: Option<Duration>,
+
+    // Whether the pool is shutting down, which is also what sleeping workers wait on.
+    shutting_down: Mutex<bool>,
+    wake: Condvar,
+
+    // Signalled (with `shutting_down` locked) whenever `live` changes.
+    live_changed: Condvar,
+
+    threads: Mutex<Vec<JoinHandle<()>>>,
+
+    metrics: Metrics,
+    clock: Clock,
+
+    // Only for deterministic pools.
+    simulation: Option<Mutex<Simulation>>,
+    tickers: Mutex<Vec<Ticker>>,
+}
+
+impl Workers {
+    // Create the pool's workers, starting with `min_threads` of them.
+    pub(crate) fn start(min_threads: usize, max_threads: usize, idle_timeout: Option<Duration>) -> Arc<Self> {
+        let workers = Arc::new(Self::new(min_threads, max_threads, idle_timeout, Clock::real(), None));
+        for _ in 0..min_threads {
+            workers.add_worker();
+        }
+        workers
+    }
+
+    // Create a deterministic pool's virtual workers.
+    pub(crate) fn start_deterministic(num_threads: usize) -> Arc<Self> {
+        let simulation = Simulation { now: Duration::ZERO, free_at: vec![Some(Duration::ZERO); num_threads], depth: 0 };
+        let workers = Self::new(num_threads, num_threads, None, Clock::virtual_clock(), Some(simulation));
+        *workers.slots.write().unwrap() = (0..num_threads).map(|_| Arc::new(Slot::new())).collect();
+        workers.live.store(num_threads, Ordering::SeqCst);
+        Arc::new(workers)
+    }
+
+    fn new(min_threads: usize, max_threads: usize, idle_timeout: Option<Duration>, clock: Clock, simulation: Option<Simulation>) -> Self {
+        Self {
+            slots: RwLock::new(Vec::with_capacity(max_threads)),
+            next_slot: AtomicUsize::new(0),
+            shared: [Mutex::new(VecDeque::new()), Mutex::new(VecDeque::new()), Mutex::new(VecDeque::new())],
+            shared_queued: [AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0)],
+            queued: AtomicUsize::new(0),
+            live: AtomicUsize::new(0),
+            sleepers: AtomicUsize::new(0),
+            min_threads: AtomicUsize::new(min_threads),
+            max_threads: AtomicUsize::new(max_threads),
+            idle_timeout,
+            shutting_down: Mutex::new(false),
+            wake: Condvar::new(),
+            live_changed: Condvar::new(),
+            threads: Mutex::new(Vec::new()),
+            metrics: Metrics::new(),
+            clock,
+            simulation: simulation.map(Mutex::new),
+            tickers: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    pub(crate) fn is_deterministic(&self) -> bool {
+        self.simulation.is_some()
+    }
+
+    // Something for a handle to run jobs on while it's waiting, if nothing else is going to.
+    pub(crate) fn driver(self: &Arc<Self>) -> Option<Arc<Self>> {
+        self.simulation.as_ref().map(|_| Arc::clone(self))
+    }
+
+    pub(crate) fn live_threads(&self) -> usize {
+        self.live.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn max_threads(&self) -> usize {
+        self.max_threads.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn stats(&self) -> PoolStats {
+        let workers = self.slots.read().unwrap().iter().map(|slot| slot.stats()).collect();
+        self.metrics.snapshot(self.live_threads(), self.queued.load(Ordering::SeqCst), workers)
+    }
+
+    // Change the limits on the number of workers, starting or retiring workers to match.
+    pub(crate) fn resize(self: &Arc<Self>, min_threads: usize, max_threads: usize) {
+        self.min_threads.store(min_threads, Ordering::SeqCst);
+        self.max_threads.store(max_threads, Ordering::SeqCst);
+        if let Some(simulation) = &self.simulation {
+            let mut simulation = simulation.lock().unwrap();
+            let now = simulation.now;
+            simulation.free_at.resize(max_threads, Some(now));
+            let mut slots = self.slots.write().unwrap();
+            while slots.len() < max_threads {
+                slots.push(Arc::new(Slot::new()));
+            }
+            for (index, slot) in slots.iter().enumerate() {
+                slot.active.store(index < max_threads, Ordering::SeqCst);
+            }
+            self.live.store(max_threads, Ordering::SeqCst);
+            drop(slots);
+            drop(simulation);
+            self.notify_live_changed();
+            return;
+        }
+        while self.live.load(Ordering::SeqCst) < min_threads && self.add_worker() {}
+
+        // Any surplus workers will notice and retire once they wake up.
+        let _guard = self.shutting_down.lock().unwrap();
+        self.wake.notify_all();
+    }
+
+    pub(crate) fn push(self: &Arc<Self>, mut task: Task, priority: Priority) {
+        task.queued_at = self.clock.now();
+        self.queued.fetch_add(1, Ordering::SeqCst);
+        if self.is_deterministic() {
+            // There's nobody to deal it out to, or to wake up.
+            self.push_shared(task, priority);
+            return;
+        }
+        match priority {
+            Priority::Normal => {
+                if let Err(task) = self.push_to_slot(task) {
+                    self.push_shared(task, Priority::Normal);
+                }
+            }
+            _ => self.push_shared(task, priority),
+        }
+
+        // A worker going to sleep registers as a sleeper before checking `queued`, and we
+        // bump `queued` before checking for sleepers, so at least one of us sees the other.
+        // Taking the lock means that we can't notify in between its check and its wait.
+        let sleepers = self.sleepers.load(Ordering::SeqCst);
+        if sleepers > 0 {
+            let _guard = self.shutting_down.lock().unwrap();
+            self.wake.notify_one();
+        }
+
+        // If there's more work waiting than there are idle workers to pick it up, start
+        // another worker, if we're allowed one.
+        if self.queued.load(Ordering::SeqCst) > sleepers && self.live.load(Ordering::SeqCst) < self.max_threads() {
+            self.add_worker();
+        }
+    }
+
+    // Deal the task out to the next active worker's deque, or hand it back if there aren't any.
+    fn push_to_slot(&self, task: Task) -> Result<(), Task> {
+        let slots = self.slots.read().unwrap();
+        for _ in 0..slots.len() {
+            let slot = &slots[self.next_slot.fetch_add(1, Ordering::Relaxed) % slots.len()];
+            let mut queue = slot.queue.lock().unwrap();
+            if slot.active.load(Ordering::SeqCst) {
+                queue.push_back(task);
+                return Ok(());
+            }
+        }
+        Err(task)
+    }
+
+    fn push_shared(&self, task: Task, priority: Priority) {
+        let mut queue = self.shared[priority as usize].lock().unwrap();
+        self.shared_queued[priority as usize].fetch_add(1, Ordering::SeqCst);
+        queue.push_back(task);
+    }
+
+    fn pop_shared(&self, priority: Priority) -> Option<Task> {
+        if self.shared_queued[priority as usize].load(Ordering::SeqCst) == 0 {
+            return None;
+        }
+        let task = self.shared[priority as usize].lock().unwrap().pop_front();
+        if task.is_some() {
+            self.shared_queued[priority as usize].fetch_sub(1, Ordering::SeqCst);
+        }
+        task
+    }
+
+    // Start a new worker, if we're allowed another.  Returns whether we did.
+    fn add_worker(self: &Arc<Self>) -> bool {
+        let mut live = self.live.load(Ordering::SeqCst);
+        loop {
+            if live >= self.max_threads() {
+                return false;
+            }
+            match self.live.compare_exchange(live, live + 1, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => break,
+                Err(actual) => live = actual,
+            }
+        }
+        self.notify_live_changed();
+
+        // Reuse a slot that's been left behind by a retired worker, if there is one.
+        let index = {
+            let mut slots = self.slots.write().unwrap();
+            let free = slots.iter().position(|slot| {
+                // Claim the slot if nobody's using it.
+                let _queue = slot.queue.lock().unwrap();
+                !slot.active.swap(true, Ordering::SeqCst)
+            });
+            free.unwrap_or_else(|| {
+                slots.push(Arc::new(Slot::new()));
+                slots.len() - 1
+            })
+        };
+        self.start_thread(index);
+        true
+    }
+
+    fn start_thread(self: &Arc<Self>, index: usize) {
+        let workers = Arc::clone(self);
+        let thread = thread::spawn(move || {
+            while let Some(task) = workers.next_task(index) {
+                if task.token.is_cancelled() {
+                    // Nobody wants this any more.  Dropping it lets anyone waiting on it
+                    // know that it isn't going to happen.
+                    workers.metrics.job_cancelled();
+                    continue;
+                }
+                if workers.run_task(index, task) {
+                    // The job panicked.  Its result has been reported, so bow out in
+                    // favour of a fresh thread - there's no telling what state the job
+                    // left this one in.  The replacement takes over this thread's slot.
+                    workers.start_thread(index);
+                    break;
+                }
+            }
+        });
+
+        // Forget about any threads that have already finished, so that the list doesn't
+        // grow forever as workers come and go.
+        let mut threads = self.threads.lock().unwrap();
+        threads.retain(|thread| !thread.is_finished());
+        threads.push(thread);
+    }
+
+    // Run a task for the worker in slot `index`, keeping count.  Returns whether the job
+    // panicked.
+    fn run_task(&self, index: usize, task: Task) -> bool {
+        let started = self.clock.now();
+        self.metrics.job_started(started.saturating_sub(task.queued_at));
+        let panicked = (task.run)();
+        let elapsed = self.clock.now().saturating_sub(started);
+        self.metrics.job_finished(elapsed, panicked);
+        if let Some(slot) = self.slots.read().unwrap().get(index) {
+            slot.jobs.fetch_add(1, Ordering::Relaxed);
+            slot.busy_nanos.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+        }
+        panicked
+    }
+
+    // Block until there's a task for the worker in slot `index` to run, or until it's time
+    // for the worker to retire.
+    fn next_task(self: &Arc<Self>, index: usize) -> Option<Task> {
+        loop {
+            if self.live.load(Ordering::SeqCst) > self.max_threads() && self.retire(index, self.max_threads()) {
+                return None;
+            }
+            if let Some(task) = self.find_task(index) {
+                return Some(task);
+            }
+
+            let mut shutting_down = self.shutting_down.lock().unwrap();
+            self.sleepers.fetch_add(1, Ordering::SeqCst);
+            let mut timed_out = false;
+            while self.queued.load(Ordering::SeqCst) == 0 &&
+                  !*shutting_down &&
+                  self.live.load(Ordering::SeqCst) <= self.max_threads() {
+                match self.idle_timeout {
+                    Some(timeout) => {
+                        let (guard, result) = self.wake.wait_timeout(shutting_down, timeout).unwrap();
+                        shutting_down = guard;
+                        if result.timed_out() {
+                            timed_out = true;
+                            break;
+                        }
+                    }
+                    None => shutting_down = self.wake.wait(shutting_down).unwrap(),
+                }
+            }
+            self.sleepers.fetch_sub(1, Ordering::SeqCst);
+            if *shutting_down && self.queued.load(Ordering::SeqCst) == 0 {
+                drop(shutting_down);
+                self.retire(index, 0);
+                return None;
+            }
+            drop(shutting_down);
+
+            if timed_out &&
+               self.queued.load(Ordering::SeqCst) == 0 &&
+               self.retire(index, self.min_threads.load(Ordering::SeqCst)) {
+                return None;
+            }
+        }
+    }
+
+    fn find_task(&self, index: usize) -> Option<Task> {
+        if let Some(task) = self.pop_shared(Priority::High) {
+            return Some(self.dequeued(task));
+        }
+
+        let slots = self.slots.read().unwrap();
+        if let Some(task) = slots[index].queue.lock().unwrap().pop_front() {
+            return Some(self.dequeued(task));
+        }
+        if let Some(task) = self.pop_shared(Priority::Normal) {
+            return Some(self.dequeued(task));
+        }
+
+        // Nothing of our own to do, so go round the other workers, and steal half of the
+        // first non-empty deque we find.  Never hold two deques' locks at once.
+        for offset in 1..slots.len() {
+            let mut stolen = {
+                let mut victim = slots[(index + offset) % slots.len()].queue.lock().unwrap();
+                let keep = victim.len() / 2;
+                victim.split_off(keep)
+            };
+            if let Some(task) = stolen.pop_front() {
+                if !stolen.is_empty() {
+                    slots[index].queue.lock().unwrap().append(&mut stolen);
+                }
+                return Some(self.dequeued(task));
+            }
+        }
+
+        self.pop_shared(Priority::Low).map(|task| self.dequeued(task))
+    }
+
+    fn dequeued(&self, task: Task) -> Task {
+        self.queued.fetch_sub(1, Ordering::SeqCst);
+        task
+    }
+
+    // Retire the worker in slot `index`, as long as that leaves at least `floor` workers.
+    // Returns whether it retired.
+    fn retire(self: &Arc<Self>, index: usize, floor: usize) -> bool {
+        let mut live = self.live.load(Ordering::SeqCst);
+        loop {
+            if live <= floor {
+                return false;
+            }
+            match self.live.compare_exchange(live, live - 1, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => break,
+                Err(actual) => live = actual,
+            }
+        }
+        self.notify_live_changed();
+
+        // Give up the slot, passing on anything that was dealt to it after we last looked.
+        let leftovers: Vec<Task> = {
+            let slots = self.slots.read().unwrap();
+            let mut queue = slots[index].queue.lock().unwrap();
+            slots[index].active.store(false, Ordering::SeqCst);
+            queue.drain(..).collect()
+        };
+        for task in leftovers {
+            self.push_shared(task, Priority::Normal);
+        }
+
+        // If that was the last worker, but more work has turned up in the meantime, someone
+        // needs to do it.
+        if self.live.load(Ordering::SeqCst) == 0 && self.queued.load(Ordering::SeqCst) > 0 {
+            self.add_worker();
+        }
+        true
+    }
+
+    fn notify_live_changed(&self) {
+        let _guard = self.shutting_down.lock().unwrap();
+        self.live_changed.notify_all();
+    }
+
+    // Block until there are exactly `num_threads` workers.  Only tests need to wait for
+    // the pool to grow or shrink, rather than just asking how big it is.
+    #[cfg(test)]
+    pub(crate) fn wait_for_live_threads(&self, num_threads: usize) {
+        let mut guard = self.shutting_down.lock().unwrap();
+        while self.live.load(Ordering::SeqCst) != num_threads {
+            guard = self.live_changed.wait(guard).unwrap();
+        }
+    }
+
+    // In a deterministic pool, run the next job on the virtual worker that's free soonest, as
+    // long as it would start no later than `limit`.  Returns whether there was one to run.
+    pub(crate) fn step(&self, limit: Option<Duration>) -> bool {
+        let simulation = match &self.simulation {
+            Some(simulation) => simulation,
+            None => return false,
+        };
+
+        // Make any reports that are due by the time the next job starts, while it's still
+        // queued.
+        if let Some(start) = self.next_start(simulation, limit) {
+            self.tick(start);
+        }
+
+        let next = {
+            let mut simulation = simulation.lock().unwrap();
+
+            // If every worker is busy, they must all be waiting on jobs that can't be run.
+            let (free_at, index) = match simulation.soonest_free() {
+                Some(free) => free,
+                None => return false,
+            };
+            let (task, start) = match self.pop_ready(std::cmp::max(free_at, simulation.now), limit) {
+                Some(next) => next,
+                None => return false,
+            };
+            if task.token.is_cancelled() {
+                Err(task)
+            } else {
+                simulation.now = std::cmp::max(simulation.now, start);
+                simulation.free_at[index] = None;
+                simulation.depth += 1;
+                Ok((index, start, task))
+            }
+        };
+        let (index, start, task) = match next {
+            Ok(next) => next,
+            Err(_cancelled) => {
+                self.metrics.job_cancelled();
+                return true;
+            }
+        };
+
+        let outer = self.clock.now();
+        self.clock.set(start);
+        self.run_task(index, task);
+        let finished = self.clock.now();
+
+        let mut simulation = simulation.lock().unwrap();
+        if let Some(free_at) = simulation.free_at.get_mut(index) {
+            *free_at = Some(finished);
+        }
+        simulation.depth -= 1;
+        self.clock.set(if simulation.depth == 0 { simulation.now } else { outer });
+        true
+    }
+
+    // When `step()` would start the next job, if there are any reports waiting for it to
+    // find out.
+    fn next_start(&self, simulation: &Mutex<Simulation>, limit: Option<Duration>) -> Option<Duration> {
+        if self.tickers.lock().unwrap().is_empty() {
+            return None;
+        }
+        let simulation = simulation.lock().unwrap();
+        let (free_at, _) = simulation.soonest_free()?;
+        let (_, _, start) = find_ready(&self.lock_queues(), std::cmp::max(free_at, simulation.now))?;
+        if limit.is_some_and(|limit| start > limit) { None } else { Some(start) }
+    }
+
+    // Take the task that a virtual worker that's free at `ready` would pick up, along with
+    // when it would start.  Leaves it be if it wouldn't start by `limit`.
+    fn pop_ready(&self, ready: Duration, limit: Option<Duration>) -> Option<(Task, Duration)> {
+        let mut queues = self.lock_queues();
+        let (which, position, start) = find_ready(&queues, ready)?;
+        if limit.is_some_and(|limit| start > limit) {
+            return None;
+        }
+        let task = queues[which].remove(position).unwrap();
+        self.shared_queued[PRIORITY_ORDER[which] as usize].fetch_sub(1, Ordering::SeqCst);
+        Some((self.dequeued(task), start))
+    }
+
+    // The shared queues, highest priority first.
+    fn lock_queues(&self) -> Vec<MutexGuard<'_, VecDeque<Task>>> {
+        PRIORITY_ORDER.iter().map(|&priority| self.shared[priority as usize].lock().unwrap()).collect()
+    }
+
+    // Run a deterministic pool's jobs until there are none left, and move the clock on to
+    // when the last of them finishes.
+    pub(crate) fn run_until_idle(&self) {
+        while self.step(None) {}
+        let finished = match &self.simulation {
+            Some(simulation) => {
+                let mut simulation = simulation.lock().unwrap();
+                if simulation.depth > 0 {
+                    return;
+                }
+                let finished = simulation.free_at.iter().flatten().max().copied().unwrap_or_default();
+                simulation.now = std::cmp::max(simulation.now, finished);
+                self.clock.set(simulation.now);
+                simulation.now
+            }
+            None => return,
+        };
+        self.tick(finished);
+    }
+
+    // Run a deterministic pool's jobs until the clock has moved on by `duration`.
+    pub(crate) fn advance(&self, duration: Duration) {
+        let simulation = match &self.simulation {
+            Some(simulation) => simulation,
+            None => return,
+        };
+        let limit = simulation.lock().unwrap().now + duration;
+        while self.step(Some(limit)) {}
+        {
+            let mut simulation = simulation.lock().unwrap();
+            simulation.now = std::cmp::max(simulation.now, limit);
+            if simulation.depth == 0 {
+                self.clock.set(simulation.now);
+            }
+        }
+        self.tick(limit);
+    }
+
+    // Have a deterministic pool call `report` every `interval` of virtual time, as it's run.
+    pub(crate) fn report_on_ticks(&self, interval: Duration, report: Box<dyn FnMut(&PoolStats) + Send>) {
+        let now = self.clock.now();
+        self.tickers.lock().unwrap().push(Ticker::new(now, interval, report));
+    }
+
+    // Make any reports that are due by `time`, in order, with the clock showing when each
+    // one was due.
+    fn tick(&self, time: Duration) {
+        let mut tickers = self.tickers.lock().unwrap();
+        if tickers.is_empty() {
+            return;
+        }
+        let outer = self.clock.now();
+        while let Some(ticker) = tickers.iter_mut().filter(|ticker| ticker.next() <= time).min_by_key(|ticker| ticker.next()) {
+            self.clock.set(ticker.next());
+            ticker.report(&self.stats());
+        }
+        self.clock.set(outer);
+    }
+
+    // Stop reporting on ticks, letting go of the reports.
+    pub(crate) fn stop_ticking(&self) {
+        self.tickers.lock().unwrap().clear();
+    }
+
+    // Tell the workers to finish up once they've run out of work.  A deterministic pool
+    // has nobody to do that in the background, so does it there and then.
+    pub(crate) fn shut_down(&self) {
+        self.run_until_idle();
+        *self.shutting_down.lock().unwrap() = true;
+        self.wake.notify_all();
+    }
+
+    // Shut down, and wait for the workers to finish.
+    pub(crate) fn join(&self) {
+        self.shut_down();
+
+        // Replacement threads can be started while we're waiting, so keep going until
+        // there are none left.
+        loop {
+            let threads: Vec<JoinHandle<()>> = self.threads.lock().unwrap().drain(..).collect();
+            if threads.is_empty() {
+                break;
+            }
+            for thread in threads {
+                thread.join().unwrap();
+            }
+        }
+    }
+}
+
+// Find the task that a virtual worker that's free at `ready` would pick up from `queues`
+// (highest priority first), returning which queue it's in, where, and when it would start:
+// it's the first task in priority order that's been queued by then, or failing that,
+// whichever is queued soonest afterwards.
+fn find_ready(queues: &[MutexGuard<'_, VecDeque<Task>>], ready: Duration) -> Option<(usize, usize, Duration)> {
+    let waiting = queues.iter().enumerate().find_map(|(which, queue)| {
+        queue.iter().position(|task| task.queued_at <= ready).map(|position| (which, position))
+    });
+    let (which, position) = match waiting {
+        Some(found) => found,
+        None => {
+            let soonest = queues.iter().enumerate()
+                .flat_map(|(which, queue)| queue.iter().enumerate().map(move |(position, task)| (task.queued_at, which, position)))
+                .min()?;
+            (soonest.1, soonest.2)
+        }
+    };
+    Some((which, position, std::cmp::max(ready, queues[which][position].queued_at)))
+}
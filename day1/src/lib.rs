@@ -0,0 +1,65 @@
+use std::io;
+
+use aoc_core::{Answer, ParseError, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_modules(input)?)
+    }
+
+    fn part1(modules: &Self::Input) -> Answer {
+        modules.iter().copied().map(|module| fuel_for_weight(module, false)).sum::<i32>().into()
+    }
+
+    fn part2(modules: &Self::Input) -> Answer {
+        modules.iter().copied().map(|module| fuel_for_weight(module, true)).sum::<i32>().into()
+    }
+}
+
+fn parse_modules(modules: &str) -> Result<Vec<i32>, io::Error> {
+    modules
+        .lines()
+        .map(|line| line.parse::<i32>()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err)))
+        .collect()
+}
+
+fn fuel_for_weight(weight: i32, include_fuel_for_fuel: bool) -> i32 {
+    let mut total_fuel = 0;
+    let mut weight_just_added = weight;
+    loop {
+        match (weight_just_added / 3) - 2 {
+            fuel if fuel >= 0 => {
+                weight_just_added = fuel;
+                total_fuel += fuel;
+            },
+            _ => break,
+        }
+        if !include_fuel_for_fuel { break; }
+    }
+    total_fuel
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuel_for_modules() {
+        assert_eq!(fuel_for_weight(12, false), 2);
+        assert_eq!(fuel_for_weight(14, false), 2);
+        assert_eq!(fuel_for_weight(1969, false), 654);
+        assert_eq!(fuel_for_weight(100756, false), 33583);
+    }
+
+    #[test]
+    fn fuel_for_fuel() {
+        assert_eq!(fuel_for_weight(14, true), 2);
+        assert_eq!(fuel_for_weight(1969, true), 966);
+        assert_eq!(fuel_for_weight(100756, true), 50346);
+    }
+}
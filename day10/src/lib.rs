@@ -0,0 +1,389 @@
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+
+use std::cell::OnceCell;
+use std::io;
+
+use aoc_core::{Answer, ParseError, Solution};
+use grid::{Grid, Point};
+
+// Once again, today's code is verbose, but both performant and (hopefully) easy to follow.
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Input = Asteroids;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Asteroids {
+            map: parse_map(input)?,
+            station: OnceCell::new(),
+        })
+    }
+
+    // Find the best location for a base.
+    fn part1(asteroids: &Self::Input) -> Answer {
+        asteroids.station().visible_asteroids.into()
+    }
+
+    fn part2(asteroids: &Self::Input) -> Answer {
+        // Start from the best location for a base, and the map with visibility as calculated
+        // from that position.
+        let station = asteroids.station();
+        let best_position = station.position;
+        let mut map = station.map.clone();
+
+        // `find_best_position` already calculated the visibility of all other asteroids from the
+        // chosen location - we also need to calculate the angles.
+        calculate_angles(&mut map, best_position);
+
+        // Let's avoid having to continually skip over ourselves!
+        map.positions[best_position].asteroid = false;
+
+        // IMMA FIRIN MAH LAZOR
+        let final_asteroid = destroy_asteroids(&mut map, 200, best_position);
+        ((final_asteroid.x * 100) + final_asteroid.y).into()
+    }
+
+    // Where the station went.
+    fn extra(asteroids: &Self::Input) -> Vec<(&'static str, Answer)> {
+        vec![("station", asteroids.station().position.to_string().into())]
+    }
+}
+
+pub struct Asteroids {
+    map: Map,
+    // The best location for a base, which both parts and the extras share.
+    station: OnceCell<Station>,
+}
+
+impl Asteroids {
+    fn station(&self) -> &Station {
+        self.station.get_or_init(|| find_best_position(&self.map))
+    }
+}
+
+// The best location for a base, how many asteroids can be seen from it, and the map with
+// visibility calculated from there.
+struct Station {
+    position: Point,
+    visible_asteroids: usize,
+    map: Map,
+}
+
+// Destroy `quantity` asteroids, and return the last asteroid destroyed.
+fn destroy_asteroids(map: &mut Map, quantity: usize, source: Point) -> Point {
+    // Starting from a negative angle means the first asteroid we find will be one with angle 0.0.
+    let mut last_angle = -1.0;
+    let mut asteroids_destroyed = 0;
+
+    loop {
+        let (asteroid, angle) = find_next_asteroid(map, last_angle);
+        destroy_asteroid(map, source, asteroid);
+        last_angle = angle;
+
+        asteroids_destroyed += 1;
+        if asteroids_destroyed == quantity {
+            break asteroid;
+        }
+    }
+}
+
+fn _print_map(map: &Map, position: Point, asteroid: Point) {
+    let mut drawing = String::new();
+    for (point, contents) in map.positions.iter() {
+        if point.x == 0 {
+            drawing.push('\n');
+        }
+        if point == position {
+            drawing.push('B');
+        } else if point == asteroid {
+            drawing.push('#');
+        } else if contents.asteroid {
+            drawing.push('o');
+        } else {
+            drawing.push(' ');
+        }
+    }
+    println!("{}", drawing);
+}
+
+// Given the angle of the last asteroid we destroyed, figure out the next one we find rotating
+// clockwise. It's the visible asteroid whose angle is strictly larger than the last one, but by
+// the least amount. When we complete a circle, we'll fail to find one, in which case we start
+// again from a negative value (which makes asteroids with angle 0.0 valid).
+fn find_next_asteroid(map: &Map, last_angle: f64) -> (Point, f64) {
+    let (next_asteroid, best_angle) = map
+        .asteroids
+        .iter()
+        .filter(|asteroid| {
+            // Filter out asteroids that are...
+            map.positions[**asteroid].asteroid && // already destroyed
+            map.positions[**asteroid].visible && // hidden behind still-existing ones
+            map.positions[**asteroid].angle > last_angle // counter-clockwise from here
+        })
+        .fold((None, 2.0 * std::f64::consts::PI), |acc, asteroid| {
+            if map.positions[*asteroid].angle < acc.1 {
+                // New best asteroid
+                (Some(*asteroid), map.positions[*asteroid].angle)
+            } else {
+                acc
+            }
+        });
+
+    if let Some(asteroid) = next_asteroid {
+        (asteroid, best_angle)
+    } else {
+        // We didn't find one - reset the angle to negative and try again.
+        find_next_asteroid(map, -1.0)
+    }
+}
+
+// Blow up an asteroid and reveal one behind it, if any.
+fn destroy_asteroid(map: &mut Map, source: Point, asteroid: Point) {
+    assert!(map.positions[asteroid].visible);
+    map.positions[asteroid].asteroid = false;
+
+    let delta = calculate_visibility_delta(source, asteroid);
+    let mut pos = asteroid + delta;
+    while let Some(position) = map.positions.get_mut(pos) {
+        if position.asteroid {
+            assert!(!position.visible);
+            position.visible = true;
+            break;
+        }
+        pos += delta;
+    }
+}
+
+// Update all asteroids with a calculated angle from the specified source.
+fn calculate_angles(map: &mut Map, source: Point) {
+    for (point, position) in map.positions.iter_mut() {
+        position.angle = angle(point - source);
+    }
+}
+
+// Calculate the angle towards a co-ordinate, in radians from 0 to 2*PI,
+// with straight up being 0 and increasing clockwise.
+fn angle(delta: Point) -> f64 {
+    if (delta.x == 0) && (delta.y < 0) {
+        // Straight up
+        0.0
+    } else {
+        (-delta.x as f64).atan2(delta.y as f64) + std::f64::consts::PI
+    }
+}
+
+// The part 1 problem - find the asteroid that can see the most other asteroids. We take a pretty
+// brute-force approach here:
+// -  For each asteroid:
+//    -  Start with a fresh map
+//    -  For each other asteroid, cross off every location that's rendered invisible behind it
+//    -  Count how many visible asteroids are left
+//    -  Keep track of the best we've found.
+//
+// Determining which asteroids
+//
+// Returns the best position, how many asteroids can be seen from it, and what the map looks like
+// from there.
+fn find_best_position(map: &Map) -> Station {
+    let mut best_position = Point::ORIGIN;
+    let mut most_visible_asteroids = 0;
+    let mut best_positions = None;
+    for possible_location in &map.asteroids {
+        let mut positions = map.positions.clone();
+
+        for asteroid in &map.asteroids {
+            mark_hidden_positions(&mut positions, *possible_location, *asteroid);
+        }
+
+        let visible_asteroids = map
+            .asteroids
+            .iter()
+            .filter(|asteroid| positions[**asteroid].visible)
+            .count()
+            - 1; // Knock off one to avoid including ourselves
+        if visible_asteroids > most_visible_asteroids {
+            most_visible_asteroids = visible_asteroids;
+            best_position = *possible_location;
+            best_positions = Some(positions);
+        }
+    }
+
+    Station {
+        position: best_position,
+        visible_asteroids: most_visible_asteroids,
+        map: Map {
+            positions: best_positions.unwrap(),
+            asteroids: map.asteroids.clone(),
+        },
+    }
+}
+
+// Figure out and cross off positions that are hidden from view by a specified asteroid from a
+// specified starting location.
+//
+// The mechanic for this is:
+// -  Figure out the coordinate delta from source to target
+// -  Calculate at what further deltas beyond that target positions are hidden
+// -  From the target, take adjusted delta steps, marking each one as invisible, until we
+//    reach the edge of the map.
+fn mark_hidden_positions(positions: &mut Grid<Position>, source: Point, asteroid: Point) {
+    if asteroid == source {
+        return; // Skip our own asteroid
+    }
+
+    let delta = calculate_visibility_delta(source, asteroid);
+    let mut pos = asteroid + delta;
+    while let Some(position) = positions.get_mut(pos) {
+        position.visible = false;
+        pos += delta;
+    }
+}
+
+// Looking from a starting point towards an asteroid, figure out the steps beyond that asteroid in
+// which positions are hidden by that asteroid. E.g. if we're looking at an asteroid that's [6, -3]
+// away, it hides everything that's in steps of [2, -1] beyond it.
+//
+// That's calculated by dividing the deltas by their greatest common denominator.
+fn calculate_visibility_delta(source: Point, target: Point) -> Point {
+    let delta = target - source;
+    let gcd = greatest_common_denominator(delta.x.unsigned_abs(), delta.y.unsigned_abs()) as i64;
+    Point::new(delta.x / gcd, delta.y / gcd)
+}
+
+// I stole this.  I don't do maths.
+fn greatest_common_denominator(u: u64, v: u64) -> u64 {
+    if u == v {
+        u
+    } else if u == 0 {
+        v
+    } else if v == 0 {
+        u
+    } else if (!u & 1) != 0 {
+        // u is even
+        if (v & 1) == 0 {
+            // both even
+            greatest_common_denominator(u >> 1, v >> 1) << 1
+        } else {
+            // v is odd
+            greatest_common_denominator(u >> 1, v)
+        }
+    } else if (!v & 1) != 0 {
+        // u is odd, v is even
+        greatest_common_denominator(u, v >> 1)
+    } else if u > v {
+        // Reduce larger argument
+        greatest_common_denominator((u - v) >> 1, v)
+    } else {
+        greatest_common_denominator((v - u) >> 1, u)
+    }
+}
+
+// A record of what we know about the asteroid field: everything we know about each location,
+// and where the asteroids were to start with.
+#[derive(Clone)]
+struct Map {
+    positions: Grid<Position>,
+    asteroids: Vec<Point>,
+}
+
+// A location in space.
+// -  `asteroid` records whether there's an asteroid in it.  Initially this is loaded from the
+//    input map, but mutated once we start destroying asteroids in part 2.
+// -  `visible` and `angle` are N/A until we've determined the location for the base in part 1.
+//    They're then calculated from the perspective of the selected location.  They're only
+//    applicable to locations with asteroids.
+#[derive(Clone, Copy)]
+struct Position {
+    asteroid: bool,
+    visible: bool,
+    angle: f64,
+}
+
+impl Position {
+    fn new(asteroid: bool) -> Self {
+        Position {
+            asteroid,
+            visible: true,
+            angle: 0.0,
+        }
+    }
+}
+
+fn parse_map(data: &str) -> Result<Map, io::Error> {
+    let positions = Grid::parse(data)?.map(|&c| Position::new(c == '#'));
+    let asteroids = positions
+        .iter()
+        .filter(|(_, position)| position.asteroid)
+        .map(|(point, _)| point)
+        .collect();
+    Ok(Map {
+        positions,
+        asteroids,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LARGE_EXAMPLE: &str = "\
+.#..##.###...#######
+##.############..##.
+.#.######.########.#
+.###.#######.####.#.
+#####.##.#.##.###.##
+..#####..#.#########
+####################
+#.####....###.#.#.##
+##.#################
+#####.##.###..####..
+..######..##.#######
+####.##.####...##..#
+.#####..#.######.###
+##...#.##########...
+#.##########.#######
+.####.#.###.###.#.##
+....##.##.###..#####
+.#.#.###########.###
+#.#.#.#####.####.###
+###.##.####.##.#..##";
+
+    fn best_position(map: &str) -> (Point, usize) {
+        let station = find_best_position(&parse_map(map).unwrap());
+        (station.position, station.visible_asteroids)
+    }
+
+    #[test]
+    fn best_positions() {
+        assert_eq!(best_position(".#..#\n.....\n#####\n....#\n...##"), (Point::new(3, 4), 8));
+        assert_eq!(
+            best_position(
+                "......#.#.\n#..#.#....\n..#######.\n.#.#.###..\n.#..#.....\n\
+                 ..#....#.#\n#..#....#.\n.##.#..###\n##...#..#.\n.#....####"
+            ),
+            (Point::new(5, 8), 33)
+        );
+        assert_eq!(best_position(LARGE_EXAMPLE), (Point::new(11, 13), 210));
+    }
+
+    #[test]
+    fn vaporisation() {
+        let asteroids = Day10::parse(LARGE_EXAMPLE).unwrap();
+        assert_eq!(Day10::part1(&asteroids), Answer::from(210));
+        assert_eq!(Day10::part2(&asteroids), Answer::from(802));
+        assert_eq!(Day10::extra(&asteroids), vec![("station", Answer::from(String::from("11,13")))]);
+    }
+
+    #[test]
+    fn angles() {
+        assert_eq!(angle(Point::new(0, -1)), 0.0);
+        assert!((angle(Point::new(1, 0)) - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+        assert!((angle(Point::new(0, 1)) - std::f64::consts::PI).abs() < 1e-9);
+        assert!(angle(Point::new(-1, -1)) > angle(Point::new(-1, 0)));
+    }
+}
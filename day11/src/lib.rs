@@ -0,0 +1,207 @@
+use std::cell::OnceCell;
+use std::sync::mpsc;
+
+use aoc_core::{Answer, ParseError, Solution};
+use grid::{Direction, Grid, Point, SparseGrid, Turn};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Input = Hull;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Hull {
+            program: intcode::parse_program(input)?,
+            registration: OnceCell::new(),
+        })
+    }
+
+    fn part1(hull: &Self::Input) -> Answer {
+        run_paint_sequence(&hull.program, false).count_painted_panels().into()
+    }
+
+    // If the registration can't be read, give the painting instead, for a person to read.
+    // The extras say why it couldn't be read.
+    fn part2(hull: &Self::Input) -> Answer {
+        let robot = hull.registration();
+        match robot.painting().read_letters() {
+            Ok(registration) => registration.into(),
+            Err(_) => Answer::Image(robot.draw_painting()),
+        }
+    }
+
+    fn extra(hull: &Self::Input) -> Vec<(&'static str, Answer)> {
+        let robot = hull.registration();
+        let mut extra = vec![("painting", Answer::Image(robot.draw_painting()))];
+        if let Err(error) = robot.painting().read_letters() {
+            extra.push(("ocr_error", Answer::Text(error.to_string())));
+        }
+        extra
+    }
+}
+
+pub struct Hull {
+    program: Vec<i64>,
+    // The robot once it's painted the registration, which part 2 and the picture share.
+    registration: OnceCell<Robot>,
+}
+
+impl Hull {
+    fn registration(&self) -> &Robot {
+        self.registration.get_or_init(|| run_paint_sequence(&self.program, true))
+    }
+}
+
+fn run_paint_sequence(program: &[i64], paint_current_panel: bool) -> Robot {
+    let (in_send, in_recv) = mpsc::channel();
+    let (out_send, out_recv) = mpsc::channel();
+    let mut computer = intcode::ChannelIOComputer::new(program, in_recv, out_send);
+    std::thread::spawn(move || {
+        computer.run();
+    });
+
+    let mut robot = Robot::new();
+    if paint_current_panel {
+        robot.paint(1);
+    }
+
+    loop {
+        if in_send.send(robot.get_current_color()).is_err() {
+            break;
+        }
+        match out_recv.recv() {
+            Ok(val) => match val {
+                0 | 1 => robot.paint(val),
+                _ => unreachable!("Invalid paint instruction received: {}", val),
+            },
+            Err(_) => break,
+        };
+        match out_recv.recv() {
+            Ok(val) => match val {
+                0 => robot.turn_and_move(Turn::Left),
+                1 => robot.turn_and_move(Turn::Right),
+                _ => unreachable!("Invalid turn instruction received: {}", val),
+            },
+            Err(_) => break,
+        };
+    }
+
+    robot
+}
+
+struct Robot {
+    position: Point,
+    dir: Direction,
+    panels: SparseGrid<Panel>,
+}
+
+impl Robot {
+    fn new() -> Self {
+        Self {
+            position: Point::ORIGIN,
+            dir: Direction::Up,
+            panels: SparseGrid::new(),
+        }
+    }
+
+    fn count_painted_panels(&self) -> usize {
+        self.panels.iter().filter(|(_, panel)| panel.painted).count()
+    }
+
+    fn paint(&mut self, color: i64) {
+        self.get_current_panel().paint(color);
+    }
+
+    fn get_current_color(&mut self) -> i64 {
+        self.get_current_panel().color
+    }
+
+    fn turn_and_move(&mut self, turn: Turn) {
+        self.dir = self.dir.turn(turn);
+        self.position += self.dir;
+    }
+
+    fn get_current_panel(&mut self) -> &mut Panel {
+        self.panels.entry(self.position).or_insert_with(Panel::new)
+    }
+
+    // Which panels are white, out of every panel the robot has visited.  Panels it hasn't
+    // visited are still black.
+    fn painting(&self) -> Grid<bool> {
+        let white: SparseGrid<bool> =
+            self.panels.iter().map(|(pos, panel)| (pos, panel.color == 1)).collect();
+        white.to_grid(false).0
+    }
+
+    // Draws every panel the robot has visited.  Panels it hasn't visited are still black.
+    fn draw_painting(&self) -> Vec<String> {
+        self.panels.render(|panel| match panel.map_or(0, |panel| panel.color) {
+            1 => '#',
+            _ => ' ',
+        })
+    }
+}
+
+struct Panel {
+    color: i64,
+    painted: bool,
+}
+
+impl Panel {
+    fn new() -> Self {
+        Self {
+            color: 0,
+            painted: false,
+        }
+    }
+
+    fn paint(&mut self, color: i64) {
+        self.color = color;
+        self.painted = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let mut robot = Robot::new();
+        let instructions = [(1, Turn::Left), (0, Turn::Left), (1, Turn::Left), (1, Turn::Left),
+                            (0, Turn::Right), (1, Turn::Left), (1, Turn::Left)];
+        for (color, turn) in instructions.iter() {
+            robot.paint(*color);
+            robot.turn_and_move(*turn);
+        }
+        assert_eq!(robot.count_painted_panels(), 6);
+        assert_eq!(robot.draw_painting(), vec!["  #", "  #", "## "]);
+    }
+
+    #[test]
+    fn reading_the_painting() {
+        let mut robot = Robot::new();
+        let letter = ["#...", "#...", "#...", "#...", "#...", "####"];
+        for (y, row) in letter.iter().enumerate() {
+            for (x, panel) in row.chars().enumerate() {
+                robot.position = Point::new(x as i64 + 3, y as i64 - 2);
+                robot.paint(if panel == '#' { 1 } else { 0 });
+            }
+        }
+        assert_eq!(robot.painting().read_letters().unwrap(), "L");
+        let hull = Hull { program: Vec::new(), registration: OnceCell::from(robot) };
+        assert_eq!(Day11::part2(&hull), Answer::from("L"));
+        assert_eq!(Day11::extra(&hull).len(), 1);
+    }
+
+    #[test]
+    fn unreadable_painting() {
+        let mut robot = Robot::new();
+        robot.paint(1);
+        let hull = Hull { program: Vec::new(), registration: OnceCell::from(robot) };
+        assert_eq!(Day11::part2(&hull), Answer::Image(vec![String::from("#")]));
+        let error = String::from("Letters should be 6 or 10 pixels high, not 1");
+        assert_eq!(Day11::extra(&hull)[1], ("ocr_error", Answer::Text(error)));
+    }
+}
@@ -1,5 +1,7 @@
 use std::cell::RefCell;
 
+use aoc_core::{Answer, ParseError, Solution};
+
 extern crate num;
 extern crate primes;
 #[macro_use]
@@ -24,7 +26,7 @@ fn simulate(moons: &[RefCell<MoonDimension>]) -> u64 {
 fn perform_step(moons: &[RefCell<MoonDimension>]) {
     for this_moon_cell in moons {
         for other_moon_cell in moons {
-            if this_moon_cell as *const _ == other_moon_cell as *const _ {
+            if std::ptr::eq(this_moon_cell, other_moon_cell) {
                 continue;
             }
             calc_vel_change(this_moon_cell, other_moon_cell);
@@ -54,67 +56,79 @@ fn apply_vel_change(moon_cell: &RefCell<MoonDimension>) {
     moon.pos += moon.vel;
 }
 
-fn main() {
-    let start_time = std::time::Instant::now();
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    type Input = Vec<Vec<RefCell<MoonDimension>>>;
 
     // There's no relationship between the three dimensions - what happens in one is entirely
     // independent of what happens in the other two.  We're going to make use of that to
     // simulate them independently, so store them independently.
-    let dimensions = vec![
-        vec![
-            RefCell::new(MoonDimension { pos: -10, vel: 0 }),
-            RefCell::new(MoonDimension { pos: 5, vel: 0 }),
-            RefCell::new(MoonDimension { pos: 3, vel: 0 }),
-            RefCell::new(MoonDimension { pos: 1, vel: 0 }),
-        ],
-        vec![
-            RefCell::new(MoonDimension { pos: -10, vel: 0 }),
-            RefCell::new(MoonDimension { pos: 5, vel: 0 }),
-            RefCell::new(MoonDimension { pos: 8, vel: 0 }),
-            RefCell::new(MoonDimension { pos: 3, vel: 0 }),
-        ],
-        vec![
-            RefCell::new(MoonDimension { pos: -13, vel: 0 }),
-            RefCell::new(MoonDimension { pos: -9, vel: 0 }),
-            RefCell::new(MoonDimension { pos: -16, vel: 0 }),
-            RefCell::new(MoonDimension { pos: -3, vel: 0 }),
-        ],
-    ];
-
-    // We're going to mutate the data, so use a different copy for each part.
-    let part_1_data = dimensions.clone();
-    let part_2_data = dimensions;
-
-    // Part 1 - just perform a thousand iterations on the whole thing.
-    for _ in 0..1_000 {
-        for dimension in &part_1_data {
-            perform_step(dimension);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut dimensions = vec![Vec::new(), Vec::new(), Vec::new()];
+        for line in input.lines() {
+            for (dimension, pos) in dimensions.iter_mut().zip(parse_position(line)?.iter()) {
+                dimension.push(RefCell::new(MoonDimension { pos: *pos, vel: 0 }));
+            }
         }
+        Ok(dimensions)
+    }
+
+    // Just perform a thousand iterations on the whole thing.
+    fn part1(dimensions: &Self::Input) -> Answer {
+        total_energy_after(dimensions, 1_000).into()
     }
-    let total_energy: i32 = transpose_data(&part_1_data)
-        .iter()
-        .map(Moon::total_energy)
-        .sum();
 
-    // Part 2 - figure out how many steps required in each dimension to return to the initial
+    // Figure out how many steps required in each dimension to return to the initial
     // state (the dimensions are independent, rememeber).  The total steps for a complete
     // return to the initial state is then the lowest common multiple of the steps for each
     // individual dimension.  Because the dimensions are independent, let's run the
     // simulations on separate threads for a bit more juicy speed.
-    let threads = part_2_data.iter().map(move |dimension| {
-        let moved_dimension = dimension.to_vec();
-        std::thread::spawn(move || simulate(&moved_dimension))
-    }).collect::<Vec<_>>();
-
-    let steps = threads.into_iter().map(|thread| thread.join().unwrap()).collect::<Vec<_>>();
-    let iterations_to_reset = lowest_common_multiple(&steps);
-
-    println!(
-        "Part 1: {}\nPart 2: {}\nTime: {}ms",
-        total_energy,
-        iterations_to_reset,
-        start_time.elapsed().as_millis()
-    );
+    fn part2(dimensions: &Self::Input) -> Answer {
+        let threads = dimensions.iter().map(move |dimension| {
+            let moved_dimension = dimension.to_vec();
+            std::thread::spawn(move || simulate(&moved_dimension))
+        }).collect::<Vec<_>>();
+
+        let steps = threads.into_iter().map(|thread| thread.join().unwrap()).collect::<Vec<_>>();
+        lowest_common_multiple(&steps).into()
+    }
+}
+
+// Perform some number of steps on the whole system, and return the total energy afterwards.
+// We're going to mutate the data, so work on a copy.
+fn total_energy_after(dimensions: &[Vec<RefCell<MoonDimension>>], steps: usize) -> i32 {
+    let data = dimensions.to_vec();
+    for _ in 0..steps {
+        for dimension in &data {
+            perform_step(dimension);
+        }
+    }
+    transpose_data(&data)
+        .iter()
+        .map(Moon::total_energy)
+        .sum()
+}
+
+// Each line of the input is a moon's position, e.g. "<x=-1, y=0, z=2>".
+fn parse_position(line: &str) -> Result<[i32; 3], ParseError> {
+    let invalid = || ParseError::new(format!("Not a position: {}", line));
+    let coords = line
+        .trim()
+        .strip_prefix('<')
+        .and_then(|line| line.strip_suffix('>'))
+        .ok_or_else(invalid)?;
+    let mut position = [0; 3];
+    let mut coords = coords.split(',');
+    for (axis, pos) in ["x=", "y=", "z="].iter().zip(position.iter_mut()) {
+        let coord = coords.next().ok_or_else(invalid)?.trim();
+        *pos = coord.strip_prefix(axis).ok_or_else(invalid)?.parse()?;
+    }
+    if coords.next().is_some() {
+        return Err(invalid());
+    }
+    Ok(position)
 }
 
 // It was better for part 2 to have dimensions containing moons. But part 1 wants moons containing
@@ -163,7 +177,7 @@ fn lowest_common_multiple(nums: &[u64]) -> u64 {
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
-struct MoonDimension {
+pub struct MoonDimension {
     pos: i32,
     vel: i32,
 }
@@ -187,3 +201,46 @@ impl Moon {
         self.potential_energy() * self.kinetic_energy()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST_EXAMPLE: &str = "<x=-1, y=0, z=2>
+<x=2, y=-10, z=-7>
+<x=4, y=-8, z=8>
+<x=3, y=5, z=-1>";
+
+    const SECOND_EXAMPLE: &str = "<x=-8, y=-10, z=0>
+<x=5, y=5, z=10>
+<x=2, y=-7, z=3>
+<x=9, y=-8, z=-3>";
+
+    #[test]
+    fn energy() {
+        assert_eq!(total_energy_after(&Day12::parse(FIRST_EXAMPLE).unwrap(), 10), 179);
+        assert_eq!(total_energy_after(&Day12::parse(SECOND_EXAMPLE).unwrap(), 100), 1940);
+    }
+
+    #[test]
+    fn repetition() {
+        let first = Day12::parse(FIRST_EXAMPLE).unwrap();
+        assert_eq!(Day12::part2(&first), Answer::from(2772));
+        let second = Day12::parse(SECOND_EXAMPLE).unwrap();
+        assert_eq!(Day12::part2(&second), Answer::from(4_686_774_924_u64));
+    }
+
+    #[test]
+    fn lcm() {
+        assert_eq!(lowest_common_multiple(&[4, 6]), 12);
+        assert_eq!(lowest_common_multiple(&[18, 28, 44]), 2772);
+        assert_eq!(lowest_common_multiple(&[7]), 7);
+    }
+
+    #[test]
+    fn positions() {
+        assert_eq!(parse_position("<x=-1, y=0, z=2>").unwrap(), [-1, 0, 2]);
+        assert!(parse_position("<x=-1, y=0>").is_err());
+        assert!(parse_position("x=-1, y=0, z=2").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+
+use std::cell::OnceCell;
+use std::sync::mpsc;
+
+use aoc_core::{Answer, ParseError, Solution};
+use grid::{Point, SparseGrid};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    type Input = Arcade;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Arcade {
+            program: intcode::parse_program(input)?,
+            first_screen: OnceCell::new(),
+        })
+    }
+
+    // Run the computer as provided - it just outputs a single screen and then exits.  We
+    // just want to know the number of block tiles, so we skip all the X and Y coordinates, and
+    // see how many tiles are type 2 (block).
+    fn part1(arcade: &Self::Input) -> Answer {
+        arcade
+            .first_screen()
+            .iter()
+            .skip(2)
+            .step_by(3)
+            .filter(|tile_id| **tile_id == 2)
+            .count()
+            .into()
+    }
+
+    // Run the computer again with a modified address 0. This time it'll run in
+    // "interactive" mode.
+    fn part2(arcade: &Self::Input) -> Answer {
+        let mut program = arcade.program.clone();
+        program[0] = 2;
+        let (in_send, in_recv) = mpsc::channel();
+        // Output is bounded to a single tile at a time, so the computer can't race ahead of us.
+        let (out_send, out_recv) = mpsc::sync_channel(3);
+        let mut computer = intcode::ChannelIOComputer::new_bounded(&program, in_recv, out_send);
+        std::thread::spawn(move || { computer.run(); });
+
+        // We're still going to ignore most of what the computer is telling us, though. All we
+        // need to do is (a) remember the score we've been told and (b) keep the paddle in line
+        // with the ball.  (The screen as it starts is in the extras, for anyone who wants to see
+        // it.)
+        let mut score = 0;
+        let mut paddle_x = 10;
+
+        while let Ok(x) = out_recv.recv() {
+            let y = out_recv.recv().unwrap();
+            let tile_id = out_recv.recv().unwrap();
+
+            if (x == -1) && (y == 0) {
+                score = tile_id;
+                continue;
+            }
+
+            if tile_id == 3 {
+                paddle_x = x;
+            } else if tile_id == 4 {
+                match x {
+                    less if less < paddle_x => in_send.send(-1).unwrap(),
+                    more if more > paddle_x => in_send.send(1).unwrap(),
+                    _ => in_send.send(0).unwrap(),
+                }
+            }
+        }
+
+        score.into()
+    }
+
+    fn extra(arcade: &Self::Input) -> Vec<(&'static str, Answer)> {
+        vec![("screen", Answer::Image(draw_screen(arcade.first_screen())))]
+    }
+}
+
+pub struct Arcade {
+    program: Vec<i64>,
+    // What the game outputs when it's run as provided, which part 1 and the picture share.
+    first_screen: OnceCell<Vec<i64>>,
+}
+
+impl Arcade {
+    fn first_screen(&self) -> &[i64] {
+        self.first_screen.get_or_init(|| intcode::run_parallel_computer(&self.program, &[]))
+    }
+}
+
+// Draws the screen from the tiles that the game outputs, three numbers at a time: walls are
+// #, blocks are =, the paddle is _ and the ball is o.  The score, at x = -1, isn't drawn.
+fn draw_screen(outputs: &[i64]) -> Vec<String> {
+    let tiles: SparseGrid<i64> = outputs
+        .chunks(3)
+        .filter(|tile| tile.len() == 3 && tile[0] >= 0)
+        .map(|tile| (Point::new(tile[0], tile[1]), tile[2]))
+        .collect();
+    tiles.render(|tile| match tile {
+        Some(1) => '#',
+        Some(2) => '=',
+        Some(3) => '_',
+        Some(4) => 'o',
+        _ => ' ',
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_blocks() {
+        // Draws a paddle at 1,2 and blocks at 6,5 and 7,5.
+        let arcade =
+            Day13::parse("104,1,104,2,104,3,104,6,104,5,104,2,104,7,104,5,104,2,99").unwrap();
+        assert_eq!(Day13::part1(&arcade), Answer::from(2));
+        let screen = ["_      ", "       ", "       ", "     =="];
+        let screen = screen.iter().map(|row| row.to_string()).collect();
+        assert_eq!(Day13::extra(&arcade), vec![("screen", Answer::Image(screen))]);
+    }
+}
@@ -0,0 +1,319 @@
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+use std::io;
+
+use aoc_core::{Answer, ParseError, Solution};
+
+extern crate math;
+
+const ONE_TRILLION: u64 = 1_000_000_000_000;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Input = HashMap<String, Rc<RefCell<Chemical>>>;
+
+    // `parse_reactions` gives us a map of chemical name to a representation of that chemical.
+    // But it goes beyond that - behind the scenes, the chemicals are all connected, and
+    // understand their dependencies on one another.  Each chemical understands what it needs
+    // to make a certain quantity of itself, and what quantity of itself is needed to make
+    // what other chemicals need.
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_reactions(input)?)
+    }
+
+    // How much ORE is needed to make one FUEL.
+    fn part1(map: &Self::Input) -> Answer {
+        FuelDemand::new(map).ore_needed(1).into()
+    }
+
+    // How much FUEL can be made with a trillion ORE?
+    fn part2(map: &Self::Input) -> Answer {
+        let demand = FuelDemand::new(map);
+        let ore_for_one_fuel = demand.ore_needed(1);
+
+        // Let's start with a naive ratio - i.e. if we needed a billion ORE for one FUEL, let's
+        // see how much ORE is needed for a thousand FUEL.
+        let mut fuel_amount = (ONE_TRILLION / ore_for_one_fuel) + 1;
+        let initial_ore = demand.ore_needed(fuel_amount);
+
+        // That won't be anywhere near correct - the graph of ORE to FUEL will be nothing like
+        // a straight line due to the complexity of the dependencies.  We can get a whole lot
+        // closer, though, by adjusting by the ratio by which we were off first time.  E.g. if
+        // we wound up needing half a trillion ORE with the FUEL we guessed, let's try doubling
+        // the FUEL.
+        let correction_factor = ONE_TRILLION as f64 / initial_ore as f64;
+        fuel_amount = (fuel_amount as f64 * correction_factor) as u64;
+
+        // Now we can iterate one by one until correct.
+        loop {
+            if demand.ore_needed(fuel_amount) > ONE_TRILLION {
+                fuel_amount -= 1;
+                break;
+            } else {
+                fuel_amount += 1;
+            }
+        }
+        fuel_amount.into()
+    }
+}
+
+// To start with, there's no actual demand for anything, because while every chemical
+// understands what WOULD be needed to make some of itself, its parent chemical(s)
+// haven't asked it for anything.  To get things going, we construct a phantom parent of
+// FUEL that asks for however much FUEL we want.  Once we're done, it goes away again, so
+// the chemicals are ready for the next question.
+struct FuelDemand {
+    fuel: Rc<RefCell<Chemical>>,
+    ore: Rc<RefCell<Chemical>>,
+    req: Rc<RefCell<Requirement>>,
+}
+
+impl FuelDemand {
+    fn new(map: &HashMap<String, Rc<RefCell<Chemical>>>) -> Self {
+        let fuel = Rc::clone(map.get("FUEL").unwrap());
+        let ore = Rc::clone(map.get("ORE").unwrap());
+        let req = Rc::new(RefCell::new(Requirement {
+            // This says: I make one of myself with one FUEL, and none of me is needed yet.
+            chemical: Rc::downgrade(&fuel),
+            reaction_quantity: 1,
+            needed: 0,
+        }));
+        fuel.borrow_mut().needed_by.push(Rc::downgrade(&req));
+        Self { fuel, ore, req }
+    }
+
+    fn ore_needed(&self, fuel_amount: u64) -> u64 {
+        self.req.borrow_mut().needed = fuel_amount;
+
+        // Trigger calculation.  That flows all the way down, so throughout the tree we can
+        // see how much of each chemical is required - and everything ultimately comes from
+        // ORE.
+        recalculate_requirements(&mut self.fuel.borrow_mut());
+        self.ore.borrow().needed
+    }
+}
+
+impl Drop for FuelDemand {
+    fn drop(&mut self) {
+        self.fuel.borrow_mut().needed_by.retain(|weak| weak.as_ptr() != Rc::as_ptr(&self.req));
+    }
+}
+
+// Figure out how many of the specified chemical are needed to satisfy its parents' requirements.
+// Operates recursively to update its child chemicals.
+fn recalculate_requirements(chemical: &mut Chemical) {
+    // The demand for this chemical is the sum of its `needed_by` list.
+    chemical.needed = chemical
+        .needed_by
+        .iter()
+        .map(|weak| weak.upgrade().unwrap().borrow().needed)
+        .sum();
+
+    // We can't necessarily generate an arbitrary quantity of this chemical, however - each
+    // reaction generates a specific amount.  See how many reactions generate what we need
+    // with minimum wastage.
+    let reactions =
+        math::round::ceil(chemical.needed as f64 / chemical.creates_quantity as f64, 0) as u64;
+
+    // Update our requirements on our children accordingly, and recurse to them.
+    for req_cell in &chemical.reqs {
+        let mut req = req_cell.borrow_mut();
+        req.needed = reactions * req.reaction_quantity;
+        drop(req);
+        let req = req_cell.borrow();
+        let req_chemical_cell = req.chemical.upgrade().unwrap();
+        let mut req_chemical = req_chemical_cell.borrow_mut();
+        recalculate_requirements(&mut req_chemical);
+    }
+}
+
+pub struct Chemical {
+    reqs: Vec<Rc<RefCell<Requirement>>>,
+    creates_quantity: u64,
+    needed: u64,
+    needed_by: Vec<Weak<RefCell<Requirement>>>,
+}
+
+impl Chemical {
+    fn new() -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self {
+            reqs: Vec::new(),
+            creates_quantity: 0,
+            needed: 0,
+            needed_by: Vec::new(),
+        }))
+    }
+}
+
+struct Requirement {
+    chemical: Weak<RefCell<Chemical>>,
+    reaction_quantity: u64,
+    needed: u64,
+}
+
+impl Requirement {
+    fn new(chemical: &Rc<RefCell<Chemical>>, quantity: u64) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self {
+            chemical: Rc::downgrade(chemical),
+            reaction_quantity: quantity,
+            needed: 0,
+        }))
+    }
+}
+
+fn parse_reactions(reactions: &str) -> Result<HashMap<String, Rc<RefCell<Chemical>>>, io::Error> {
+    let mut map: HashMap<String, Rc<RefCell<Chemical>>> = HashMap::new();
+
+    for reaction in reactions.lines() {
+        let mut sides = reaction.split(" => ");
+        let inputs = sides
+            .next()
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Invalid input line: {}", reaction),
+                )
+            })?
+            .split(", ");
+        let output = sides.next().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid input line: {}", reaction),
+            )
+        })?;
+
+        let (output_quantity, output_name) = parse_quantity(output)?;
+        let output_chemical_cell = get_chemical(&mut map, output_name);
+        let mut output_chemical = output_chemical_cell.borrow_mut();
+        output_chemical.creates_quantity = output_quantity;
+
+        for input in inputs {
+            let (input_quantity, input_name) = parse_quantity(input)?;
+            let input_chemical_cell = get_chemical(&mut map, input_name);
+            let req = Requirement::new(&input_chemical_cell, input_quantity);
+            output_chemical.reqs.push(req.clone());
+            let mut input_chemical = input_chemical_cell.borrow_mut();
+            input_chemical.needed_by.push(Rc::downgrade(&req));
+        }
+    }
+
+    Ok(map)
+}
+
+fn parse_quantity(input: &str) -> Result<(u64, &str), io::Error> {
+    let mut inputs = input.split(' ');
+    let quantity_str = inputs.next().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid input data: {}", input),
+        )
+    })?;
+    let quantity = quantity_str
+        .parse::<u64>()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let name = inputs.next().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid input data: {}", input),
+        )
+    })?;
+    Ok((quantity, name))
+}
+
+fn get_chemical(
+    map: &mut HashMap<String, Rc<RefCell<Chemical>>>,
+    name: &str,
+) -> Rc<RefCell<Chemical>> {
+    map.entry(name.to_string()).or_insert_with(Chemical::new).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIMPLE_EXAMPLES: [(&str, u64); 2] = [
+        ("10 ORE => 10 A\n1 ORE => 1 B\n7 A, 1 B => 1 C\n7 A, 1 C => 1 D\n7 A, 1 D => 1 E\n\
+          7 A, 1 E => 1 FUEL", 31),
+        ("9 ORE => 2 A\n8 ORE => 3 B\n7 ORE => 5 C\n3 A, 4 B => 1 AB\n5 B, 7 C => 1 BC\n\
+          4 C, 1 A => 1 CA\n2 AB, 3 BC, 4 CA => 1 FUEL", 165),
+    ];
+
+    const LARGER_EXAMPLES: [(&str, u64, u64); 3] = [
+        ("157 ORE => 5 NZVS
+165 ORE => 6 DCFZ
+44 XJWVT, 5 KHKGT, 1 QDVJ, 29 NZVS, 9 GPVTF, 48 HKGWZ => 1 FUEL
+12 HKGWZ, 1 GPVTF, 8 PSHF => 9 QDVJ
+179 ORE => 7 PSHF
+177 ORE => 5 HKGWZ
+7 DCFZ, 7 PSHF => 2 XJWVT
+165 ORE => 2 GPVTF
+3 DCFZ, 7 NZVS, 5 HKGWZ, 10 PSHF => 8 KHKGT", 13312, 82_892_753),
+        ("2 VPVL, 7 FWMGM, 2 CXFTF, 11 MNCFX => 1 STKFG
+17 NVRVD, 3 JNWZP => 8 VPVL
+53 STKFG, 6 MNCFX, 46 VJHF, 81 HVMC, 68 CXFTF, 25 GNMV => 1 FUEL
+22 VJHF, 37 MNCFX => 5 FWMGM
+139 ORE => 4 NVRVD
+144 ORE => 7 JNWZP
+5 MNCFX, 7 RFSQX, 2 FWMGM, 2 VPVL, 19 CXFTF => 3 HVMC
+5 VJHF, 7 MNCFX, 9 VPVL, 37 CXFTF => 6 GNMV
+145 ORE => 6 MNCFX
+1 NVRVD => 8 CXFTF
+1 VJHF, 6 MNCFX => 4 RFSQX
+176 ORE => 6 VJHF", 180_697, 5_586_022),
+        ("171 ORE => 8 CNZTR
+7 ZLQW, 3 BMBT, 9 XCVML, 26 XMNCP, 1 WPTQ, 2 MZWV, 1 RJRHP => 4 PLWSL
+114 ORE => 4 BHXH
+14 VRPVC => 6 BMBT
+6 BHXH, 18 KTJDG, 12 WPTQ, 7 PLWSL, 31 FHTLT, 37 ZDVW => 1 FUEL
+6 WPTQ, 2 BMBT, 8 ZLQW, 18 KTJDG, 1 XMNCP, 6 MZWV, 1 RJRHP => 6 FHTLT
+15 XDBXC, 2 LTCX, 1 VRPVC => 6 ZLQW
+13 WPTQ, 10 LTCX, 3 RJRHP, 14 XMNCP, 2 MZWV, 1 ZLQW => 1 ZDVW
+5 BMBT => 4 WPTQ
+189 ORE => 9 KTJDG
+1 MZWV, 17 XDBXC, 3 XCVML => 2 XMNCP
+12 VRPVC, 27 CNZTR => 2 XDBXC
+15 KTJDG, 12 BHXH => 5 XCVML
+3 BHXH, 2 VRPVC => 7 MZWV
+121 ORE => 7 VRPVC
+7 XCVML => 6 RJRHP
+5 BHXH, 4 VRPVC => 5 LTCX", 2_210_736, 460_664),
+    ];
+
+    #[test]
+    fn ore_for_one_fuel() {
+        for (reactions, ore) in SIMPLE_EXAMPLES.iter() {
+            assert_eq!(FuelDemand::new(&parse_reactions(reactions).unwrap()).ore_needed(1), *ore);
+        }
+        for (reactions, ore, _) in LARGER_EXAMPLES.iter() {
+            assert_eq!(FuelDemand::new(&parse_reactions(reactions).unwrap()).ore_needed(1), *ore);
+        }
+    }
+
+    #[test]
+    fn fuel_for_a_trillion_ore() {
+        for (reactions, _, fuel) in LARGER_EXAMPLES.iter() {
+            assert_eq!(Day14::part2(&parse_reactions(reactions).unwrap()), Answer::from(*fuel));
+        }
+    }
+
+    #[test]
+    fn demands_dont_linger() {
+        let map = parse_reactions(SIMPLE_EXAMPLES[0].0).unwrap();
+        assert_eq!(FuelDemand::new(&map).ore_needed(2), 62);
+        assert_eq!(FuelDemand::new(&map).ore_needed(1), 31);
+    }
+
+    #[test]
+    fn bad_reactions() {
+        assert!(parse_reactions("10 ORE").is_err());
+        assert!(parse_reactions("ten ORE => 10 A").is_err());
+    }
+}
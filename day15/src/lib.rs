@@ -0,0 +1,278 @@
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+
+use std::cell::OnceCell;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use aoc_core::{Answer, ParseError, Solution};
+use grid::{Direction, Grid, Point, SparseGrid};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    type Input = Droidware;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Droidware { program: intcode::parse_program(input)?, ship: OnceCell::new() })
+    }
+
+    // We can use the completed map to solve both parts via breadth-first search.  Part 1 is
+    // shortest path from origin to oxygen system; part 2 is the "longest shortest path" from the
+    // oxygen system, i.e. the shortest path to the furthest-away point.
+    fn part1(droidware: &Self::Input) -> Answer {
+        let ship = droidware.ship();
+        search_maze(&ship.grid, ship.start, Some(ship.oxygen_system)).unwrap().into()
+    }
+
+    fn part2(droidware: &Self::Input) -> Answer {
+        let ship = droidware.ship();
+        search_maze(&ship.grid, ship.oxygen_system, None).unwrap().into()
+    }
+
+    fn extra(droidware: &Self::Input) -> Vec<(&'static str, Answer)> {
+        vec![("ship", Answer::Image(draw_ship(droidware.ship())))]
+    }
+}
+
+// The droid's Intcode program, and the map of the ship that the droid makes with it.
+pub struct Droidware {
+    program: Vec<i64>,
+    ship: OnceCell<Ship>,
+}
+
+impl Droidware {
+    // Firstly, use the droid (via the Intcode computer) to construct a map of the entire ship.
+    // Part 1 doesn't require that, but part 2 does, and having it up front makes part 1 simpler.
+    // Whichever part runs first does the exploring.
+    fn ship(&self) -> &Ship {
+        self.ship.get_or_init(|| {
+            let (in_send, in_recv) = mpsc::channel();
+            let (out_send, out_recv) = mpsc::channel();
+            let mut computer = intcode::ChannelIOComputer::new(&self.program, in_recv, out_send);
+            std::thread::spawn(move || { computer.run(); });
+
+            let mut droid = Droid::new(in_send, out_recv);
+            droid.explore_ship()
+        })
+    }
+}
+
+// Draws the map of the ship, with the droid's starting point as D and the oxygen system as O.
+fn draw_ship(ship: &Ship) -> Vec<String> {
+    let mut picture = ship.grid.clone();
+    picture[ship.oxygen_system] = 'O';
+    picture[ship.start] = 'D';
+    picture.render(|&c| c)
+}
+
+// Perform a breadth-first search of a supplied maze from a given starting point.
+// If `stop_at` is provided, returns the length of the shortest path to that point.
+// Otherwise, returns the length of the longest shortest path to any point.  Returns None if
+// the target can't be reached.
+fn search_maze(grid: &Grid<char>, from: Point, stop_at: Option<Point>) -> Option<usize> {
+    let open = |&pos: &Point| grid.neighbours(pos).filter(move |&next| grid[next] != '#');
+    let mut search = search::bfs(from, open);
+    let end = match stop_at {
+        Some(target) => search.find(|&(pos, _)| pos == target),
+        None => search.last(),
+    };
+    end.map(|(_, distance)| distance)
+}
+
+// The droid's program numbers the directions north, south, west and east.
+fn movement_command(dir: Direction) -> i64 {
+    match dir {
+        Direction::Up => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+        Direction::Right => 4,
+    }
+}
+
+enum MoveResult {
+    Wall,
+    Corridor,
+    OxygenSystem,
+}
+
+impl MoveResult {
+    fn from(output: i64) -> Self {
+        match output {
+            0 => Self::Wall,
+            1 => Self::Corridor,
+            2 => Self::OxygenSystem,
+            _ => panic!("Unexpected output from computer: {}", output),
+        }
+    }
+}
+
+// A repository of information about a ship layout that we've learned so far.
+struct LearnedShipInfo {
+    // All the grid locations we've learned about.
+    map: SparseGrid<char>,
+
+    // The location of the oxygen system.
+    oxygen_system: Point,
+
+    // Whether, in exploring this ship, we've moved away from the starting point.
+    // We store this because we're finished when we return to the starting point,
+    // but don't necessarily move off it immediately.
+    moved_off_starting_pos: bool,
+}
+
+impl LearnedShipInfo {
+    fn new() -> Self {
+        let mut info = Self {
+            map: SparseGrid::new(),
+            oxygen_system: Point::ORIGIN,
+            moved_off_starting_pos: false,
+        };
+        info.map.insert(Point::ORIGIN, '.');
+        info
+    }
+}
+
+// Complete information about the ship layout.
+struct Ship {
+    // A visual representation of the ship layout, with anywhere the droid never found out
+    // about left blank.
+    grid: Grid<char>,
+
+    // The droid's starting location in the ship.
+    start: Point,
+
+    // The location of the oxygen system.
+    oxygen_system: Point,
+}
+
+impl Ship {
+    // The droid started at the origin, but that's not necessarily the top left of the ship,
+    // so everything moves to fit in the grid.
+    fn construct(info: LearnedShipInfo) -> Self {
+        let (grid, delta) = info.map.to_grid(' ');
+        Self {
+            grid,
+            start: Point::ORIGIN + delta,
+            oxygen_system: info.oxygen_system + delta,
+        }
+    }
+}
+
+// Our representation of the droid that's exploring the ship, with which we
+// indirectly communicate via the Intcode computer.
+struct Droid {
+    // The droid's current location in the ship.
+    pos: Point,
+
+    // A channel to send instructions to the computer for moving the droid.
+    tx: Sender<i64>,
+
+    // A channel to receive movement results from the computer.
+    rx: Receiver<i64>,
+}
+
+impl Droid {
+    fn new(tx: Sender<i64>, rx: Receiver<i64>) -> Self {
+        Self {
+            pos: Point::ORIGIN,
+            tx,
+            rx,
+        }
+    }
+
+    // Fully explore the ship.
+    // Implements a basic "wall follower" algorithm, right-hand rule.
+    fn explore_ship(&mut self) -> Ship {
+        let mut learned_info = LearnedShipInfo::new();
+        let mut dir = Direction::Up;
+
+        while !learned_info.moved_off_starting_pos || self.pos != Point::ORIGIN {
+            dir = self.attempt_move(&mut learned_info, dir);
+        }
+
+        Ship::construct(learned_info)
+    }
+
+    // Try to move in the specified direction, record what we learn by so doing,
+    // and decide which direction to move in next. We simulate the droid keeping
+    // its right hand on a wall at all times, so it tries to turn right if it
+    // successfully moves forwards, and left if it hits a wall.
+    fn attempt_move(&mut self, info: &mut LearnedShipInfo, dir: Direction) -> Direction {
+        let target_pos = self.pos + dir;
+
+        self.tx.send(movement_command(dir)).unwrap();
+        let (map_char, new_dir, moved) = match MoveResult::from(self.rx.recv().unwrap()) {
+            MoveResult::Wall => ('#', dir.turn_left(), false),
+            MoveResult::Corridor => ('.', dir.turn_right(), true),
+            MoveResult::OxygenSystem => {
+                info.oxygen_system = target_pos;
+                ('.', dir.turn_right(), true)
+            }
+        };
+
+        if moved {
+            self.pos = target_pos;
+            info.moved_off_starting_pos = true;
+        }
+
+        info.map.insert(target_pos, map_char);
+
+        new_dir
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example from part 2.  The droid starts at the dead end in the top row.
+    const SHIP: [&str; 5] = [" ##   ", "#..## ", "#.#..#", "#.O.# ", " ###  "];
+
+    // Pretends to be the droid's program, moving around the ship as it's told to.
+    fn fake_droid(mut pos: Point) -> Droid {
+        let (in_send, in_recv) = mpsc::channel();
+        let (out_send, out_recv) = mpsc::channel();
+        std::thread::spawn(move || {
+            while let Ok(input) = in_recv.recv() {
+                let dir = match input {
+                    1 => Direction::Up,
+                    2 => Direction::Down,
+                    3 => Direction::Left,
+                    _ => Direction::Right,
+                };
+                let target = pos + dir;
+                let output = match SHIP[target.y as usize].as_bytes()[target.x as usize] {
+                    b'#' => 0,
+                    b'O' => 2,
+                    _ => 1,
+                };
+                if output != 0 {
+                    pos = target;
+                }
+                if out_send.send(output).is_err() {
+                    break;
+                }
+            }
+        });
+        Droid::new(in_send, out_recv)
+    }
+
+    #[test]
+    fn search() {
+        let grid = Grid::parse(&SHIP.join("\n")).unwrap();
+        let oxygen_system = Point::new(2, 3);
+        assert_eq!(search_maze(&grid, Point::new(1, 1), Some(oxygen_system)), Some(3));
+        assert_eq!(search_maze(&grid, oxygen_system, None), Some(4));
+    }
+
+    #[test]
+    fn explore() {
+        let ship = fake_droid(Point::new(2, 1)).explore_ship();
+        assert_eq!(search_maze(&ship.grid, ship.start, Some(ship.oxygen_system)), Some(4));
+        assert_eq!(search_maze(&ship.grid, ship.oxygen_system, None), Some(4));
+        assert_eq!(draw_ship(&ship), vec![" ##   ", "#.D## ", "#.#..#", "#.O.# ", " ###  "]);
+    }
+}
@@ -3,37 +3,45 @@
 #![allow(clippy::cast_possible_truncation)]
 #![allow(clippy::cast_possible_wrap)]
 
-use std::iter;
+use aoc_core::{Answer, ParseError, Solution};
 
-fn main() {
-    let start_time = std::time::Instant::now();
-    let signal = String::from("59755896917240436883590128801944128314960209697748772345812613779993681653921392130717892227131006192013685880745266526841332344702777305618883690373009336723473576156891364433286347884341961199051928996407043083548530093856815242033836083385939123450194798886212218010265373470007419214532232070451413688761272161702869979111131739824016812416524959294631126604590525290614379571194343492489744116326306020911208862544356883420805148475867290136336455908593094711599372850605375386612760951870928631855149794159903638892258493374678363533942710253713596745816693277358122032544598918296670821584532099850685820371134731741105889842092969953797293495");
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let signal = input.trim_end();
+        match signal.chars().find(|c| !c.is_ascii_digit()) {
+            Some(c) => Err(ParseError::new(format!("Not a digit: {}", c))),
+            None => Ok(String::from(signal)),
+        }
+    }
 
     // Part 1 performs FFT over the full signal, though in an optimised way.
     // See `_old_part_1` and related code for a pretty but non-optimised alternative!
-    let part_1_answer = full_fft(&signal, 100);
-
-    //03036732577212944063491565474664
-    //let part_2_answer = partial_fft("00000202577212944063491565474664", 1, 5);
-    let part_2_answer = partial_fft(&signal, 10000, 100);
-
-    // let offset: usize = signal[0..7].parse().unwrap();
-    // let partial_fft = PartialFFT::new(signal, 10000).unwrap();
-    // let part_2_answer = partial_fft
-    //     .skip(99)
-    //     .next()
-    //     .unwrap()
-    //     .iter()
-    //     .skip(offset)
-    //     .take(8)
-    //     .map(std::string::ToString::to_string)
-    //     .collect::<String>();
-    println!(
-        "Part 1: {}\nPart 2: {}\nTime: {}ms",
-        part_1_answer,
-        part_2_answer,
-        start_time.elapsed().as_millis()
-    );
+    fn part1(signal: &Self::Input) -> Answer {
+        full_fft(signal, 100).into()
+    }
+
+    fn part2(signal: &Self::Input) -> Answer {
+        //03036732577212944063491565474664
+        //partial_fft("00000202577212944063491565474664", 1, 5)
+        partial_fft(signal, 10000, 100).into()
+
+        // let offset: usize = signal[0..7].parse().unwrap();
+        // let partial_fft = PartialFFT::new(signal, 10000).unwrap();
+        // partial_fft
+        //     .skip(99)
+        //     .next()
+        //     .unwrap()
+        //     .iter()
+        //     .skip(offset)
+        //     .take(8)
+        //     .map(std::string::ToString::to_string)
+        //     .collect::<String>()
+    }
 }
 
 fn full_fft(signal: &str, iterations: usize) -> String {
@@ -177,13 +185,45 @@ impl Iterator for FullFFT {
     }
 }
 
-// This builds the pattern that would apply to the element in a given position.
+// This builds the pattern that would apply to the element in a given position: each value of
+// the base pattern repeated `position` times, over and over, skipping the very first value.
 fn fft_pattern(position: usize) -> impl Iterator<Item = i32> {
-    iter::repeat(0)
-        .take(position)
-        .chain(iter::repeat(1).take(position))
-        .chain(iter::repeat(0).take(position))
-        .chain(iter::repeat(-1).take(position))
-        .cycle()
-        .skip(1)
+    const BASE_PATTERN: [i32; 4] = [0, 1, 0, -1];
+    (1..).map(move |index| BASE_PATTERN[(index / position) % 4])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phases() {
+        assert_eq!(full_fft("12345678", 1), "48226158");
+        assert_eq!(full_fft("12345678", 2), "34040438");
+        assert_eq!(full_fft("12345678", 4), "01029498");
+
+        // The literal version gets the same results.
+        let phase = FullFFT::new("12345678").nth(3).unwrap();
+        assert_eq!(phase, vec![0, 1, 0, 2, 9, 4, 9, 8]);
+    }
+
+    #[test]
+    fn full_signals() {
+        assert_eq!(full_fft("80871224585914546619083218645595", 100), "24176176");
+        assert_eq!(full_fft("19617804207202209144916044189917", 100), "73745418");
+        assert_eq!(full_fft("69317163492948606335995924319873", 100), "52432133");
+    }
+
+    #[test]
+    fn real_signals() {
+        assert_eq!(partial_fft("03036732577212944063491565474664", 10000, 100), "84462026");
+        assert_eq!(partial_fft("02935109699940807407585447034323", 10000, 100), "78725270");
+        assert_eq!(partial_fft("03081770884921959731165446850517", 10000, 100), "53553731");
+    }
+
+    #[test]
+    fn patterns() {
+        assert_eq!(fft_pattern(1).take(8).collect::<Vec<_>>(), vec![1, 0, -1, 0, 1, 0, -1, 0]);
+        assert_eq!(fft_pattern(2).take(8).collect::<Vec<_>>(), vec![0, 1, 1, 0, 0, -1, -1, 0]);
+    }
 }
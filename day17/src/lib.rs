@@ -0,0 +1,294 @@
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+
+use std::cell::OnceCell;
+
+use aoc_core::{Answer, ParseError, Solution};
+use grid::{Direction, Grid, Point};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+    type Input = Camera;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Camera { program: intcode::parse_program(input)?, scaffold: OnceCell::new() })
+    }
+
+    // We've found each intersection twice, so we'll need to halve the result.
+    fn part1(camera: &Self::Input) -> Answer {
+        let answer = camera
+            .scaffold()
+            .intersections
+            .iter()
+            .map(|intersection| intersection.x * intersection.y)
+            .sum::<i64>()
+            / 2;
+        answer.into()
+    }
+
+    // Split the movement instructions into subroutines and a main routine, and feed them into
+    // the Intcode computer in movement mode.
+    fn part2(camera: &Self::Input) -> Answer {
+        let logic = MovementLogic::parse(&camera.scaffold().program);
+        let mut program = camera.program.clone();
+        program[0] = 2;
+        move_robot(&program, logic).into()
+    }
+
+    fn extra(camera: &Self::Input) -> Vec<(&'static str, Answer)> {
+        vec![("scaffold", Answer::Image(camera.scaffold().picture.render(|&c| c)))]
+    }
+}
+
+// The robot's Intcode program, and the scaffold that it shows us.
+pub struct Camera {
+    program: Vec<i64>,
+    scaffold: OnceCell<Scaffold>,
+}
+
+impl Camera {
+    fn scaffold(&self) -> &Scaffold {
+        self.scaffold.get_or_init(|| {
+            // Run the Intcode computer in camera mode to build a picture of the scaffold.
+            let picture = intcode::run_parallel_computer(&self.program, &[]);
+
+            // Parse the picture to generate a list of the intersections (needed for part 1) and
+            // a set of movement instructions to follow it (needed for part 2).
+            Scaffold::generate(&picture)
+        })
+    }
+}
+
+fn move_robot(program: &[i64], logic: MovementLogic) -> i64 {
+    let inputs = logic.into_intcode_inputs();
+    let outputs = intcode::run_parallel_computer(program, &inputs);
+    let final_output = outputs.last().unwrap();
+    assert!(*final_output > 255);
+    *final_output
+}
+
+struct MovementLogic {
+    main_routine: String,
+    subroutines: Vec<String>,
+}
+
+impl MovementLogic {
+    // Split the path into a main routine and at most three subroutines, none of them more than
+    // 20 characters long.  Each step along the path is a turn and a distance, e.g. "R,8".
+    fn parse(full_program: &str) -> Self {
+        let instructions: Vec<&str> = full_program.trim_end_matches(',').split(',').collect();
+        let steps: Vec<String> = instructions.chunks(2).map(|step| step.join(",")).collect();
+        let mut calls = Vec::new();
+        let mut subroutines = Vec::new();
+        assert!(
+            Self::compress(&steps, &mut calls, &mut subroutines),
+            "Can't split the path into subroutines"
+        );
+
+        Self {
+            main_routine: calls
+                .iter()
+                .map(|&call| ((b'A' + call as u8) as char).to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+            subroutines: subroutines.iter().map(|subroutine| subroutine.join(",")).collect(),
+        }
+    }
+
+    // Work through the steps in order: either call a subroutine we've already got that matches
+    // what comes next, or start a new one.  If that leaves us stuck, backtrack and try something
+    // else.  Returns whether we managed to cover all the steps.
+    fn compress<'a>(
+        steps: &'a [String],
+        calls: &mut Vec<usize>,
+        subroutines: &mut Vec<&'a [String]>,
+    ) -> bool {
+        if steps.is_empty() {
+            return true;
+        }
+
+        // Ten calls is as many as will fit in the main routine.
+        if calls.len() == 10 {
+            return false;
+        }
+
+        for index in 0..subroutines.len() {
+            if steps.starts_with(subroutines[index]) {
+                calls.push(index);
+                if Self::compress(&steps[subroutines[index].len()..], calls, subroutines) {
+                    return true;
+                }
+                calls.pop();
+            }
+        }
+
+        if subroutines.len() < 3 {
+            for length in 1..=steps.len() {
+                if steps[..length].join(",").len() > 20 {
+                    break;
+                }
+                subroutines.push(&steps[..length]);
+                calls.push(subroutines.len() - 1);
+                if Self::compress(&steps[length..], calls, subroutines) {
+                    return true;
+                }
+                calls.pop();
+                subroutines.pop();
+            }
+        }
+
+        false
+    }
+
+    fn into_intcode_inputs(self) -> Vec<i64> {
+        let mut inputs = Vec::new();
+        self.main_routine
+            .chars()
+            .for_each(|c| inputs.push(c as i64));
+        inputs.push('\n' as i64);
+        for subroutine in self.subroutines {
+            subroutine.chars().for_each(|c| inputs.push(c as i64));
+            inputs.push('\n' as i64);
+        }
+        inputs.push('n' as i64);
+        inputs.push('\n' as i64);
+        inputs
+    }
+}
+
+struct Scaffold {
+    picture: Grid<char>,
+    program: String,
+    intersections: Vec<Point>,
+}
+
+impl Scaffold {
+    fn generate(picture: &[i64]) -> Self {
+        // The picture finishes with a blank line, which isn't part of the grid.
+        let text: String = picture.iter().map(|num| *num as u8 as char).collect();
+        let grid = Grid::parse(text.trim_end()).unwrap();
+        let scaffold = |pos: Point| grid.get(pos) == Some(&'#');
+        let mut pos = grid.find(|c| "^v<>".contains(*c)).unwrap();
+        let mut dir = Direction::from_char(grid[pos]).unwrap();
+        let mut program = String::new();
+        let mut intersections = Vec::new();
+
+        loop {
+            let mut distance = 0;
+
+            // Go as far as we can along the scaffold in a straight line, until we hit a corner.
+            while scaffold(pos + dir) {
+                // We're about to move forwards. Before we do, check to see if the space
+                // we're vacating was an intersection.
+                if distance > 0 && scaffold(pos + dir.turn_left()) {
+                    intersections.push(pos);
+                }
+
+                // We're OK to move forwards.
+                pos += dir;
+                distance += 1;
+            }
+
+            // Record how far we moved.
+            if distance > 0 {
+                program += &distance.to_string();
+                program.push(',');
+            }
+
+            // Figure out whether to turn left or right to continue following the scaffold, and
+            // record that too.
+            if scaffold(pos + dir.turn_left()) {
+                dir = dir.turn_left();
+                program.push('L');
+                program.push(',');
+            } else if scaffold(pos + dir.turn_right()) {
+                dir = dir.turn_right();
+                program.push('R');
+                program.push(',');
+            } else {
+                // Neither turning left nor right finds continued scaffold - we must have reached the end.
+                break;
+            }
+        }
+
+        Self {
+            picture: grid,
+            program,
+            intersections,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART_1_EXAMPLE: &str = "\
+..#..........
+..#..........
+#######...###
+#.#...#...#.#
+#############
+..#...#...#..
+..#####...^..
+";
+
+    const PART_2_EXAMPLE: &str = "\
+#######...#####
+#.....#...#...#
+#.....#...#...#
+......#...#...#
+......#...###.#
+......#.....#.#
+^########...#.#
+......#.#...#.#
+......#########
+........#...#..
+....#########..
+....#...#......
+....#...#......
+....#...#......
+....#####......
+";
+
+    fn picture(text: &str) -> Vec<i64> {
+        text.bytes().map(i64::from).collect()
+    }
+
+    #[test]
+    fn alignment_parameters() {
+        let scaffold = Scaffold::generate(&picture(PART_1_EXAMPLE));
+        let mut intersections = scaffold.intersections.clone();
+        intersections.sort_unstable();
+        intersections.dedup();
+        let expected = [(2, 2), (2, 4), (6, 4), (10, 4)];
+        assert_eq!(intersections, expected.iter().map(|&point| point.into()).collect::<Vec<_>>());
+        let alignment = scaffold.intersections.iter().map(|point| point.x * point.y).sum::<i64>();
+        assert_eq!(alignment / 2, 76);
+        assert_eq!(scaffold.picture.render(|&c| c).join("\n") + "\n", PART_1_EXAMPLE);
+    }
+
+    #[test]
+    fn movement() {
+        let scaffold = Scaffold::generate(&picture(PART_2_EXAMPLE));
+        assert_eq!(scaffold.program, "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2,");
+
+        // The puzzle splits it up as A,B,C,B,A,C.  We find a different split, but it still has to
+        // add up to the whole path, and fit in the robot's memory.
+        let logic = MovementLogic::parse(&scaffold.program);
+        assert!(logic.subroutines.len() <= 3);
+        assert!(logic.main_routine.len() <= 20);
+        assert!(logic.subroutines.iter().all(|subroutine| subroutine.len() <= 20));
+        let path = logic
+            .main_routine
+            .split(',')
+            .map(|name| logic.subroutines[(name.as_bytes()[0] - b'A') as usize].as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        assert_eq!(path + ",", scaffold.program);
+    }
+}
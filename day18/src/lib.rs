@@ -0,0 +1,309 @@
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+
+use aoc_core::{Answer, ParseError, Solution};
+use grid::{Grid, Point};
+
+// This solution uses a straightforward breadth-first search.  In part 2, the locations of the
+// other robots form part of the state, and whenever the active robot encounters a key, we add
+// the other robots to the queue.  This results in a tonne of branching, so fast it is NOT.
+// Keeping the keys as bits, and not caring which of the waiting robots is which, cut out
+// enough states to get it down from 3 minutes to about half a minute on my box, though :-)
+//
+// Sadly I'm not particularly interested in learning more of the theory behind these sorts of
+// problems, so I'm sticking with that rather than investing any more into this one!
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+
+    // The map for each part.
+    type Input = (Grid<char>, Grid<char>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let map = Grid::parse(input)?;
+        if map.width() == 0 {
+            return Err(ParseError::new("The map should be a rectangle"));
+        }
+        if let Some(pos) = map.find(|&c| !"#.@".contains(c) && !c.is_ascii_alphabetic()) {
+            return Err(ParseError::new(format!("Unexpected char in map: {}", map[pos])));
+        }
+        let split_map = split_vault(&map)?;
+        Ok((map, split_map))
+    }
+
+    fn part1((map, _): &Self::Input) -> Answer {
+        Maze::construct(map).solve().unwrap().into()
+    }
+
+    fn part2((_, map): &Self::Input) -> Answer {
+        Maze::construct(map).solve().unwrap().into()
+    }
+}
+
+// Part 2 updates the map: the single entrance is walled off, and there's a robot in each
+// corner of the 3x3 area around it instead.
+//
+//  ...      @#@
+//  .@.  =>  ###
+//  ...      @#@
+fn split_vault(map: &Grid<char>) -> Result<Grid<char>, ParseError> {
+    let entrances: Vec<Point> =
+        map.iter().filter(|(_, &c)| c == '@').map(|(pos, _)| pos).collect();
+    if entrances.len() != 1 {
+        return Err(ParseError::new("The map should have exactly one entrance"));
+    }
+
+    let entrance = entrances[0];
+    if entrance.all_neighbours().any(|pos| map.get(pos) != Some(&'.')) {
+        return Err(ParseError::new("The entrance should be surrounded by open space"));
+    }
+    let mut split_map = map.clone();
+    for (y, replacement) in ["@#@", "###", "@#@"].iter().enumerate() {
+        for (x, c) in replacement.chars().enumerate() {
+            split_map[entrance + Point::new(x as i64 - 1, y as i64 - 1)] = c;
+        }
+    }
+    Ok(split_map)
+}
+
+struct Maze {
+    grid: Grid<char>,
+    all_keys: Keys,
+    starting_locations: Vec<Point>,
+}
+
+impl Maze {
+    fn construct(map: &Grid<char>) -> Self {
+        let mut maze = Self {
+            grid: map.clone(),
+            all_keys: Keys::default(),
+            starting_locations: Vec::new(),
+        };
+
+        for (pos, c) in maze.grid.iter_mut() {
+            match *c {
+                '@' => {
+                    maze.starting_locations.push(pos);
+                    *c = '.';
+                }
+                key if key.is_lowercase() => maze.all_keys = maze.all_keys.with(key),
+                _ => {}
+            }
+        }
+
+        maze
+    }
+
+    fn solve(&self) -> Option<usize> {
+        // Any of the robots might be the one to go first.
+        let starts = (0..self.starting_locations.len()).map(|i| {
+            let mut other_robots = self.starting_locations.clone();
+            let location = other_robots.remove(i);
+            State {
+                location,
+                keys: Keys::default(),
+                other_robots,
+            }
+        });
+
+        search::Bfs::new(starts, |state| self.next_states(state))
+            .find(|(state, _)| state.keys == self.all_keys)
+            .map(|(_, distance)| distance)
+    }
+
+    fn next_states(&self, state: &State) -> Vec<State> {
+        let mut next_states = Vec::new();
+        for location in state.location.neighbours() {
+            // Don't bother with bounds check - the mazes all have walls at the edges
+            let tile = self.grid[location];
+            if tile == '#' {
+                // Wall
+                continue;
+            }
+            if tile.is_uppercase() && !state.keys.contains(tile.to_ascii_lowercase()) {
+                // Locked door
+                continue;
+            }
+
+            if tile.is_lowercase() && !state.keys.contains(tile) {
+                // Key.  The correct move from here might be for one of the other robots to
+                // go next, so we'll schedule each of them too.
+                let keys = state.keys.with(tile);
+                for i in 0..state.other_robots.len() {
+                    let mut other_robots = state.other_robots.clone();
+                    let other_location = std::mem::replace(&mut other_robots[i], location);
+                    other_robots.sort();
+                    next_states.push(State {
+                        location: other_location,
+                        keys,
+                        other_robots,
+                    });
+                }
+                next_states.push(State {
+                    location,
+                    keys,
+                    other_robots: state.other_robots.clone(),
+                });
+            } else {
+                next_states.push(State {
+                    location,
+                    keys: state.keys,
+                    other_robots: state.other_robots.clone(),
+                });
+            }
+        }
+        next_states
+    }
+}
+
+// The keys we've picked up, one bit for each letter.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+struct Keys(u32);
+
+impl Keys {
+    fn bit(key: char) -> u32 {
+        1 << (key as u32 - 'a' as u32)
+    }
+
+    fn contains(self, key: char) -> bool {
+        self.0 & Self::bit(key) != 0
+    }
+
+    fn with(self, key: char) -> Self {
+        Self(self.0 | Self::bit(key))
+    }
+}
+
+// State contains everything we need to know at any time about our progress through
+// exploring the maze: where the active robot is, which keys we've got, and where the other
+// robots are waiting (kept sorted, as it doesn't matter which robot is which).
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct State {
+    location: Point,
+    keys: Keys,
+    other_robots: Vec<Point>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shortest_path(map: &str) -> Option<usize> {
+        Maze::construct(&Grid::parse(map).unwrap()).solve()
+    }
+
+    #[test]
+    fn one_robot() {
+        assert_eq!(shortest_path("#########\n#b.A.@.a#\n#########"), Some(8));
+        assert_eq!(
+            shortest_path(
+                "########################\n\
+                 #f.D.E.e.C.b.A.@.a.B.c.#\n\
+                 ######################.#\n\
+                 #d.....................#\n\
+                 ########################"
+            ),
+            Some(86)
+        );
+        assert_eq!(
+            shortest_path(
+                "########################\n\
+                 #...............b.C.D.f#\n\
+                 #.######################\n\
+                 #.....@.a.B.c.d.A.e.F.g#\n\
+                 ########################"
+            ),
+            Some(132)
+        );
+        assert_eq!(
+            shortest_path(
+                "#################\n\
+                 #i.G..c...e..H.p#\n\
+                 ########.########\n\
+                 #j.A..b...f..D.o#\n\
+                 ########@########\n\
+                 #k.E..a...g..B.n#\n\
+                 ########.########\n\
+                 #l.F..d...h..C.m#\n\
+                 #################"
+            ),
+            Some(136)
+        );
+        assert_eq!(
+            shortest_path(
+                "########################\n\
+                 #@..............ac.GI.b#\n\
+                 ###d#e#f################\n\
+                 ###A#B#C################\n\
+                 ###g#h#i################\n\
+                 ########################"
+            ),
+            Some(81)
+        );
+    }
+
+    #[test]
+    fn four_robots() {
+        assert_eq!(
+            shortest_path(
+                "###############\n\
+                 #d.ABC.#.....a#\n\
+                 ######@#@######\n\
+                 ###############\n\
+                 ######@#@######\n\
+                 #b.....#.....c#\n\
+                 ###############"
+            ),
+            Some(24)
+        );
+        assert_eq!(
+            shortest_path(
+                "#############\n\
+                 #DcBa.#.GhKl#\n\
+                 #.###@#@#I###\n\
+                 #e#d#####j#k#\n\
+                 ###C#@#@###J#\n\
+                 #fEbA.#.FgHi#\n\
+                 #############"
+            ),
+            Some(32)
+        );
+        assert_eq!(
+            shortest_path(
+                "#############\n\
+                 #g#f.D#..h#l#\n\
+                 #F###e#E###.#\n\
+                 #dCba@#@BcIJ#\n\
+                 #############\n\
+                 #nK.L@#@G...#\n\
+                 #M###N#H###.#\n\
+                 #o#m..#i#jk.#\n\
+                 #############"
+            ),
+            Some(72)
+        );
+    }
+
+    #[test]
+    fn split() {
+        let (map, split_map) =
+            Day18::parse("#######\n#a.#Cd#\n##...##\n##.@.##\n##...##\n#cB#Ab#\n#######").unwrap();
+        assert_eq!(
+            split_map.render(|&c| c).join("\n"),
+            "#######\n#a.#Cd#\n##@#@##\n#######\n##@#@##\n#cB#Ab#\n#######"
+        );
+        assert_eq!(Day18::part2(&(map, split_map)), Answer::from(8));
+    }
+
+    #[test]
+    fn bad_maps() {
+        assert!(Day18::parse("#####\n#@.a#\n####").is_err());
+        assert!(Day18::parse("#####\n#@.a#\n#####\n#@..#\n#####").is_err());
+        assert!(Day18::parse("#####\n#@.a#\n#####").is_err());
+        assert!(Day18::parse("#####\n#@.!#\n#####").is_err());
+    }
+}
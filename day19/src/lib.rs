@@ -0,0 +1,194 @@
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+
+use std::sync::Arc;
+
+use aoc_core::{Answer, ParseError, Solution};
+use cjp_threadpool::ThreadPool;
+use grid::{Bounds, Point};
+
+struct Row {
+    first_col: Option<i64>,
+    last_col: Option<i64>,
+}
+
+impl Row {
+    fn count_affected_points(&self) -> i64 {
+        if let Some(first) = self.first_col {
+            self.last_col.unwrap() - first + 1
+        } else {
+            0
+        }
+    }
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+    type Input = Arc<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Arc::new(intcode::parse_program(input)?))
+    }
+
+    // Just probe every point in the 50x50 area - every probe is independent, so spread them
+    // across a thread pool.
+    fn part1(program: &Self::Input) -> Answer {
+        let pool = ThreadPool::<()>::new_with_default_size();
+        let program = Arc::clone(program);
+        let answer = pool
+            .reduce(
+                Bounds::new(Point::ORIGIN, Point::new(49, 49)).points(),
+                move |point| i64::from(point_affected(&program, point)),
+                |a, b| a + b,
+            )
+            .unwrap();
+        pool.join();
+        answer.into()
+    }
+
+    fn part2(program: &Self::Input) -> Answer {
+        let corner = find_square(&|point| point_affected(program, point), 100);
+        ((corner.x * 10000) + corner.y).into()
+    }
+}
+
+// Find the first square of the given size that fits in the beam, and return its top left corner.
+// Work down the beam a row at a time, using each row to narrow down where to look in the next.
+fn find_square(probe: &dyn Fn(Point) -> bool, size: i64) -> Point {
+    let mut rows: Vec<Row> = Vec::new();
+    let mut y = 0;
+    let mut first_possible_finish = None;
+    loop {
+        rows.push(scan_row(probe, y, rows.last()));
+        let row = &rows[y as usize];
+
+        // A 90-degree angle on the tractor beam is pretty unlikely, but for safety,
+        // let's assume that the first row we see with `size` affected points is a
+        // candidate for the first square.  (We'll actually start checking for
+        // a valid square at the _bottom_ row of the area.)
+        if first_possible_finish.is_none() && row.count_affected_points() >= size {
+            first_possible_finish = Some(y + size - 1);
+        }
+
+        if first_possible_finish.is_some_and(|finish| y >= finish) {
+            // If a square that horizontally starts at the beginning of this row
+            // also fits in `size - 1` rows up, that's the one.
+            let top = y - size + 1;
+            if row.first_col.unwrap() + size - 1 == rows[top as usize].last_col.unwrap() {
+                break Point::new(row.first_col.unwrap(), top);
+            }
+        }
+
+        y += 1;
+    }
+}
+
+// Deploy a drone to the specified co-ordinates to see whether the tractor beam affects it.
+fn point_affected(program: &[i64], point: Point) -> bool {
+    let outputs = intcode::run_parallel_computer(program, &[point.x, point.y]);
+    outputs[0] == 1
+}
+
+// Find the first and last affected columns in a row, by searching from the same columns as the
+// previous row's first and last.
+fn scan_row(probe: &dyn Fn(Point) -> bool, y: i64, previous_row: Option<&Row>) -> Row {
+    let (start_col, previous_end) = if let Some(previous_row) = previous_row {
+        if let Some(previous_start) = previous_row.first_col {
+            (previous_start, previous_row.last_col.unwrap())
+        } else {
+            (0, 0)
+        }
+    } else {
+        (0, 0)
+    };
+
+    let mut x = start_col;
+    let first_col = loop {
+        if probe(Point::new(x, y)) {
+            break Some(x);
+        }
+
+        x += 1;
+        if x == start_col + 8 {
+            // This row looks empty, let's give up on it
+            break None;
+        }
+    };
+
+    let last_col = if let Some(first_col) = first_col {
+        x = std::cmp::max(first_col, previous_end);
+        loop {
+            if !probe(Point::new(x, y)) {
+                break Some(x - 1);
+            }
+            x += 1;
+        }
+    } else {
+        None
+    };
+
+    Row {
+        first_col,
+        last_col,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The beam in the example for part 1, with nothing beyond the edges of the picture.
+    const BEAM: [&str; 10] = [
+        "#.........",
+        ".#........",
+        "..##......",
+        "...###....",
+        "....###...",
+        ".....####.",
+        "......####",
+        "......####",
+        ".......###",
+        "........##",
+    ];
+
+    fn example_beam(point: Point) -> bool {
+        BEAM.get(point.y as usize)
+            .and_then(|row| row.as_bytes().get(point.x as usize))
+            .is_some_and(|&point| point == b'#')
+    }
+
+    // A beam that goes on forever, covering the points between slopes of 0.6 and 0.8.
+    fn long_beam(point: Point) -> bool {
+        point.x * 10 >= point.y * 6 && point.x * 10 <= point.y * 8
+    }
+
+    #[test]
+    fn rows() {
+        let mut rows: Vec<Row> = Vec::new();
+        for y in 0..10 {
+            let row = scan_row(&example_beam, y, rows.last());
+            rows.push(row);
+        }
+        assert_eq!(rows.iter().map(Row::count_affected_points).sum::<i64>(), 27);
+        assert_eq!((rows[5].first_col, rows[5].last_col), (Some(5), Some(8)));
+    }
+
+    #[test]
+    fn squares() {
+        // Check against looking at every square in turn, top to bottom and left to right.
+        for size in [1, 2, 5, 10].iter() {
+            let corner = (0..)
+                .flat_map(|y| (0..=y).map(move |x| Point::new(x, y)))
+                .find(|corner| {
+                    long_beam(*corner + Point::new(0, size - 1))
+                        && long_beam(*corner + Point::new(size - 1, 0))
+                })
+                .unwrap();
+            assert_eq!(find_square(&long_beam, *size), corner);
+        }
+    }
+}
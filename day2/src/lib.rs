@@ -0,0 +1,68 @@
+#[macro_use] extern crate itertools;
+
+use aoc_core::{Answer, ParseError, Solution};
+use cjp_threadpool::ThreadPool;
+
+const TARGET: i64 = 19_690_720;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(intcode::parse_program(input)?)
+    }
+
+    fn part1(program: &Self::Input) -> Answer {
+        // Run the program with the tweaks specified in the question. Extract the value from memory address 0.
+        let mut memory = program.clone();
+        memory[1] = 12;
+        memory[2] = 2;
+        run(memory).into()
+    }
+
+    fn part2(memory: &Self::Input) -> Answer {
+        // Part 2: try every possible combination of values, looking for a combination that
+        // results in memory address 0 containing TARGET after execution completes.  Just for
+        // the lulz, use a thread pool to parallelise the search, which stops as soon as one is
+        // found.  The jobs borrow the program through a scope, and each makes its own copy to
+        // patch and run.  Some combinations make for invalid programs, which panic - the pool
+        // just treats those as not matching.
+        let pool = ThreadPool::<()>::new_with_default_size();
+        let (noun, verb) = pool
+            .scope(|s| {
+                s.find_any(iproduct!(0..memory.len(), 0..memory.len()), |&(noun, verb)| {
+                    let mut memory_copy = memory.to_vec();
+                    memory_copy[1] = noun as i64;
+                    memory_copy[2] = verb as i64;
+                    run(memory_copy) == TARGET
+                })
+            })
+            .expect("No combination of noun and verb produces the target");
+        pool.join();
+        ((noun * 100) + verb).into()
+    }
+}
+
+// Runs the program to completion, and returns what it leaves in memory address 0.
+fn run(memory: Vec<i64>) -> i64 {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut computer = intcode::ChannelIOComputer::from_vec(memory, rx, tx);
+    computer.run();
+    computer.fetch_address_zero()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_examples() {
+        assert_eq!(run(vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50]), 3500);
+        assert_eq!(run(vec![1, 0, 0, 0, 99]), 2);
+        assert_eq!(run(vec![2, 3, 0, 3, 99]), 2);
+        assert_eq!(run(vec![1, 1, 1, 4, 99, 5, 6, 0, 99]), 30);
+    }
+}
@@ -0,0 +1,244 @@
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+
+use std::collections::HashMap;
+
+use aoc_core::{Answer, ParseError, Solution};
+use grid::{Direction, Grid, Point};
+
+// More noddy breadth-first search, so again not very performant (fortunately only a
+// couple of seconds this time), and again you probably want to look at someone else's
+// solution for this day.
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+    type Input = Maze;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_maze(input)
+    }
+
+    fn part1(maze: &Self::Input) -> Answer {
+        solve_maze(maze, false).unwrap().into()
+    }
+
+    fn part2(maze: &Self::Input) -> Answer {
+        solve_maze(maze, true).unwrap().into()
+    }
+}
+
+// Search through the maze, where our state is our location and how many levels deep into
+// the recursion we are (which is always 0 if the maze isn't recursive).
+fn solve_maze(maze: &Maze, recursive: bool) -> Option<usize> {
+    let steps = |&(location, level): &(Point, usize)| {
+        location.neighbours().filter_map(move |next| {
+            let portal_exit = match maze.portals.get(&next) {
+                Some(&portal_exit) => portal_exit,
+                None if maze.grid[next] == '.' => return Some((next, level)),
+                None => return None,
+            };
+
+            // Teleport!
+            if !recursive {
+                Some((portal_exit, level))
+            } else if next.y == 1
+                || next.y == maze.bottom_row
+                || next.x == 1
+                || next.x == maze.rightmost_col
+            {
+                // Outer - up a level, unless we're already at the top, where it's a wall.
+                level.checked_sub(1).map(|level| (portal_exit, level))
+            } else {
+                Some((portal_exit, level + 1))
+            }
+        })
+    };
+
+    let exit = (maze.boundary.exit, 0);
+    search::bfs((maze.boundary.entrance, 0), steps)
+        .find(|(state, _)| *state == exit)
+        .map(|(_, distance)| distance)
+}
+
+// One end of a portal: the letter you step onto, and the open tile next to it.
+#[derive(Copy, Clone)]
+struct Portal {
+    entrance: Point,
+    exit: Point,
+}
+
+#[derive(Clone)]
+struct MazeBoundary {
+    entrance: Point,
+    exit: Point,
+}
+
+#[derive(Clone)]
+pub struct Maze {
+    grid: Grid<char>,
+    boundary: MazeBoundary,
+    portals: HashMap<Point, Point>,
+    bottom_row: i64,
+    rightmost_col: i64,
+}
+
+// Constructing the maze from the input.  Each portal is labelled with two letters, reading
+// left to right or top to bottom, with an open tile at one end of the label.  Stepping onto the
+// letter next to that tile takes you to the tile next to the other label with the same name.
+fn parse_maze(input_maze: &str) -> Result<Maze, ParseError> {
+    // Lines might have lost their trailing spaces, so pad them all out to the same width.
+    let mut grid = Grid::parse_padded(input_maze, ' ');
+
+    // The labels on the outside of the maze are just beyond its walls.
+    let (mut bottom_row, mut rightmost_col) = (0, 0);
+    for (pos, c) in grid.iter() {
+        if *c == '#' || *c == '.' {
+            bottom_row = bottom_row.max(pos.y + 1);
+            rightmost_col = rightmost_col.max(pos.x + 1);
+        }
+    }
+
+    // Find every label, and the letter and open tile at its open end.
+    let is_letter = |c: Option<&char>| c.is_some_and(char::is_ascii_uppercase);
+    let mut labels: HashMap<String, Vec<Portal>> = HashMap::new();
+    for first in grid.points() {
+        if !is_letter(grid.get(first)) {
+            continue;
+        }
+        for &dir in &[Direction::Down, Direction::Right] {
+            let second = first + dir;
+            if !is_letter(grid.get(second)) {
+                continue;
+            }
+            let name: String = [grid[first], grid[second]].iter().collect();
+            let (before, after) = (first + dir.reverse(), second + dir);
+            let portal = if grid.get(before) == Some(&'.') {
+                Portal { entrance: first, exit: before }
+            } else if grid.get(after) == Some(&'.') {
+                Portal { entrance: second, exit: after }
+            } else {
+                return Err(ParseError::new(format!("Portal {} doesn't lead anywhere", name)));
+            };
+            labels.entry(name).or_default().push(portal);
+        }
+    }
+
+    let mut boundary = MazeBoundary {
+        entrance: Point::ORIGIN,
+        exit: Point::ORIGIN,
+    };
+    let mut portals = HashMap::new();
+    for (name, ends) in &labels {
+        match (name.as_str(), ends.as_slice()) {
+            ("AA", [start]) => {
+                boundary.entrance = start.exit;
+                // Don't let the seeker exit the maze via the entrance
+                grid[start.entrance] = '#';
+            }
+            ("ZZ", [end]) => boundary.exit = end.exit,
+            (_, [a, b]) if name != "AA" && name != "ZZ" => {
+                portals.insert(a.entrance, b.exit);
+                portals.insert(b.entrance, a.exit);
+            }
+            _ => return Err(ParseError::new(format!("Portal {} should have a pair", name))),
+        }
+    }
+    if !labels.contains_key("AA") || !labels.contains_key("ZZ") {
+        return Err(ParseError::new("The maze needs an entrance, AA, and an exit, ZZ"));
+    }
+
+    Ok(Maze {
+        grid,
+        boundary,
+        portals,
+        bottom_row,
+        rightmost_col,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIMPLE_EXAMPLE: &str = "         A           
+         A           
+  #######.#########  
+  #######.........#  
+  #######.#######.#  
+  #######.#######.#  
+  #######.#######.#  
+  #####  B    ###.#  
+BC...##  C    ###.#  
+  ##.##       ###.#  
+  ##...DE  F  ###.#  
+  #####    G  ###.#  
+  #########.#####.#  
+DE..#######...###.#  
+  #.#########.###.#  
+FG..#########.....#  
+  ###########.#####  
+             Z       
+             Z       
+";
+
+    const LARGER_EXAMPLE: &str = "                   A               
+                   A               
+  #################.#############  
+  #.#...#...................#.#.#  
+  #.#.#.###.###.###.#########.#.#  
+  #.#.#.......#...#.....#.#.#...#  
+  #.#########.###.#####.#.#.###.#  
+  #.............#.#.....#.......#  
+  ###.###########.###.#####.#.###  
+  #.....#        A   C    #.#.#.#  
+  #######        S   P    #####.#  
+  #.#...#                 #......VT
+  #.#.#.#                 #.#####  
+  #...#.#               YN....#.#  
+  #.###.#                 #####.#  
+DI....#.#                 #.....#  
+  #####.#                 #.###.#  
+ZZ......#               QG....#..AS
+  ###.###                 #######  
+JO..#.#.#                 #.....#  
+  #.#.#.#                 ###.#.#  
+  #...#..DI             BU....#..LF
+  #####.#                 #.#####  
+YN......#               VT..#....QG
+  #.###.#                 #.###.#  
+  #.#...#                 #.....#  
+  ###.###    J L     J    #.#.###  
+  #.....#    O F     P    #.#...#  
+  #.###.#####.#.#####.#####.###.#  
+  #...#.#.#...#.....#.....#.#...#  
+  #.#####.###.###.#.#.#########.#  
+  #...#.#.....#...#.#.#.#.....#.#  
+  #.###.#####.###.###.#.#.#######  
+  #.#.........#...#.............#  
+  #########.###.###.#############  
+           B   J   C               
+           U   P   P               
+";
+
+    #[test]
+    fn simple() {
+        let maze = Day20::parse(SIMPLE_EXAMPLE).unwrap();
+        assert_eq!(solve_maze(&maze, false), Some(23));
+        assert_eq!(solve_maze(&maze, true), Some(26));
+    }
+
+    #[test]
+    fn larger() {
+        assert_eq!(solve_maze(&Day20::parse(LARGER_EXAMPLE).unwrap(), false), Some(58));
+    }
+
+    #[test]
+    fn bad_mazes() {
+        assert!(Day20::parse(&SIMPLE_EXAMPLE.replace('Z', " ")).is_err());
+        assert!(Day20::parse(&SIMPLE_EXAMPLE.replacen("FG", "XY", 1)).is_err());
+    }
+}
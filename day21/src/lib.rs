@@ -0,0 +1,133 @@
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+
+use std::sync::mpsc;
+
+use aoc_core::{Answer, ParseError, Solution};
+
+// Part 1's logic is straightforward: jump if we have to (there's a hole at 1, 2 or 3) and if
+// we can (there isn't one at 4).
+const PART_1_SCRIPT: &str = 
+"NOT A J
+NOT B T
+OR T J
+NOT C T
+OR T J
+AND D J
+WALK
+";
+
+// Part 2's logic bolts onto that: don't jump unless either we can jump again immediately on
+// landing (no hole at 8) or we don't need to (no hole at 5).
+const PART_2_SCRIPT: &str = 
+"NOT A J
+NOT B T
+OR T J
+NOT C T
+OR T J
+AND D J
+NOT J T
+OR E T
+OR H T
+AND T J    
+RUN
+";
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(intcode::parse_program(input)?)
+    }
+
+    fn part1(program: &Self::Input) -> Answer {
+        run_program(program, PART_1_SCRIPT).into()
+    }
+
+    fn part2(program: &Self::Input) -> Answer {
+        run_program(program, PART_2_SCRIPT).into()
+    }
+}
+
+fn run_program(intcode: &[i64], springscript: &str) -> i64 {
+    let (in_send, in_recv) = mpsc::channel();
+    let (out_send, out_recv) = mpsc::channel();
+    let mut computer = intcode::ChannelIOComputer::new(intcode, in_recv, out_send);
+    std::thread::spawn(move || {
+        computer.run();
+    });
+
+    for c in springscript.chars() {
+        in_send.send(c as i64).unwrap();
+    }
+
+    while let Ok(input) = out_recv.recv() {
+        if input > 255 {
+            return input;
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Runs a springscript over a stretch of hull, starting at the beginning, and returns whether
+    // the droid makes it to the end.  Beyond the end, it's ground all the way.
+    fn survives(springscript: &str, hull: &str) -> bool {
+        let hull = hull.as_bytes();
+        let ground = |pos: usize| hull.get(pos).is_none_or(|&tile| tile == b'#');
+        let mut pos = 0;
+        while pos < hull.len() {
+            let (mut t, mut j) = (false, false);
+            for instruction in springscript.lines() {
+                let words: Vec<&str> = instruction.split_whitespace().collect();
+                if words.len() != 3 {
+                    continue; // WALK or RUN
+                }
+                let read = |register: &str, t: bool, j: bool| match register {
+                    "T" => t,
+                    "J" => j,
+                    sensor => ground(pos + usize::from(sensor.as_bytes()[0] - b'A') + 1),
+                };
+                let (x, y) = (read(words[1], t, j), read(words[2], t, j));
+                let result = match words[0] {
+                    "AND" => x && y,
+                    "OR" => x || y,
+                    _ => !x,
+                };
+                if words[2] == "T" { t = result; } else { j = result; }
+            }
+            pos += if j { 4 } else { 1 };
+            if !ground(pos) {
+                return false;
+            }
+        }
+        true
+    }
+
+    const SIMPLE_HULLS: [&str; 4] =
+        ["#####.####", "#####..#.####", "#####...####", "#####.#..####"];
+    const TRICKY_HULLS: [&str; 4] =
+        ["#####.#.##..#.####", "#####.##.##.#.###", "#####.#.#...#.###", "#####..####.#..###"];
+
+    #[test]
+    fn walking() {
+        assert!(SIMPLE_HULLS.iter().all(|hull| survives(PART_1_SCRIPT, hull)));
+
+        // Walking doesn't look far enough ahead for some hulls.
+        assert!(!survives(PART_1_SCRIPT, TRICKY_HULLS[0]));
+    }
+
+    #[test]
+    fn running() {
+        assert!(SIMPLE_HULLS.iter().all(|hull| survives(PART_2_SCRIPT, hull)));
+        assert!(TRICKY_HULLS.iter().all(|hull| survives(PART_2_SCRIPT, hull)));
+    }
+}
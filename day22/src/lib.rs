@@ -3,10 +3,12 @@
 #![allow(clippy::cast_possible_truncation)]
 #![allow(clippy::cast_possible_wrap)]
 
-use std::io::{self, Read};
+use std::io;
 
 use std::collections::VecDeque;
 
+use aoc_core::{Answer, ParseError, Solution};
+
 const PART_1_DECK_SIZE: i128 = 10_007;
 const PART_2_DECK_SIZE: i128 = 119_315_717_514_047;
 const NUM_SHUFFLES: i128 = 101_741_582_076_661;
@@ -17,26 +19,31 @@ const NUM_SHUFFLES: i128 = 101_741_582_076_661;
 // the meat of this program is merely an implementation of the algorithm explained in that post.
 // So don't go looking for comments, just read that post :-)
 
-fn main() {
-    let start_time = std::time::Instant::now();
-    let mut part_1_deck: Vec<u32> = (0..PART_1_DECK_SIZE as u32).collect();
-    let instructions = load_instructions().unwrap_or_else(|err| {
-        println!("Could not load input file!\n{:?}", err);
-        std::process::exit(1);
-    });
-
-    let mut f = (1, 0);
-    for technique in &instructions {
-        f = compose(f, translate(technique));
-        part_1_deck = apply_technique(part_1_deck, technique);
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u32 = 22;
+    type Input = Vec<Techniques>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_instructions(input)?)
     }
 
-    let part_1_location = find_card(&part_1_deck, 2019).unwrap();
+    fn part1(instructions: &Self::Input) -> Answer {
+        let deck = shuffle(PART_1_DECK_SIZE as u32, instructions);
+        find_card(&deck, 2019).unwrap().into()
+    }
+
+    fn part2(instructions: &Self::Input) -> Answer {
+        let mut f = (1, 0);
+        for technique in instructions {
+            f = compose(f, translate(technique));
+        }
 
-    let big_f = pow_compose(f, NUM_SHUFFLES);
-    let x = 2020;
-    let card = mod_divide(x - big_f.1, big_f.0, PART_2_DECK_SIZE);
-    println!("Part 1: {}\nPart 2: {}\nTime: {}ms", part_1_location, card, start_time.elapsed().as_millis());
+        let big_f = pow_compose(f, NUM_SHUFFLES);
+        let x = 2020;
+        mod_divide(x - big_f.1, big_f.0, PART_2_DECK_SIZE).into()
+    }
 }
 
 fn translate(technique: &Techniques) -> (i128, i128) {
@@ -75,12 +82,20 @@ fn mod_divide(numerator: i128, denominator: i128, m: i128) -> i128 {
     (numerator * pow_mod(denominator, m-2, m)) % m
 }
 
-enum Techniques {
+pub enum Techniques {
     DealIntoNewStack,
     Cut(i128),
     DealWithIncrement(i128),
 }
 
+fn shuffle(deck_size: u32, instructions: &[Techniques]) -> Vec<u32> {
+    let mut deck: Vec<u32> = (0..deck_size).collect();
+    for technique in instructions {
+        deck = apply_technique(deck, technique);
+    }
+    deck
+}
+
 fn apply_technique(mut deck: Vec<u32>, technique: &Techniques) -> Vec<u32> {
     match technique {
         Techniques::DealIntoNewStack => {
@@ -91,7 +106,7 @@ fn apply_technique(mut deck: Vec<u32>, technique: &Techniques) -> Vec<u32> {
             cut_deck(&deck, *count as usize)
         },
         Techniques::Cut(count) => {
-            cut_deck(&deck, deck.len() - count.abs() as usize)
+            cut_deck(&deck, deck.len() - count.unsigned_abs() as usize)
         }
         Techniques::DealWithIncrement(increment) => {
             let mut old_deck = VecDeque::from(deck.clone());
@@ -118,20 +133,16 @@ const DEAL_INTO_NEW_STACK: &str = "deal into new stack";
 const CUT: &str = "cut ";
 const DEAL_WITH_INCREMENT: &str = "deal with increment ";
 
-fn load_instructions() -> Result<Vec<Techniques>, io::Error> {
-    let mut input = std::fs::File::open("day22/input.txt")?;
-    let mut instructions = String::new();
-    input.read_to_string(&mut instructions)?;
-
+fn parse_instructions(instructions: &str) -> Result<Vec<Techniques>, io::Error> {
     let mut techniques = Vec::new();
     for line in instructions.lines() {
         if line.starts_with(DEAL_INTO_NEW_STACK) {
             techniques.push(Techniques::DealIntoNewStack);
-        } else if line.starts_with(CUT) {
-            let count = line[CUT.len()..].parse::<i128>().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        } else if let Some(count) = line.strip_prefix(CUT) {
+            let count = count.parse::<i128>().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
             techniques.push(Techniques::Cut(count));
-        } else if line.starts_with(DEAL_WITH_INCREMENT) {
-            let increment = line[DEAL_WITH_INCREMENT.len()..].parse::<i128>().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        } else if let Some(increment) = line.strip_prefix(DEAL_WITH_INCREMENT) {
+            let increment = increment.parse::<i128>().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
             techniques.push(Techniques::DealWithIncrement(increment));
         }
     }
@@ -144,4 +155,57 @@ fn find_card(deck: &[u32], card: u32) -> Option<usize> {
         if *deck_card == card { return Some(index); }
     }
     None
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shuffle_ten(instructions: &str) -> Vec<u32> {
+        shuffle(10, &parse_instructions(instructions).unwrap())
+    }
+
+    #[test]
+    fn techniques() {
+        assert_eq!(shuffle_ten("deal into new stack"), vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+        assert_eq!(shuffle_ten("cut 3"), vec![3, 4, 5, 6, 7, 8, 9, 0, 1, 2]);
+        assert_eq!(shuffle_ten("cut -4"), vec![6, 7, 8, 9, 0, 1, 2, 3, 4, 5]);
+        assert_eq!(shuffle_ten("deal with increment 3"), vec![0, 7, 4, 1, 8, 5, 2, 9, 6, 3]);
+    }
+
+    #[test]
+    fn shuffles() {
+        assert_eq!(
+            shuffle_ten("deal with increment 7\ndeal into new stack\ndeal into new stack"),
+            vec![0, 3, 6, 9, 2, 5, 8, 1, 4, 7]
+        );
+        assert_eq!(
+            shuffle_ten("cut 6\ndeal with increment 7\ndeal into new stack"),
+            vec![3, 0, 7, 4, 1, 8, 5, 2, 9, 6]
+        );
+        assert_eq!(
+            shuffle_ten("deal with increment 7\ndeal with increment 9\ncut -2"),
+            vec![6, 3, 0, 7, 4, 1, 8, 5, 2, 9]
+        );
+        assert_eq!(
+            shuffle_ten(
+                "deal into new stack\ncut -2\ndeal with increment 7\ncut 8\ncut -4\n\
+                 deal with increment 7\ncut 3\ndeal with increment 9\ndeal with increment 3\ncut -1"
+            ),
+            vec![9, 2, 5, 8, 1, 4, 7, 0, 3, 6]
+        );
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        let f = compose(
+            translate(&Techniques::DealWithIncrement(7)),
+            translate(&Techniques::Cut(-2)),
+        );
+        assert_eq!(pow_compose(f, 3), compose(compose(f, f), f));
+        assert_eq!(pow_mod(3, 4, 7), 4);
+
+        // Dividing undoes multiplying.
+        let product = (123_456_789 * 987_654_321) % PART_2_DECK_SIZE;
+        assert_eq!(mod_divide(product, 987_654_321, PART_2_DECK_SIZE), 123_456_789);
+    }
+}
@@ -0,0 +1,479 @@
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+
+use std::cell::OnceCell;
+use std::collections::{HashMap, VecDeque};
+use std::iter::FromIterator;
+
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures::future::{self, Either};
+use futures::stream::{self, StreamExt};
+
+use aoc_core::{Answer, ParseError, Solution};
+use cjp_threadpool::ThreadPool;
+use intcode::{AsyncComputeNotification, RunOutcome, Scheduler, StreamingIOComputer};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+    type Input = Nics;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Nics { program: intcode::parse_program(input)?, answers: OnceCell::new() })
+    }
+
+    fn part1(nics: &Self::Input) -> Answer {
+        nics.answers().0.into()
+    }
+
+    fn part2(nics: &Self::Input) -> Answer {
+        nics.answers().1.into()
+    }
+
+    fn extra(_nics: &Self::Input) -> Vec<(&'static str, Answer)> {
+        vec![("network", network().into())]
+    }
+}
+
+// The NIC program, and the answers we get by running a network of them.  One run of the
+// network answers both parts, so whichever part runs first does it.
+pub struct Nics {
+    program: Vec<i64>,
+    answers: OnceCell<(i64, i64)>,
+}
+
+impl Nics {
+    // Today, you get not one, but three implementations.  The default implementation uses a
+    // SynchronousComputer for efficiency, and is faster.  But I wanted to try out async
+    // code as well, so there's also a network of StreamingIOComputers with communication
+    // via streams - it's slower, but shows how this might work if that's what you had to
+    // do.  Set DAY23_NETWORK to "async" to use it, or "pool" to run the same thing on a
+    // ThreadPool rather than the futures crate's executor.  Or set it to "scheduled" to have
+    // an intcode::Scheduler do the work of wiring everything together.
+    fn answers(&self) -> (i64, i64) {
+        *self.answers.get_or_init(|| {
+            let program = &self.program;
+            match network() {
+                "async" => futures::executor::block_on(AsyncNetwork::new(50, program).run()),
+                "pool" => {
+                    let pool = ThreadPool::<()>::new_with_default_size();
+                    let answers = pool.spawn(AsyncNetwork::new(50, program).run()).wait();
+                    answers.unwrap_or_else(|err| panic!("{}", err))
+                }
+                "scheduled" => ScheduledNetwork::new(50, program).run(),
+                _ => Network::new(50, program).run(),
+            }
+        })
+    }
+}
+
+// Which network implementation DAY23_NETWORK asks for.
+fn network() -> &'static str {
+    match std::env::var("DAY23_NETWORK").as_deref() {
+        Ok("async") => "async",
+        Ok("pool") => "pool",
+        Ok("scheduled") => "scheduled",
+        _ => "synchronous",
+    }
+}
+
+// How many times in a row a computer has to ask for input and be told there isn't any,
+// without sending anything in between, before we consider it to be idle.
+const IDLE_POLLS: usize = 2;
+
+type NetworkComputer =
+    StreamingIOComputer<UnboundedReceiver<i64>, UnboundedSender<AsyncComputeNotification>>;
+
+struct Message {
+    destination: i64,
+    x: i64,
+    y: i64,
+}
+
+// Simulate the NAT from the question - a device that is sent messages, and then echoes
+// the last one sent to it back to computer 0 when the rest of the network is idle.
+#[derive(Default)]
+struct Nat {
+    stored_x: Option<i64>,
+    stored_y: i64,
+}
+
+impl Nat {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&mut self, message: &Message) {
+        assert!(message.destination == 255);
+        self.stored_x = Some(message.x);
+        self.stored_y = message.y;
+    }
+
+    fn pop(&self) -> Option<Message> {
+        self.stored_x.map(|x| Message {
+            destination: 0,
+            x,
+            y: self.stored_y,
+        })
+    }
+}
+
+struct ComputerLink {
+    computer: intcode::SynchronousComputer,
+    outbound_messages: Vec<i64>,
+}
+
+impl ComputerLink {
+    // Create an Intcode computer and initialise its input queue with its address.
+    fn new(program: &[i64], address: i64) -> Self {
+        Self {
+            computer: intcode::SynchronousComputer::new(program),
+            outbound_messages: vec![address],
+        }
+    }
+}
+
+/// A network of Intcode computers, each running a NIC program, plus a NAT.  All the
+/// computers are run in turn on the current thread using `SynchronousComputer`s.
+pub struct Network {
+    computers: HashMap<i64, ComputerLink>,
+    nat: Nat,
+}
+
+impl Network {
+    #[must_use]
+    pub fn new(num_computers: i64, computer_program: &[i64]) -> Self {
+        let mut computers = HashMap::with_capacity(num_computers as usize);
+        for i in 0..num_computers {
+            computers.insert(i, ComputerLink::new(computer_program, i));
+        }
+
+        Self {
+            computers,
+            nat: Nat::new(),
+        }
+    }
+
+    /// Runs the network until the NAT sends the same Y value to computer 0 twice in a row.
+    /// Returns the first Y value sent to the NAT, and that repeated Y value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a computer halts, or sends a message to a computer that doesn't exist.
+    #[must_use]
+    pub fn run(mut self) -> (i64, i64) {
+        let mut first_y = None;
+        let mut last_sent_y = None;
+
+        loop {
+            let mut outputs = Vec::new();
+
+            // Transmit to and receive from every computer.  We'll transmit everything that's
+            // queued, and if that's "nothing", transmit -1.
+            for link in self.computers.values_mut() {
+                if link.outbound_messages.is_empty() {
+                    link.outbound_messages.push(-1);
+                }
+                let inputs: Vec<i64> = link.outbound_messages.drain(..).collect();
+                let mut compute_output = link.computer.run(&inputs);
+                assert!(compute_output.result == intcode::SynchronousComputeResult::InputRequired);
+
+                // Gather all of the outputs together.
+                outputs.append(&mut compute_output.outputs);
+            }
+
+            if outputs.is_empty() {
+                // No computer had any output for us - poke the NAT.
+                let message = self.nat.pop();
+                if let Some(message) = message {
+                    if let Some(last_y) = last_sent_y {
+                        if last_y == message.y {
+                            // Found the answer to part 2
+                            break;
+                        }
+                    }
+                    last_sent_y = Some(message.y);
+
+                    outputs.push(message.destination);
+                    outputs.push(message.x);
+                    outputs.push(message.y);
+                }
+            }
+
+            let mut queue = VecDeque::from_iter(outputs);
+            while !queue.is_empty() {
+                let destination = queue.pop_front().unwrap();
+                let x = queue.pop_front().unwrap();
+                let y = queue.pop_front().unwrap();
+                if destination == 255 {
+                    // Send to the NAT.
+                    if first_y.is_none() {
+                        // Got the answer to part 1.
+                        first_y = Some(y);
+                    }
+                    self.nat.push(&Message {
+                        destination: 255,
+                        x,
+                        y,
+                    });
+                } else {
+                    // Send to another computer.
+                    let link = self.computers.get_mut(&destination).unwrap();
+                    link.outbound_messages.push(x);
+                    link.outbound_messages.push(y);
+                }
+            }
+        }
+
+        (first_y.unwrap(), last_sent_y.unwrap())
+    }
+}
+
+// The network's view of one of the `StreamingIOComputer`s: how to talk to it, what's
+// waiting to be delivered to it, and how much it's been doing lately.
+struct AsyncComputerLink {
+    to_computer: UnboundedSender<i64>,
+    queued_inputs: VecDeque<i64>,
+    partial_message: Vec<i64>,
+    empty_polls: usize,
+}
+
+impl AsyncComputerLink {
+    fn send(&self, input: i64) {
+        self.to_computer
+            .unbounded_send(input)
+            .expect("Network computer stopped listening!");
+    }
+}
+
+/// The same network as [`Network`], but with each computer being a `StreamingIOComputer`,
+/// all talking to a driver that routes messages between them.
+///
+/// Nothing here depends on a particular async runtime - [`run()`] returns a plain future,
+/// which can be run on Tokio, async-std, or `futures::executor::block_on`.  Everything
+/// happens within that one future, so there's no need for the executor to be able to
+/// spawn tasks either.
+///
+/// [`Network`]: ./struct.Network.html
+/// [`run()`]: #method.run
+pub struct AsyncNetwork {
+    computers: Vec<NetworkComputer>,
+    links: Vec<AsyncComputerLink>,
+    notifications: Vec<UnboundedReceiver<AsyncComputeNotification>>,
+    nat: Nat,
+}
+
+impl AsyncNetwork {
+    #[must_use]
+    pub fn new(num_computers: i64, computer_program: &[i64]) -> Self {
+        let mut computers = Vec::with_capacity(num_computers as usize);
+        let mut links = Vec::with_capacity(num_computers as usize);
+        let mut notifications = Vec::with_capacity(num_computers as usize);
+
+        for address in 0..num_computers {
+            let (to_computer, from_network) = mpsc::unbounded();
+            let (to_network, from_computer) = mpsc::unbounded();
+            computers.push(StreamingIOComputer::new(
+                computer_program,
+                from_network,
+                to_network,
+            ));
+            links.push(AsyncComputerLink {
+                to_computer,
+                queued_inputs: VecDeque::from(vec![address]),
+                partial_message: Vec::with_capacity(3),
+                empty_polls: 0,
+            });
+            notifications.push(from_computer);
+        }
+
+        Self {
+            computers,
+            links,
+            notifications,
+            nat: Nat::new(),
+        }
+    }
+
+    /// Runs the network until the NAT sends the same Y value to computer 0 twice in a row.
+    /// Returns the first Y value sent to the NAT, and that repeated Y value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a computer halts, or sends a message to a computer that doesn't exist.
+    pub async fn run(self) -> (i64, i64) {
+        let Self {
+            mut computers,
+            mut links,
+            notifications,
+            mut nat,
+        } = self;
+
+        // Merge all of the computers' notifications into a single stream, labelled with
+        // which computer sent them.
+        let mut notifications = stream::select_all(
+            notifications
+                .into_iter()
+                .enumerate()
+                .map(|(address, stream)| stream.map(move |notification| (address, notification))),
+        );
+
+        let driver = async move {
+            let mut first_y = None;
+            let mut last_sent_y = None;
+
+            while let Some((address, notification)) = notifications.next().await {
+                match notification {
+                    AsyncComputeNotification::ProgramEnded => {
+                        panic!("Network computer terminated unexpectedly!")
+                    }
+                    AsyncComputeNotification::Output(output) => {
+                        let link = &mut links[address];
+                        link.empty_polls = 0;
+                        link.partial_message.push(output);
+                        if link.partial_message.len() < 3 {
+                            continue;
+                        }
+
+                        let message = Message {
+                            destination: link.partial_message[0],
+                            x: link.partial_message[1],
+                            y: link.partial_message[2],
+                        };
+                        link.partial_message.clear();
+
+                        if message.destination == 255 {
+                            // Send to the NAT.
+                            if first_y.is_none() {
+                                // Got the answer to part 1.
+                                first_y = Some(message.y);
+                            }
+                            nat.push(&message);
+                        } else {
+                            let destination = &mut links[message.destination as usize];
+                            destination.queued_inputs.push_back(message.x);
+                            destination.queued_inputs.push_back(message.y);
+                        }
+                    }
+                    AsyncComputeNotification::InputRequired => {
+                        // Hand over the next thing queued for this computer, or -1 if there's
+                        // nothing.
+                        let link = &mut links[address];
+                        if let Some(input) = link.queued_inputs.pop_front() {
+                            link.empty_polls = 0;
+                            link.send(input);
+                        } else {
+                            link.empty_polls += 1;
+                            link.send(-1);
+                        }
+
+                        let idle = links.iter().all(|link| {
+                            link.queued_inputs.is_empty() && link.empty_polls >= IDLE_POLLS
+                        });
+                        if idle {
+                            // Idle - poke the NAT.
+                            if let Some(message) = nat.pop() {
+                                if last_sent_y == Some(message.y) {
+                                    // Found the answer to part 2
+                                    break;
+                                }
+                                last_sent_y = Some(message.y);
+
+                                let destination = &mut links[message.destination as usize];
+                                destination.queued_inputs.push_back(message.x);
+                                destination.queued_inputs.push_back(message.y);
+                            }
+                        }
+                    }
+                }
+            }
+
+            (first_y.unwrap(), last_sent_y.unwrap())
+        };
+
+        // The computers run forever, so we're done as soon as the driver is.
+        let running = future::join_all(computers.iter_mut().map(|computer| computer.run()));
+        let answers = match future::select(Box::pin(driver), running).await {
+            Either::Left((answers, _)) => answers,
+            Either::Right(_) => unreachable!("Network computers all terminated"),
+        };
+        answers
+    }
+}
+
+/// The same network again, this time as machines in an `intcode::Scheduler`.  Each NIC
+/// reads from a pipe named after itself, and they all write to a router that splits their
+/// output into packets and delivers them, with anything for address 255 going to a pipe
+/// called "nat".
+pub struct ScheduledNetwork {
+    scheduler: Scheduler,
+}
+
+impl ScheduledNetwork {
+    #[must_use]
+    pub fn new(num_computers: i64, computer_program: &[i64]) -> Self {
+        let mut scheduler = Scheduler::new(1000);
+        scheduler.add_router("bus", 3, |packet| match packet[0] {
+            255 => ("nat".to_string(), packet[1..].to_vec()),
+            address => (format!("nic{}", address), packet[1..].to_vec()),
+        });
+        for address in 0..num_computers {
+            let name = format!("nic{}", address);
+            scheduler.send(&name, address);
+            scheduler.add_machine(&name, computer_program, &name, "bus");
+            scheduler.set_idle_input(&name, -1);
+        }
+
+        Self { scheduler }
+    }
+
+    /// Runs the network until the NAT sends the same Y value to computer 0 twice in a row.
+    /// Returns the first Y value sent to the NAT, and that repeated Y value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a computer halts.
+    #[must_use]
+    pub fn run(mut self) -> (i64, i64) {
+        let mut nat = Nat::new();
+        let mut first_y = None;
+        let mut last_sent_y = None;
+
+        loop {
+            // The scheduler returns whenever the network goes idle.
+            let outcome = self
+                .scheduler
+                .run()
+                .unwrap_or_else(|deadlock| panic!("{}", deadlock));
+            assert!(outcome == RunOutcome::Idle, "Network computers terminated!");
+
+            for packet in self.scheduler.drain("nat").chunks(2) {
+                if first_y.is_none() {
+                    // Got the answer to part 1.
+                    first_y = Some(packet[1]);
+                }
+                nat.push(&Message {
+                    destination: 255,
+                    x: packet[0],
+                    y: packet[1],
+                });
+            }
+
+            // Idle - poke the NAT.
+            if let Some(message) = nat.pop() {
+                if last_sent_y == Some(message.y) {
+                    // Found the answer to part 2
+                    break;
+                }
+                last_sent_y = Some(message.y);
+                self.scheduler.send("nic0", message.x);
+                self.scheduler.send("nic0", message.y);
+            }
+        }
+
+        (first_y.unwrap(), last_sent_y.unwrap())
+    }
+}
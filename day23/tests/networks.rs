@@ -0,0 +1,61 @@
+use cjp_threadpool::ThreadPool;
+use day23::{AsyncNetwork, Network, ScheduledNetwork};
+
+// A stand-in for the real NIC program, small enough to follow by hand.  Each computer
+// waits for a packet (x, y) and passes on (x, x + y) to the next computer along, with the
+// last one sending to the NAT.  Computer 0 kicks things off by sending (1, 10), and zeroes
+// x in anything it passes on - so the NAT first sees y = 10 + n - 1, then 10 + n forever.
+#[rustfmt::skip]
+fn nic_program(num_computers: i64) -> Vec<i64> {
+    vec![
+        3, 100, // address = input
+        1008, 100, 0, 104, // is_zero = address == 0
+        1006, 104, 15, // if !is_zero goto loop
+        104, 1, 104, 1, 104, 10, // send (1, 1, 10)
+        3, 101, // loop: x = input
+        1008, 101, -1, 104, // nothing = x == -1
+        1005, 104, 15, // if nothing goto loop
+        3, 102, // y = input
+        1, 101, 102, 102, // y = x + y
+        1001, 100, 1, 103, // destination = address + 1
+        1008, 103, num_computers, 104, // is_last = destination == n
+        1006, 104, 45, // if !is_last goto send
+        1101, 255, 0, 103, // destination = 255
+        1005, 100, 52, // if address != 0 goto send
+        1101, 0, 0, 101, // x = 0
+        4, 103, 4, 101, 4, 102, // send: output (destination, x, y)
+        1105, 1, 15, // goto loop
+    ]
+}
+
+const EXPECTED: (i64, i64) = (14, 15);
+
+#[test]
+fn synchronous_network() {
+    assert_eq!(Network::new(5, &nic_program(5)).run(), EXPECTED);
+}
+
+#[test]
+fn scheduled_network() {
+    assert_eq!(ScheduledNetwork::new(5, &nic_program(5)).run(), EXPECTED);
+}
+
+#[test]
+fn async_network_on_futures_executor() {
+    let network = AsyncNetwork::new(5, &nic_program(5));
+    assert_eq!(futures::executor::block_on(network.run()), EXPECTED);
+}
+
+#[tokio::test]
+async fn async_network_on_tokio() {
+    let network = AsyncNetwork::new(5, &nic_program(5));
+    assert_eq!(network.run().await, EXPECTED);
+}
+
+#[test]
+fn async_network_on_thread_pool() {
+    let pool = ThreadPool::<()>::new(2).unwrap();
+    let network = AsyncNetwork::new(5, &nic_program(5));
+    assert_eq!(pool.spawn(network.run()).wait(), Ok(EXPECTED));
+    pool.join();
+}
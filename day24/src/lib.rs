@@ -1,40 +1,77 @@
+use std::cell::OnceCell;
 use std::collections::HashSet;
 
-const INITIAL_STATE: &str = 
-"..#.#
-#####
-.#...
-...#.
-##...";
+use aoc_core::{Answer, ParseError, Solution};
+use grid::Grid;
 
 // This is a good'un IMO.  Runs in just 4ms and I'm pretty happy with the code.
 
-fn main() {
-    let start_time = std::time::Instant::now();
+pub struct Day24;
 
-    // Part 1: simulate a non-recursive Eris, and run it until we see the same state twice.
-    // (This program tracks the state in a bitwise fashion, so the biodiversity rating IS
-    // the state, we don't need to calculate it separately.)
-    let mut single_eris = Eris::new(INITIAL_STATE, false);
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+    type Input = Scan;
+
+    // The input is a 5x5 grid of bugs (#) and empty tiles (.).
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let eris = Grid::parse(input)?;
+        if eris.width() != 5 || eris.height() != 5 {
+            return Err(ParseError::new("Eris should be a 5x5 grid"));
+        }
+        if let Some(pos) = eris.find(|&c| c != '#' && c != '.') {
+            return Err(ParseError::new(format!("Unexpected tile: {}", eris[pos])));
+        }
+        Ok(Scan {
+            initial_state: eris.map(|&c| c == '#'),
+            first_repeat: OnceCell::new(),
+        })
+    }
+
+    // Simulate a non-recursive Eris, and run it until we see the same state twice.  (This
+    // program tracks the state in a bitwise fashion, so the biodiversity rating IS the state,
+    // we don't need to calculate it separately.)
+    fn part1(scan: &Self::Input) -> Answer {
+        scan.first_repeat().state.into()
+    }
+
+    // Simulate a recursive Eris for 200 ticks.
+    fn part2(scan: &Self::Input) -> Answer {
+        count_recursive_bugs(&scan.initial_state, 200).into()
+    }
+
+    fn extra(scan: &Self::Input) -> Vec<(&'static str, Answer)> {
+        vec![("layout", Answer::Image(scan.first_repeat().draw()))]
+    }
+}
+
+pub struct Scan {
+    // Where the bugs are.
+    initial_state: Grid<bool>,
+    // The first layout to appear twice, which part 1 and the picture share.
+    first_repeat: OnceCell<Eris>,
+}
+
+impl Scan {
+    fn first_repeat(&self) -> &Eris {
+        self.first_repeat.get_or_init(|| first_repeat(&self.initial_state))
+    }
+}
+
+fn first_repeat(initial_state: &Grid<bool>) -> Eris {
+    let mut single_eris = Eris::new(initial_state, false);
     let mut ratings = HashSet::new();
     while ratings.insert(single_eris.state) {
         single_eris = single_eris.tick();
     }
-    let part_1_answer = single_eris.state;
+    single_eris
+}
 
-    // Part 2: simulate a recursive Eris for 200 ticks.
-    let mut multi_eris = Eris::new(INITIAL_STATE, true);
-    for _ in 0..200 {
+fn count_recursive_bugs(initial_state: &Grid<bool>, minutes: usize) -> u32 {
+    let mut multi_eris = Eris::new(initial_state, true);
+    for _ in 0..minutes {
         multi_eris = multi_eris.tick();
     }
-    let part_2_answer = multi_eris.count();
-
-    println!(
-        "Part 1: {}\nPart 2: {}\nTime: {}ms",
-        part_1_answer,
-        part_2_answer,
-        start_time.elapsed().as_millis()
-    );
+    multi_eris.count()
 }
 
 // This represents a single level of Eris (which means the whole thing, for part 1.)
@@ -57,12 +94,11 @@ struct Eris {
 
 impl Eris {
     // Create the first Eris level.
-    fn new(initial_state: &str, multi_level: bool) -> Eris {
-        let state_joined: String = initial_state.lines().collect();
+    fn new(initial_state: &Grid<bool>, multi_level: bool) -> Eris {
         let mut state = 0;
-        for (i, c) in state_joined.chars().enumerate() {
-            if c == '#' {
-                state |= 1 << i
+        for (pos, &bug) in initial_state.iter() {
+            if bug {
+                state |= 1 << (pos.y * 5 + pos.x)
             }
         }
         Eris {
@@ -214,23 +250,60 @@ impl Eris {
         }
     }
 
-    fn _print_state(&self) {
-        let mut display = String::new();
-        for i in 0..25 {
-            display.push(match (i, self.state & 1 << i) {
-                (12, _) => ' ',
-                (_, 0) => '.',
-                (_, _) => '#',
-            });
-            if i % 5 == 4 {
-                display.push('\n');
-            }
-        }
-        println!("{}", display);
+    // Draws this level of Eris, the same way as the puzzle does - on a recursive Eris, the
+    // middle tile is a whole other level, so that's a ?.
+    fn draw(&self) -> Vec<String> {
+        (0..5)
+            .map(|y| {
+                (0..5)
+                    .map(|x| match (y * 5 + x, self.state & 1 << (y * 5 + x)) {
+                        (12, _) if self.multi_level => '?',
+                        (_, 0) => '.',
+                        (_, _) => '#',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
 
-        if let Some(child) = self.child.as_ref() {
-            println!();
-            child._print_state();
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "....#\n#..#.\n#..##\n..#..\n#....";
+
+    fn eris(picture: &str) -> Eris {
+        Eris::new(&Day24::parse(picture).unwrap().initial_state, false)
+    }
+
+    #[test]
+    fn single_level() {
+        let eris_after = eris(EXAMPLE).tick();
+        assert_eq!(eris_after.state, eris("#..#.\n####.\n###.#\n##.##\n.##..").state);
+        let eris_after = eris_after.tick();
+        assert_eq!(eris_after.state, eris("#####\n....#\n....#\n...#.\n#.###").state);
+    }
+
+    #[test]
+    fn biodiversity() {
+        assert_eq!(eris(".....\n.....\n.....\n#....\n.#...").state, 2_129_920);
+        assert_eq!(Day24::part1(&Day24::parse(EXAMPLE).unwrap()), Answer::from(2_129_920));
+        let scan = Day24::parse(EXAMPLE).unwrap();
+        let repeated = scan.first_repeat().draw();
+        assert_eq!(repeated, vec![".....", ".....", ".....", "#....", ".#..."]);
+        assert_eq!(Eris::new(&scan.initial_state, true).draw()[2], "#.?##");
+    }
+
+    #[test]
+    fn recursive() {
+        assert_eq!(count_recursive_bugs(&Day24::parse(EXAMPLE).unwrap().initial_state, 10), 99);
+    }
+
+    #[test]
+    fn bad_eris() {
+        assert!(Day24::parse("....#\n#..#.\n#..##\n..#..").is_err());
+        assert!(Day24::parse("....#\n#..#.\n#..##\n..#.\n#....").is_err());
+        assert!(Day24::parse("....#\n#..#.\n#.?##\n..#..\n#....").is_err());
     }
 }
@@ -0,0 +1,103 @@
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+
+use std::sync::mpsc;
+
+use aoc_core::{Answer, ParseError, Solution};
+
+// This code just serves as an interface between the Intcode computer and the user - it makes
+// no effort to automatically play the game, sorry.  Not interested in doing a bunch of dull
+// text parsing plus yet more maze solving!
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+    const INTERACTIVE: bool = true;
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(intcode::parse_program(input)?)
+    }
+
+    // The answer is the password that the game tells you once you've won.
+    fn part1(program: &Self::Input) -> Answer {
+        play(program).map_or(Answer::None, Answer::from)
+    }
+
+    // Merry Christmas!
+    fn part2(_program: &Self::Input) -> Answer {
+        Answer::None
+    }
+}
+
+// Play the game, returning the last number it tells us before it stops - or nothing if you
+// give up (by ending the input).
+fn play(program: &[i64]) -> Option<String> {
+    let (in_send, in_recv) = mpsc::channel();
+    let (out_send, out_recv) = mpsc::channel();
+    let mut computer = intcode::ChannelIOComputer::new(program, in_recv, out_send);
+    std::thread::spawn(move || { computer.run(); });
+
+    let mut last_number = None;
+    loop {
+        let mut display = String::new();
+        loop {
+            let c = match out_recv.recv() {
+                Ok(c) => c as u8 as char,
+                Err(_) => return last_number,
+            };
+            if c == '\n' {
+                println!("{}", display);
+                if display.eq("Command?") { break; }
+                if let Some(number) = find_number(&display) {
+                    last_number = Some(number.to_string());
+                }
+                display.clear();
+            } else {
+                display.push(c);
+            }
+        }
+
+        let mut input = String::new();
+        if std::io::stdin().read_line(&mut input).expect("Did not enter a string") == 0 {
+            return None;
+        }
+        // Bloody Windows and its CRLF line endings
+        if let Some('\n') = input.chars().next_back() {
+            input.pop();
+        }
+        if let Some('\r') = input.chars().next_back() {
+            input.pop();
+        }
+        input.push('\n');
+        for c in input.chars() {
+            in_send.send(c as i64).unwrap();
+        }
+    }
+}
+
+// The last number in a line of the game's output, if there is one.
+fn find_number(line: &str) -> Option<&str> {
+    line.split(|c: char| !c.is_ascii_digit()).rfind(|word| !word.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passwords() {
+        assert_eq!(
+            find_number(
+                "\"Oh, hello! You should be able to get in by typing 2147485856 on the keypad at \
+                 the main airlock.\""
+            ),
+            Some("2147485856")
+        );
+        assert_eq!(find_number("== Hull Breach =="), None);
+        assert_eq!(find_number("- 12 and 34"), Some("34"));
+    }
+}
@@ -1,61 +1,43 @@
 use std::cmp;
-use std::io::{self, Read};
+use std::io;
 
-fn main() {
-    let start_time = std::time::Instant::now();
+use aoc_core::{Answer, ParseError, Solution};
+use grid::{Direction, Point};
 
-    let wires = load_wires().unwrap_or_else(|err| {
-        println!("Could not load input file!\n{:?}", err);
-        std::process::exit(1);
-    });
+pub struct Day3;
 
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+    type Input = Vec<Wire>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_wires(input)?)
+    }
+
+    fn part1(wires: &Self::Input) -> Answer {
+        find_intersections(wires).iter().map(|int| int.manhattan_distance).min().unwrap().into()
+    }
+
+    fn part2(wires: &Self::Input) -> Answer {
+        find_intersections(wires).iter().map(|int| int.wire_length).min().unwrap().into()
+    }
+}
+
+fn find_intersections(wires: &[Wire]) -> Vec<Intersection> {
     // Could easily extend to arbitrary number of wires, but let's KISS.
-    let mut manhattan_distances = Vec::new();
-    let mut wire_lengths = Vec::new();
+    let mut intersections = Vec::new();
     for first in &wires[1].segments {
         for second in &wires[0].segments {
-            if let Some(int) = calculate_intersection(&first, &second) {
-                if (int.coords.x == 0) && (int.coords.y == 0) {
+            if let Some(int) = calculate_intersection(first, second) {
+                if int.coords == Point::ORIGIN {
                     // Of course they intersect at the origin - ignore that!
                     continue;
                 }
-                manhattan_distances.push(int.manhattan_distance);
-                wire_lengths.push(int.wire_length);
+                intersections.push(int);
             }
         }
     }
-
-    println!(
-        "Shortest Manhattan: {}\nShortest length: {}\nTime: {}us",
-        manhattan_distances.iter().min().unwrap(),
-        wire_lengths.iter().min().unwrap(),
-        start_time.elapsed().as_micros()
-    );
-}
-
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn end(&self, start: Coordinates, distance: i32) -> Coordinates {
-        match self {
-            Self::Up => Coordinates { x: start.x, y: start.y + distance },
-            Self::Down => Coordinates { x: start.x, y: start.y - distance },
-            Self::Right => Coordinates { x: start.x + distance, y: start.y },
-            Self::Left => Coordinates { x: start.x - distance, y: start.y },
-        }
-    }
-
-    fn orientation(&self) -> Orientation {
-        match self {
-            Self::Up | Self::Down => Orientation::Vertical,
-            Self::Left | Self::Right => Orientation::Horizontal,
-        }
-    }
+    intersections
 }
 
 #[derive(PartialEq, Eq)]
@@ -64,29 +46,27 @@ enum Orientation {
     Vertical,
 }
 
-#[derive(PartialEq, Eq, Copy, Clone)]
-struct Coordinates {
-    x: i32,
-    y: i32,
-}
-
-impl Coordinates {
-    fn origin() -> Self {
-        Self { x: 0, y: 0 }
+impl From<Direction> for Orientation {
+    fn from(direction: Direction) -> Self {
+        if direction.is_horizontal() {
+            Self::Horizontal
+        } else {
+            Self::Vertical
+        }
     }
 }
 
 // A single piece of a wire.
 struct Segment {
-    length_before: i32,
+    length_before: i64,
     orientation: Orientation,
-    start: Coordinates,
-    end: Coordinates,
+    start: Point,
+    end: Point,
 }
 
 impl Segment {
     // Determine whether this segment intersects another one, and if so, where.
-    fn intersects(&self, other: &Segment) -> Option<Coordinates> {
+    fn intersects(&self, other: &Segment) -> Option<Point> {
         // I'm going to be cheeky and ignore the possibility of two horizontal or two
         // vertical segments intersecting along part of their length.
         //
@@ -105,10 +85,7 @@ impl Segment {
                 && (cmp::min(other.start.y, other.end.y) <= self.start.y)
                 && (cmp::max(other.start.y, other.end.y) >= self.start.y)
             {
-                Some(Coordinates {
-                    x: other.start.x,
-                    y: self.start.y,
-                })
+                Some(Point::new(other.start.x, self.start.y))
             } else {
                 None
             }
@@ -117,48 +94,37 @@ impl Segment {
             && (cmp::min(other.start.x, other.end.x) <= self.start.x)
             && (cmp::max(other.start.x, other.end.x) >= self.start.x)
         {
-            Some(Coordinates {
-                x: self.start.x,
-                y: other.start.y,
-            })
+            Some(Point::new(self.start.x, other.start.y))
         } else {
             None
         }
     }
 }
 
-struct Wire {
+pub struct Wire {
     segments: Vec<Segment>,
 }
 
 struct Intersection {
-    coords: Coordinates,
-    manhattan_distance: i32,
-    wire_length: i32,
+    coords: Point,
+    manhattan_distance: i64,
+    wire_length: i64,
 }
 
 fn calculate_intersection(first: &Segment, second: &Segment) -> Option<Intersection> {
     first.intersects(second).map(|coords| {
-        let first_length = manhattan_distance(coords, first.start) + first.length_before;
-        let second_length = manhattan_distance(coords, second.start) + second.length_before;
+        let first_length = coords.manhattan_distance(first.start) + first.length_before;
+        let second_length = coords.manhattan_distance(second.start) + second.length_before;
         Intersection {
             coords,
-            manhattan_distance: manhattan_distance(coords, Coordinates { x: 0, y: 0 }),
+            manhattan_distance: coords.manhattan_distance(Point::ORIGIN),
             wire_length: first_length + second_length,
         }
     })
 }
 
-fn manhattan_distance(first: Coordinates, second: Coordinates) -> i32 {
-    (second.x - first.x).abs() + (second.y - first.y).abs()
-}
-
-// Load the set of wires from file.
-fn load_wires() -> Result<Vec<Wire>, io::Error> {
-    let mut input_file = std::fs::File::open("day3/input.txt")?;
-    let mut input = String::new();
-    input_file.read_to_string(&mut input)?;
-
+// Parse the set of wires from the puzzle input.
+fn parse_wires(input: &str) -> Result<Vec<Wire>, io::Error> {
     let mut wires: Vec<Wire> = Vec::new();
     for line in input.lines() {
         let wire = read_wire(line)?;
@@ -171,7 +137,7 @@ fn load_wires() -> Result<Vec<Wire>, io::Error> {
 // Construct a complete wire from a line of the input file.
 fn read_wire(input_line: &str) -> Result<Wire, io::Error> {
     let mut length = 0;
-    let mut pos = Coordinates::origin();
+    let mut pos = Point::ORIGIN;
     let segments = input_line
         .split(',')
         .map(|cmd_text| { build_segment(&mut pos, &mut length, cmd_text) })
@@ -182,20 +148,20 @@ fn read_wire(input_line: &str) -> Result<Wire, io::Error> {
 
 // Construct a wire segment from a section of a wire line. Repeated calls
 // keep track of start position and wire length.
-fn build_segment(start: &mut Coordinates, length: &mut i32, command: &str) -> Result<Segment, io::Error> {
+fn build_segment(start: &mut Point, length: &mut i64, command: &str) -> Result<Segment, io::Error> {
     let mut chars = command.chars();
     let direction = parse_direction(chars.next())?;
-    let distance = chars.as_str().parse::<i32>().map_err(|e| {
+    let distance = chars.as_str().parse::<i64>().map_err(|e| {
         io::Error::new(
             io::ErrorKind::InvalidInput,
             format!("Invalid distance: {} ({})", chars.as_str(), e),
         )
     })?;
-    let end = direction.end(*start, distance);
+    let end = *start + direction.offset() * distance;
 
     let segment = Segment {
         length_before: *length,
-        orientation: direction.orientation(),
+        orientation: direction.into(),
         start: *start,
         end,
     };
@@ -226,4 +192,51 @@ fn parse_direction(c: Option<char>) -> Result<Direction, io::Error> {
             ))
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start: (i64, i64), end: (i64, i64)) -> Segment {
+        Segment {
+            length_before: 0,
+            orientation: if start.0 == end.0 {
+                Orientation::Vertical
+            } else {
+                Orientation::Horizontal
+            },
+            start: start.into(),
+            end: end.into(),
+        }
+    }
+
+    #[test]
+    fn segments_intersect() {
+        let horizontal = segment((0, 0), (5, 0));
+        let vertical = segment((3, -2), (3, 2));
+        assert_eq!(horizontal.intersects(&vertical), Some(Point::new(3, 0)));
+        assert_eq!(vertical.intersects(&horizontal), Some(Point::new(3, 0)));
+        assert_eq!(horizontal.intersects(&segment((6, -2), (6, 2))), None);
+        let touching = segment((5, 0), (9, 0));
+        assert_eq!(horizontal.intersects(&touching), Some(Point::new(5, 0)));
+        assert_eq!(horizontal.intersects(&segment((0, 1), (5, 1))), None);
+    }
+
+    #[test]
+    fn examples() {
+        let examples = [
+            ("R8,U5,L5,D3\nU7,R6,D4,L4", 6, 30),
+            ("R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83", 159, 610),
+            (
+                "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+                135,
+                410,
+            ),
+        ];
+        for (input, distance, length) in examples.iter() {
+            let wires = Day3::parse(input).unwrap();
+            assert_eq!(Day3::part1(&wires), Answer::from(*distance));
+            assert_eq!(Day3::part2(&wires), Answer::from(*length));
+        }
+    }
+}
@@ -1,5 +1,6 @@
-const MINIMUM_VALUE: u32 = 206_938;
-const MAXIMUM_VALUE: u32 = 679_128;
+use std::ops::RangeInclusive;
+
+use aoc_core::{Answer, ParseError, Solution};
 
 // I'm not _unhappy_ with this implementation, but it's simplistic.
 // You really want to look at AxlLind's:
@@ -7,19 +8,28 @@ const MAXIMUM_VALUE: u32 = 679_128;
 // It's a thing of beauty, and runs in a fraction of the time of this code.
 // I'm not going to work on this further as I'd just be copying his.
 
-fn main() {
-    let start_time = std::time::Instant::now();
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    type Input = RangeInclusive<u32>;
 
-    let (part_1_valid, part_2_valid) = (MINIMUM_VALUE..=MAXIMUM_VALUE).map(evaluate_password).unzip::<bool, bool, Vec<bool>, Vec<bool>>();
-    let part_1_count = part_1_valid.iter().filter(|item| **item).count();
-    let part_2_count = part_2_valid.iter().filter(|item| **item).count();
+    // The input is the range of passwords, e.g. "206938-679128".
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let input = input.trim_end();
+        let dash = input
+            .find('-')
+            .ok_or_else(|| ParseError::new(format!("Not a range: {}", input)))?;
+        Ok(input[..dash].parse()?..=input[dash + 1..].parse()?)
+    }
 
-    println!(
-        "Part 1: {}\nPart 2: {}\nTime: {}ms",
-        part_1_count,
-        part_2_count,
-        start_time.elapsed().as_millis()
-    );
+    fn part1(passwords: &Self::Input) -> Answer {
+        passwords.clone().map(evaluate_password).filter(|(valid, _)| *valid).count().into()
+    }
+
+    fn part2(passwords: &Self::Input) -> Answer {
+        passwords.clone().map(evaluate_password).filter(|(_, valid)| *valid).count().into()
+    }
 }
 
 // Returns two bools - the first is whether the password is valid by part 1 rules,
@@ -57,3 +67,28 @@ fn evaluate_password(password: u32) -> (bool, bool) {
 
     (at_least_double_digit, double_digit)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_passwords() {
+        assert!(evaluate_password(111111).0);
+        assert!(!evaluate_password(223450).0);
+        assert!(!evaluate_password(123789).0);
+    }
+
+    #[test]
+    fn part2_passwords() {
+        assert_eq!(evaluate_password(112233), (true, true));
+        assert_eq!(evaluate_password(123444), (true, false));
+        assert_eq!(evaluate_password(111122), (true, true));
+    }
+
+    #[test]
+    fn parse_range() {
+        assert_eq!(Day4::parse("206938-679128\n").unwrap(), 206938..=679128);
+        assert!(Day4::parse("206938").is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use aoc_core::{Answer, ParseError, Solution};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(intcode::parse_program(input)?)
+    }
+
+    fn part1(program: &Self::Input) -> Answer {
+        (*run_diagnostic(program, 1).last().unwrap()).into()
+    }
+
+    fn part2(program: &Self::Input) -> Answer {
+        (*run_diagnostic(program, 5).first().unwrap()).into()
+    }
+}
+
+fn run_diagnostic(program: &[i64], system_id: i64) -> Vec<i64> {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let program = program.to_vec();
+    runtime.block_on(async move {
+        tokio::spawn(async move {
+            intcode::run_async_computer(&program, &[system_id]).await
+        }).await.unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn echo() {
+        assert_eq!(run_diagnostic(&[3, 0, 4, 0, 99], 42), vec![42]);
+        assert_eq!(run_diagnostic(&[1002, 4, 3, 4, 33], 1), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn comparisons() {
+        let equal_to_8 = [3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+        assert_eq!(run_diagnostic(&equal_to_8, 8), vec![1]);
+        assert_eq!(run_diagnostic(&equal_to_8, 7), vec![0]);
+        let less_than_8 = [3, 3, 1107, -1, 8, 3, 4, 3, 99];
+        assert_eq!(run_diagnostic(&less_than_8, 7), vec![1]);
+        assert_eq!(run_diagnostic(&less_than_8, 8), vec![0]);
+    }
+
+    #[test]
+    fn jumps() {
+        let compare_to_8 = [
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+        assert_eq!(run_diagnostic(&compare_to_8, 7), vec![999]);
+        assert_eq!(run_diagnostic(&compare_to_8, 8), vec![1000]);
+        assert_eq!(run_diagnostic(&compare_to_8, 9), vec![1001]);
+    }
+}
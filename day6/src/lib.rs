@@ -1,10 +1,11 @@
-use std::io::{self, Read};
-use std::process;
+use std::io;
 
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::{Rc, Weak};
 
+use aoc_core::{Answer, ParseError, Solution};
+
 // This day is a great example of my approach to these challenges and how it differs from
 // some others'. Where possible, I like to build extensible representations of the input data
 // that (a) make it really easy to understand how the problem is being solved and (b) could 
@@ -18,47 +19,37 @@ use std::rc::{Rc, Weak};
 // traversal. The price I've paid for these gains is that there's _way_ more code here, and I
 // imagine it took me a lot longer!
 
-fn main() {
-    let start_time = std::time::Instant::now();
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    type Input = HashMap<String, Rc<RefCell<OrbitalObject>>>;
 
-    // Load from file and construct a tree and map of OrbitalObjects.
-    let map = load_orbits("day6/input.txt").unwrap_or_else(|err| {
-        println!("Could not load input file!\n{:?}", err);
-        process::exit(1);
-    });
+    // Construct a tree and map of OrbitalObjects.
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(parse_orbits(input)?)
+    }
 
-    // Day 6 part 1:
     // The total depth is simply the individual depth of each orbital object, summed.
-    let total_depth: u32 = map.values().map(|rc| rc.borrow_mut().get_depth()).sum();
+    fn part1(map: &Self::Input) -> Answer {
+        map.values().map(|rc| rc.borrow_mut().get_depth()).sum::<u32>().into()
+    }
 
-    // Day 6 part 2:
     // The distance Santa needs to move to orbit the same object as us is the distance between the
     // object he's orbiting and our common ancestor, plus the distance between the object we're
     // orbiting and our common ancestor.  Find all three objects.  That calculation can be
     // expressed as (Santa's depth plus our depth minus twice the ancestor's depth minus 2).
-    let santa = map.get("SAN").unwrap_or_else(|| {
-        println!("Invalid input file - SAN not found!");
-        process::exit(1);
-    });
-    let you = map.get("YOU").unwrap_or_else(|| {
-        println!("Invalid input file - YOU not found!");
-        process::exit(1);
-    });
-    let common_ancestor = OrbitalObject::find_common_ancestor(santa, you).unwrap_or_else(|| {
-        println!("Invalid input file - YOU and SAN aren't indirectly orbiting a common object!");
-        process::exit(1);
-    });
-
-    let distance = santa.borrow().depth.unwrap() + you.borrow().depth.unwrap()
-        - 2
-        - (2 * common_ancestor.borrow().depth.unwrap());
-
-    println!(
-        "Total depth: {}\nDistance: {}\nTime: {}ms",
-        total_depth,
-        distance,
-        start_time.elapsed().as_millis()
-    );
+    fn part2(map: &Self::Input) -> Answer {
+        let santa = map.get("SAN").expect("Invalid input file - SAN not found!");
+        let you = map.get("YOU").expect("Invalid input file - YOU not found!");
+        let common_ancestor = OrbitalObject::find_common_ancestor(santa, you)
+            .expect("Invalid input file - YOU and SAN aren't indirectly orbiting a common object!");
+
+        let santa_depth = santa.borrow_mut().get_depth();
+        let you_depth = you.borrow_mut().get_depth();
+        let ancestor_depth = common_ancestor.borrow_mut().get_depth();
+        (santa_depth + you_depth - 2 - (2 * ancestor_depth)).into()
+    }
 }
 
 /// An object that may be in orbit around another object and/or may have other objects in orbit
@@ -96,8 +87,11 @@ impl OrbitalObject {
     /// # Examples
     ///
     /// ```
-    /// let parent = Rc::new(RefCell::new(OrbitalObject::new(parent_name)));
-    /// let child = Rc::new(RefCell::new(OrbitalObject::new(child_name)));
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// # use day6::OrbitalObject;
+    /// let parent = Rc::new(RefCell::new(OrbitalObject::new(String::from("COM"))));
+    /// let child = Rc::new(RefCell::new(OrbitalObject::new(String::from("B"))));
     /// OrbitalObject::associate(&parent, &child);
     /// ```
     pub fn associate(parent: &Rc<RefCell<Self>>, child: &Rc<RefCell<Self>>) {
@@ -128,14 +122,17 @@ impl OrbitalObject {
     /// # Examples
     ///
     /// ```
-    /// let parent = Rc::new(RefCell::new(OrbitalObject::new(parent_name)));
-    /// let child_one = Rc::new(RefCell::new(OrbitalObject::new(child_one_name)));
-    /// let child_two = Rc::new(RefCell::new(OrbitalObject::new(child_two_name)));
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// # use day6::OrbitalObject;
+    /// let parent = Rc::new(RefCell::new(OrbitalObject::new(String::from("COM"))));
+    /// let child_one = Rc::new(RefCell::new(OrbitalObject::new(String::from("B"))));
+    /// let child_two = Rc::new(RefCell::new(OrbitalObject::new(String::from("C"))));
     /// OrbitalObject::associate(&parent, &child_one);
     /// OrbitalObject::associate(&parent, &child_two);
     ///
-    /// let ancestor = OrbitalObject::find_common_ancestor(&child_one, &child_two);
-    /// assert_eq!(ancestor, parent);
+    /// let ancestor = OrbitalObject::find_common_ancestor(&child_one, &child_two).unwrap();
+    /// assert!(Rc::ptr_eq(&ancestor, &parent));
     /// ```
     #[must_use]
     pub fn find_common_ancestor(
@@ -171,20 +168,15 @@ impl OrbitalObject {
     }
 }
 
-/// Loads a definition of orbits from file and constructs a tree of `OrbitalObject`s from them, plus
-/// a map indexed by `OrbitalObject.name`.  Returns an error if the file cannot be found or loaded,
-/// or if its contents are invalid.
+/// Parses a definition of orbits and constructs a tree of `OrbitalObject`s from them, plus a map
+/// indexed by `OrbitalObject.name`.  Returns an error if the definition is invalid.
 ///
 /// # Errors
 ///
-/// Returns an `io::Error` for any issues reading `source_file`, including invalid contents.
-pub fn load_orbits(
-    source_file: &str,
+/// Returns an `io::Error` if any line of `orbits` is invalid.
+pub fn parse_orbits(
+    orbits: &str,
 ) -> Result<HashMap<String, Rc<RefCell<OrbitalObject>>>, io::Error> {
-    let mut input = std::fs::File::open(source_file)?;
-    let mut orbits = String::new();
-    input.read_to_string(&mut orbits)?;
-
     let mut map: HashMap<String, Rc<RefCell<OrbitalObject>>> = HashMap::new();
 
     for line in orbits.lines() {
@@ -221,3 +213,32 @@ fn get_orbital_object(
         rc
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORBITS: &str = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L";
+
+    #[test]
+    fn total_orbits() {
+        let map = Day6::parse(ORBITS).unwrap();
+        assert_eq!(map["D"].borrow_mut().get_depth(), 3);
+        assert_eq!(map["L"].borrow_mut().get_depth(), 7);
+        assert_eq!(map["COM"].borrow_mut().get_depth(), 0);
+        assert_eq!(Day6::part1(&map), Answer::from(42));
+    }
+
+    #[test]
+    fn transfers() {
+        let map = Day6::parse(&format!("{}\nK)YOU\nI)SAN", ORBITS)).unwrap();
+        let ancestor = OrbitalObject::find_common_ancestor(&map["YOU"], &map["SAN"]).unwrap();
+        assert_eq!(ancestor.borrow().name, "D");
+        assert_eq!(Day6::part2(&map), Answer::from(4));
+    }
+
+    #[test]
+    fn bad_orbits() {
+        assert!(Day6::parse("COM)B\nB-C").is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use aoc_core::{Answer, ParseError, Solution};
+use cjp_threadpool::ThreadPool;
+use itertools::Itertools;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(intcode::parse_program(input)?)
+    }
+
+    fn part1(program: &Self::Input) -> Answer {
+        find_max_amplifier_signal(program, &[0, 1, 2, 3, 4], run_amplifier_non_feedback_sequence).into()
+    }
+
+    fn part2(program: &Self::Input) -> Answer {
+        find_max_amplifier_signal(program, &[5, 6, 7, 8, 9], run_amplifier_feedback_sequence).into()
+    }
+}
+
+fn find_max_amplifier_signal(
+    program: &[i64],
+    valid_phase_settings: &[i64],
+    sequence_func: fn(&[i64], &[i64]) -> i64,
+) -> i64 {
+    // Run the amplifier sequence for each permutation of the phase settings
+    // provided, spread across a thread pool, and find the highest output.
+    let pool = ThreadPool::<()>::new_with_default_size();
+    let program = program.to_vec();
+    let sequences = valid_phase_settings.iter().copied().permutations(5);
+    let max_output = pool
+        .reduce(
+            sequences,
+            move |sequence| sequence_func(&program, &sequence),
+            std::cmp::max,
+        )
+        .unwrap();
+    pool.join();
+    max_output
+}
+
+fn run_amplifier_non_feedback_sequence(program: &[i64], sequence: &[i64]) -> i64 {
+    // Run the computer once for each phase setting in the sequence provided.
+    // Give each computer, as inputs, the corresponding phase setting, plus the output
+    // from the previous computer. (0 for the first one.)
+    let mut input = 0;
+    for phase_setting in sequence {
+        let inputs = vec![*phase_setting, input];
+        let outputs = intcode::run_parallel_computer(program, &inputs);
+        input = outputs[0];
+    }
+    input
+}
+
+fn run_amplifier_feedback_sequence(program: &[i64], sequence: &[i64]) -> i64 {
+    // Run a computer for each phase setting in the sequence provided, all taking turns on
+    // this thread.  Each amplifier reads from the pipe named after it, and writes to the
+    // next one's, with E writing back to A.  Bootstrap each pipe with the phase setting,
+    // and A's with 0 as well to kick things off.
+    const AMPLIFIERS: [&str; 5] = ["A", "B", "C", "D", "E"];
+    let mut scheduler = intcode::Scheduler::new(1000);
+    for (index, phase_setting) in sequence.iter().enumerate() {
+        let name = AMPLIFIERS[index];
+        scheduler.send(name, *phase_setting);
+        scheduler.add_machine(name, program, name, AMPLIFIERS[(index + 1) % 5]);
+    }
+    scheduler.send("A", 0);
+
+    // Once they've all halted, E's last output is sitting in A's pipe.
+    scheduler
+        .run()
+        .unwrap_or_else(|deadlock| panic!("{}", deadlock));
+    scheduler.drain("A").pop().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_feedback_examples() {
+        let examples: [(&[i64], &[i64], i64); 3] = [
+            (
+                &[3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0],
+                &[4, 3, 2, 1, 0],
+                43210,
+            ),
+            (
+                &[3, 23, 3, 24, 1002, 24, 10, 24, 1002, 23, -1, 23, 101, 5, 23, 23, 1, 24, 23, 23,
+                  4, 23, 99, 0, 0],
+                &[0, 1, 2, 3, 4],
+                54321,
+            ),
+            (
+                &[3, 31, 3, 32, 1002, 32, 10, 32, 1001, 31, -2, 31, 1007, 31, 0, 33, 1002, 33, 7,
+                  33, 1, 33, 31, 31, 1, 32, 31, 31, 4, 31, 99, 0, 0, 0],
+                &[1, 0, 4, 3, 2],
+                65210,
+            ),
+        ];
+        let run: fn(&[i64], &[i64]) -> i64 = run_amplifier_non_feedback_sequence;
+        for (program, sequence, signal) in examples.iter() {
+            assert_eq!(run(program, sequence), *signal);
+            assert_eq!(find_max_amplifier_signal(program, &[0, 1, 2, 3, 4], run), *signal);
+        }
+    }
+
+    #[test]
+    fn feedback_examples() {
+        let examples: [(&[i64], &[i64], i64); 2] = [
+            (
+                &[3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28,
+                  -1, 28, 1005, 28, 6, 99, 0, 0, 5],
+                &[9, 8, 7, 6, 5],
+                139_629_729,
+            ),
+            (
+                &[3, 52, 1001, 52, -5, 52, 3, 53, 1, 52, 56, 54, 1007, 54, 5, 55, 1005, 55, 26,
+                  1001, 54, -5, 54, 1105, 1, 12, 1, 53, 54, 53, 1008, 54, 0, 55, 1001, 55, 1, 55,
+                  2, 53, 55, 53, 4, 53, 1001, 56, -1, 56, 1005, 56, 6, 99, 0, 0, 0, 0, 10],
+                &[9, 7, 8, 5, 6],
+                18216,
+            ),
+        ];
+        let run: fn(&[i64], &[i64]) -> i64 = run_amplifier_feedback_sequence;
+        for (program, sequence, signal) in examples.iter() {
+            assert_eq!(run(program, sequence), *signal);
+            assert_eq!(find_max_amplifier_signal(program, &[5, 6, 7, 8, 9], run), *signal);
+        }
+    }
+}
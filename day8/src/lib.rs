@@ -0,0 +1,166 @@
+#![allow(clippy::cast_sign_loss)]
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+
+use aoc_core::{Answer, ParseError, Solution};
+use grid::Grid;
+
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    type Input = Image;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let data = input.trim_end();
+        if let Some(invalid) = data.chars().find(|c| !('0'..='2').contains(c)) {
+            return Err(ParseError::new(format!("Invalid pixel: {}", invalid)));
+        }
+        Ok(Image::from_str(data, WIDTH, HEIGHT))
+    }
+
+    fn part1(image: &Self::Input) -> Answer {
+        let max_zeroes = image.layers.iter().map(Layer::num_zeroes).min().unwrap();
+        image.layers.iter().find(|layer| layer.num_zeroes() == max_zeroes).unwrap().part_1_score().into()
+    }
+
+    // If the message can't be read, give the picture instead, for a person to read.  The
+    // extras say why it couldn't be read.
+    fn part2(image: &Self::Input) -> Answer {
+        match image.pixels().read_letters() {
+            Ok(message) => message.into(),
+            Err(_) => Answer::Image(image.draw()),
+        }
+    }
+
+    fn extra(image: &Self::Input) -> Vec<(&'static str, Answer)> {
+        let mut extra = vec![("message", Answer::Image(image.draw()))];
+        if let Err(error) = image.pixels().read_letters() {
+            extra.push(("ocr_error", Answer::Text(error.to_string())));
+        }
+        extra
+    }
+}
+
+struct Layer {
+    size: usize,
+    pixels: Vec<usize>,
+    pixel_counts: [usize; 3],
+}
+
+impl Layer {
+    fn new(size: usize) -> Self {
+        Self {
+            size,
+            pixels: Vec::with_capacity(size),
+            pixel_counts: [0; 3],
+        }
+    }
+
+    fn add_pixel(&mut self, pixel: usize) {
+        self.pixels.push(pixel);
+        self.pixel_counts[pixel] += 1;
+    }
+
+    fn is_full(&self) -> bool {
+        self.pixels.len() == self.size
+    }
+
+    fn num_zeroes(&self) -> usize {
+        self.pixel_counts[0]
+    }
+
+    fn part_1_score(&self) -> usize {
+        self.pixel_counts[1] * self.pixel_counts[2]
+    }
+}
+
+pub struct Image {
+    width: usize,
+    height: usize,
+    layers: Vec<Layer>,
+}
+
+impl Image {
+    fn from_str(data: &str, width: usize, height: usize) -> Self {
+        let mut image = Self { width, height, layers: Vec::new() };
+        image.new_layer();
+
+        data.chars()
+            .map(|c| c.to_digit(10).unwrap())
+            .for_each(|digit| {
+                image.add_pixel(digit as usize);
+            });
+
+        image
+    }
+
+    fn new_layer(&mut self) {
+        self.layers.push(Layer::new(self.width * self.height));
+    }
+
+    fn add_pixel(&mut self, pixel: usize) {
+        if self.layers.last().unwrap().is_full() { self.new_layer(); }
+        self.layers.last_mut().unwrap().add_pixel(pixel);
+    }
+
+    // Each pixel shows the colour of the first layer that isn't transparent there, where
+    // white is true.
+    fn pixels(&self) -> Grid<bool> {
+        let visible: Vec<bool> = (0..self.width * self.height)
+            .map(|i| {
+                let mut colours = self.layers.iter().map(|layer| layer.pixels[i]);
+                colours.find(|&colour| colour != 2) == Some(1)
+            })
+            .collect();
+        Grid::from_rows(visible.chunks(self.width).map(<[bool]>::to_vec).collect()).unwrap()
+    }
+
+    fn draw(&self) -> Vec<String> {
+        self.pixels().render(|&white| if white { '#' } else { ' ' })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layers() {
+        let image = Image::from_str("012011220122", 3, 2);
+        assert_eq!(image.layers.len(), 2);
+        assert_eq!(image.layers[0].pixels, vec![0, 1, 2, 0, 1, 1]);
+        assert_eq!(image.layers[1].pixels, vec![2, 2, 0, 1, 2, 2]);
+        assert_eq!(image.layers[0].num_zeroes(), 2);
+        assert_eq!(image.layers[1].num_zeroes(), 1);
+
+        // The layer with the fewest zeroes has one 1 and four 2s.
+        assert_eq!(Day8::part1(&image), Answer::from(4));
+    }
+
+    #[test]
+    fn draw() {
+        let image = Image::from_str("0222112222120000", 2, 2);
+        assert_eq!(image.draw(), vec![" #", "# "]);
+    }
+
+    #[test]
+    fn message() {
+        // An L, with a stray column down the side that's covered up by the layer on top.
+        let top = "22220".repeat(6);
+        let bottom = "10001".repeat(5) + "11111";
+        let image = Image::from_str(&(top + &bottom), 5, 6);
+        assert_eq!(Day8::part2(&image), Answer::from("L"));
+        assert_eq!(Day8::extra(&image).len(), 1);
+
+        // Not a letter at all.
+        let image = Image::from_str(&"10001".repeat(6), 5, 6);
+        assert_eq!(Day8::part2(&image), Answer::Image(vec![String::from("#   #"); 6]));
+        let error = image.pixels().read_letters().unwrap_err().to_string();
+        assert_eq!(Day8::extra(&image)[1], ("ocr_error", Answer::Text(error)));
+    }
+}
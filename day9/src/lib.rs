@@ -0,0 +1,38 @@
+use aoc_core::{Answer, ParseError, Solution};
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(intcode::parse_program(input)?)
+    }
+
+    fn part1(program: &Self::Input) -> Answer {
+        let outputs = intcode::run_parallel_computer(program, &[1]);
+        outputs[outputs.len() - 1].into()
+    }
+
+    fn part2(program: &Self::Input) -> Answer {
+        intcode::run_parallel_computer(program, &[2])[0].into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn quine() {
+        let program = [109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99];
+        assert_eq!(intcode::run_parallel_computer(&program, &[]), program.to_vec());
+    }
+
+    #[test]
+    fn large_numbers() {
+        let program = [1102, 34_915_192, 34_915_192, 7, 4, 7, 99, 0];
+        assert_eq!(intcode::run_parallel_computer(&program, &[]), vec![1_219_070_632_396_864]);
+        let program = [104, 1_125_899_906_842_624, 99];
+        assert_eq!(intcode::run_parallel_computer(&program, &[]), vec![1_125_899_906_842_624]);
+    }
+}
@@ -0,0 +1,100 @@
+//! Rectangular areas of a grid.
+
+use crate::Point;
+
+/// The rectangle from `min` to `max`, including both.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    /// The smallest rectangle that includes both points, whichever way round they are.
+    #[must_use]
+    pub fn new(corner: Point, other_corner: Point) -> Self {
+        Self {
+            min: Point::new(corner.x.min(other_corner.x), corner.y.min(other_corner.y)),
+            max: Point::new(corner.x.max(other_corner.x), corner.y.max(other_corner.y)),
+        }
+    }
+
+    /// The smallest rectangle that includes all of the points, or `None` if there aren't any.
+    pub fn around(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = Self::new(first, first);
+        points.for_each(|point| bounds.include(point));
+        Some(bounds)
+    }
+
+    /// Grows the rectangle, if need be, to include the point.
+    pub fn include(&mut self, point: Point) {
+        self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// Every point in the rectangle, a row at a time from the top, and left to right along
+    /// each row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes() {
+        let bounds = Bounds::new(Point::new(2, 3), Point::new(-1, 1));
+        assert_eq!(
+            (bounds.min, bounds.max),
+            (Point::new(-1, 1), Point::new(2, 3))
+        );
+        assert_eq!((bounds.width(), bounds.height()), (4, 3));
+        assert!(bounds.contains(Point::new(-1, 3)));
+        assert!(!bounds.contains(Point::new(3, 3)));
+        assert!(!bounds.contains(Point::new(0, 0)));
+    }
+
+    #[test]
+    fn growing() {
+        let points = [Point::new(1, 1), Point::new(-2, 4), Point::new(0, -1)];
+        let mut bounds = Bounds::around(points.iter().copied()).unwrap();
+        assert_eq!(bounds, Bounds::new(Point::new(-2, -1), Point::new(1, 4)));
+        bounds.include(Point::new(5, 0));
+        assert_eq!(bounds, Bounds::new(Point::new(-2, -1), Point::new(5, 4)));
+        assert_eq!(Bounds::around(Vec::new()), None);
+    }
+
+    #[test]
+    fn points() {
+        let bounds = Bounds::new(Point::new(0, 0), Point::new(1, 1));
+        assert_eq!(
+            bounds.points().collect::<Vec<_>>(),
+            vec![
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(1, 1)
+            ]
+        );
+    }
+}
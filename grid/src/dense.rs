@@ -0,0 +1,314 @@
+//! Grids with something at every point.
+
+use std::io;
+use std::ops::{Index, IndexMut};
+
+use crate::{Bounds, Point};
+
+/// A rectangular grid, `width` wide and `height` high, with its top left corner at the
+/// origin and something at every point.
+///
+/// The grid is stored a row at a time in a single `Vec`, so looking things up is quick, and
+/// cloning it is a single allocation.  Index it with a [`Point`] - that panics if the point
+/// is outside the grid, whereas [`get()`] just says there's nothing there.
+///
+/// [`Point`]: struct.Point.html
+/// [`get()`]: #method.get
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid with the same thing at every point.
+    #[must_use]
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Makes a grid out of its rows, top to bottom.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rows aren't all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, io::Error> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (index, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "The grid should be a rectangle, but row {} is {} long, not {}",
+                        index + 1,
+                        row.len(),
+                        width
+                    ),
+                ));
+            }
+            cells.extend(row);
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The rectangle that the grid covers.  (For an empty grid, that's from `(0, 0)` to
+    /// `(-1, -1)`, which has no points in it.)
+    #[must_use]
+    pub fn bounds(&self) -> Bounds {
+        Bounds {
+            min: Point::ORIGIN,
+            max: Point::new(self.width as i64 - 1, self.height as i64 - 1),
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as usize) < self.width
+            && (point.y as usize) < self.height
+    }
+
+    /// What's at a point, or `None` if it's outside the grid.
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point)
+            .map(move |index| &mut self.cells[index])
+    }
+
+    /// Every point in the grid, a row at a time from the top.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        self.bounds().points()
+    }
+
+    /// Every point in the grid, with what's there, a row at a time from the top.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// Every point in the grid, with what's there for changing, a row at a time from the top.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        self.points().zip(self.cells.iter_mut())
+    }
+
+    /// The rows of the grid, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks()` doesn't like a width of 0, but there are no rows to give then anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The neighbours of a point - the points one step up, right, down and left of it - that
+    /// are inside the grid.
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point
+            .neighbours()
+            .filter(move |&neighbour| self.contains(neighbour))
+    }
+
+    /// The first point, reading the grid a row at a time, whose contents match.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, contents)| predicate(contents))
+            .map(|(point, _)| point)
+    }
+
+    /// A grid the same size, with `f` applied to everything in it.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Draws the grid, one string per row, with `f` saying how to draw each point.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> Vec<String> {
+        self.rows()
+            .map(|row| row.iter().map(&mut f).collect())
+            .collect()
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.y as usize * self.width + point.x as usize)
+        } else {
+            None
+        }
+    }
+
+    fn expect_index(&self, point: Point) -> usize {
+        self.index_of(point).unwrap_or_else(|| {
+            panic!(
+                "{} is outside the {}x{} grid",
+                point, self.width, self.height
+            )
+        })
+    }
+}
+
+impl Grid<char> {
+    /// Reads a grid drawn in ASCII, one line per row.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lines aren't all the same length.
+    pub fn parse(text: &str) -> Result<Self, io::Error> {
+        Self::from_rows(text.lines().map(|line| line.chars().collect()).collect())
+    }
+
+    /// Reads a grid drawn in ASCII, one line per row, making the short lines up to the
+    /// length of the longest with `padding`.  That's for drawings that might have lost
+    /// the spaces off the ends of their lines.
+    #[must_use]
+    pub fn parse_padded(text: &str, padding: char) -> Self {
+        let width = text
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let rows = text
+            .lines()
+            .map(|line| {
+                let mut row: Vec<char> = line.chars().collect();
+                row.resize(width, padding);
+                row
+            })
+            .collect();
+        Self::from_rows(rows).unwrap()
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        &self.cells[self.expect_index(point)]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let index = self.expect_index(point);
+        &mut self.cells[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PICTURE: &str = "#..\n.#.\n..#\n#.#";
+
+    #[test]
+    fn parsing() {
+        let grid = Grid::parse(PICTURE).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid[Point::new(1, 1)], '#');
+        assert_eq!(grid[Point::new(2, 0)], '.');
+        assert_eq!(grid.render(|&c| c).join("\n"), PICTURE);
+
+        let grid = Grid::parse("").unwrap();
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.points().count(), 0);
+        assert!(grid.render(|&c| c).is_empty());
+    }
+
+    #[test]
+    fn bad_grids() {
+        assert_eq!(
+            Grid::parse("###\n##\n###").unwrap_err().to_string(),
+            "The grid should be a rectangle, but row 2 is 2 long, not 3"
+        );
+        let grid = Grid::parse_padded("  A\n#\n##..", ' ');
+        assert_eq!(grid.render(|&c| c), vec!["  A ", "#   ", "##.."]);
+    }
+
+    #[test]
+    fn bounds() {
+        let mut grid = Grid::new(3, 2, 0);
+        assert!(grid.contains(Point::new(2, 1)));
+        assert!(!grid.contains(Point::new(3, 1)));
+        assert!(!grid.contains(Point::new(0, -1)));
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.get_mut(Point::new(0, 2)), None);
+        *grid.get_mut(Point::new(1, 1)).unwrap() = 5;
+        for (point, contents) in grid.iter_mut() {
+            *contents += point.y;
+        }
+        grid[Point::new(2, 0)] = 7;
+        assert_eq!(grid.get(Point::new(1, 1)), Some(&6));
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[0, 0, 7][..], &[1, 6, 1][..]]
+        );
+        assert_eq!(grid.bounds(), Bounds::new(Point::ORIGIN, Point::new(2, 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "3,0 is outside the 3x2 grid")]
+    fn indexing_outside() {
+        let grid = Grid::new(3, 2, 0);
+        let _ = grid[Point::new(3, 0)];
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::parse(PICTURE).unwrap();
+        let corner: Vec<Point> = grid.neighbours(Point::ORIGIN).collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbours(Point::new(1, 1)).count(), 4);
+        let walls = grid
+            .iter()
+            .filter(|(_, &c)| c == '#')
+            .map(|(point, _)| point)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            walls,
+            vec![
+                Point::new(0, 0),
+                Point::new(1, 1),
+                Point::new(2, 2),
+                Point::new(0, 3),
+                Point::new(2, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn finding_and_mapping() {
+        let grid = Grid::parse(PICTURE).unwrap();
+        assert_eq!(grid.find(|&c| c == '#'), Some(Point::ORIGIN));
+        assert_eq!(grid.find(|&c| c == '@'), None);
+        let walls = grid.map(|&c| c == '#');
+        assert_eq!(walls.iter().filter(|(_, &wall)| wall).count(), 5);
+        assert_eq!(walls.render(|&wall| if wall { 'X' } else { ' ' })[3], "X X");
+    }
+}
@@ -0,0 +1,123 @@
+//! The ways to go from a point to its neighbours.
+
+use crate::Point;
+
+/// Up, down, left or right, as seen on a picture of the grid - so up is towards smaller `y`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// Which way to turn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// All four directions, clockwise from up.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Reads a direction from a single character.  Puzzles write directions in all sorts of
+    /// ways, so any of `U`, `R`, `D` and `L`, `N`, `E`, `S` and `W`, or the arrows `^`, `>`, `v`
+    /// and `<` will do.
+    #[must_use]
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | 'N' | '^' => Some(Self::Up),
+            'R' | 'E' | '>' => Some(Self::Right),
+            'D' | 'S' | 'v' => Some(Self::Down),
+            'L' | 'W' | '<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    /// How far one step in this direction moves.
+    #[must_use]
+    pub fn offset(self) -> Point {
+        match self {
+            Self::Up => Point::new(0, -1),
+            Self::Right => Point::new(1, 0),
+            Self::Down => Point::new(0, 1),
+            Self::Left => Point::new(-1, 0),
+        }
+    }
+
+    #[must_use]
+    pub fn turn(self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+        }
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Left => Self::Down,
+            Self::Down => Self::Right,
+            Self::Right => Self::Up,
+        }
+    }
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    /// The opposite direction.
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    /// Whether this is left or right, rather than up or down.
+    #[must_use]
+    pub fn is_horizontal(self) -> bool {
+        self == Self::Left || self == Self::Right
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        for &dir in &Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn(Turn::Right).turn(Turn::Right), dir.reverse());
+            assert_eq!(dir.offset() + dir.reverse().offset(), Point::ORIGIN);
+        }
+
+        // Clockwise, so each is a right turn from the one before.
+        for (index, &dir) in Direction::ALL.iter().enumerate() {
+            assert_eq!(dir.turn_right(), Direction::ALL[(index + 1) % 4]);
+        }
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(Direction::from_char('U'), Some(Direction::Up));
+        assert_eq!(Direction::from_char('E'), Some(Direction::Right));
+        assert_eq!(Direction::from_char('v'), Some(Direction::Down));
+        assert_eq!(Direction::from_char('<'), Some(Direction::Left));
+        assert_eq!(Direction::from_char('X'), None);
+    }
+
+    #[test]
+    fn orientation() {
+        assert!(Direction::Left.is_horizontal());
+        assert!(!Direction::Up.is_horizontal());
+    }
+}
@@ -0,0 +1,62 @@
+//! # Grid
+//!
+//! `grid` is the two-dimensional geometry that a lot of the days need: points on a grid,
+//! the directions between them, and grids of things to put at those points.
+//!
+//! - A [`Point`] is a pair of coordinates, with `x` increasing to the right and `y`
+//!   increasing _downwards_, the same as reading a picture of the grid off the screen.
+//! - A [`Direction`] is one of the four ways to step from a point to its neighbours, and
+//!   can be turned left and right.
+//! - A [`Grid`] is a rectangle with something at every point, stored in a single `Vec`.
+//!   Most puzzle inputs are one of these, drawn in ASCII, and [`Grid::parse()`] reads them.
+//! - A [`SparseGrid`] only has things at the points it's been told about, and grows in any
+//!   direction to fit them - just right for exploring somewhere without knowing how big it
+//!   is.  When we're done exploring, it can be turned into a [`Grid`].
+//!
+//! Both kinds of grid can be drawn back out as ASCII, one string per row, which is what an
+//! `Answer::Image` wants.  And when a puzzle's answer is capital letters drawn in pixels, a
+//! grid of those pixels can read them with [`Grid::read_letters()`].
+//!
+//! # Examples
+//!
+//! ```
+//! use grid::{Direction, Grid, Point};
+//!
+//! let maze = Grid::parse("#####\n#S..#\n###.#").unwrap();
+//! let start = maze.find(|&c| c == 'S').unwrap();
+//! assert_eq!(start, Point::new(1, 1));
+//!
+//! // Walk right until we hit a wall, then turn right.
+//! let mut pos = start;
+//! let mut dir = Direction::Right;
+//! while maze[pos + dir] != '#' {
+//!     pos += dir;
+//! }
+//! dir = dir.turn_right();
+//! assert_eq!((pos, dir, maze[pos + dir]), (Point::new(3, 1), Direction::Down, '.'));
+//!
+//! let open = maze.map(|&c| c != '#');
+//! assert_eq!(open.neighbours(pos).filter(|&p| open[p]).count(), 2);
+//! assert_eq!(open.render(|&open| if open { '.' } else { '#' })[2], "###.#");
+//! ```
+//!
+//! [`Point`]: struct.Point.html
+//! [`Direction`]: enum.Direction.html
+//! [`Grid`]: struct.Grid.html
+//! [`Grid::parse()`]: struct.Grid.html#method.parse
+//! [`Grid::read_letters()`]: struct.Grid.html#method.read_letters
+//! [`SparseGrid`]: struct.SparseGrid.html
+
+mod bounds;
+mod dense;
+mod direction;
+mod ocr;
+mod point;
+mod sparse;
+
+pub use bounds::Bounds;
+pub use dense::Grid;
+pub use direction::{Direction, Turn};
+pub use ocr::OcrError;
+pub use point::Point;
+pub use sparse::SparseGrid;
@@ -0,0 +1,292 @@
+//! Reading the capital letters that puzzles spell out in pixels.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::{Grid, Point};
+
+// A font, drawn the way the puzzles draw it.  Each letter starts `pitch` columns after the
+// one before; most are narrower than that, so there's a gap between letters.  A letter can
+// start with blank columns too, as I does.
+struct Font {
+    height: usize,
+    pitch: usize,
+    letters: &'static [(char, &'static [&'static str])],
+}
+
+// The font that most puzzles (this year, days 8 and 11) write their answers in.
+const SMALL: Font = Font {
+    height: 6,
+    pitch: 5,
+    letters: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+// The bigger font that some other years' puzzles use.
+#[rustfmt::skip]
+const LARGE: Font = Font {
+    height: 10,
+    pitch: 8,
+    letters: &[
+        ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#",
+                "######", "#....#", "#....#", "#....#", "#....#"]),
+        ('B', &["#####.", "#....#", "#....#", "#....#", "#####.",
+                "#....#", "#....#", "#....#", "#....#", "#####."]),
+        ('C', &[".####.", "#....#", "#.....", "#.....", "#.....",
+                "#.....", "#.....", "#.....", "#....#", ".####."]),
+        ('E', &["######", "#.....", "#.....", "#.....", "#####.",
+                "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('F', &["######", "#.....", "#.....", "#.....", "#####.",
+                "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('G', &[".####.", "#....#", "#.....", "#.....", "#.....",
+                "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+        ('H', &["#....#", "#....#", "#....#", "#....#", "######",
+                "#....#", "#....#", "#....#", "#....#", "#....#"]),
+        ('J', &["...###", "....#.", "....#.", "....#.", "....#.",
+                "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+        ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....",
+                "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+        ('L', &["#.....", "#.....", "#.....", "#.....", "#.....",
+                "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#",
+                "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+        ('P', &["#####.", "#....#", "#....#", "#....#", "#####.",
+                "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('R', &["#####.", "#....#", "#....#", "#....#", "#####.",
+                "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+        ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..",
+                "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+        ('Z', &["######", ".....#", ".....#", "....#.", "...#..",
+                "..#...", ".#....", "#.....", "#.....", "######"]),
+    ],
+};
+
+/// Why [`Grid::read_letters()`] couldn't read a grid.
+///
+/// [`Grid::read_letters()`]: struct.Grid.html#method.read_letters
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// Nothing is drawn in the grid at all.
+    Blank,
+
+    /// What's drawn is this many pixels high, which isn't the height of either font.
+    WrongHeight(usize),
+
+    /// Some of the letters weren't recognised.  `text` is what could be read, with a `?` for
+    /// each letter that couldn't, and `columns` are the columns of the grid where each of
+    /// those letters starts.
+    Unrecognised { text: String, columns: Vec<i64> },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Blank => write!(f, "There are no letters to read"),
+            Self::WrongHeight(height) => write!(
+                f,
+                "Letters should be {} or {} pixels high, not {}",
+                SMALL.height, LARGE.height, height
+            ),
+            Self::Unrecognised { text, columns } => {
+                let columns: Vec<String> = columns.iter().map(i64::to_string).collect();
+                write!(
+                    f,
+                    "Couldn't read the letters starting in columns {} of {}",
+                    columns.join(", "),
+                    text
+                )
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+impl Grid<bool> {
+    /// Reads the capital letters drawn in the grid, where `true` is a lit pixel.  Both of
+    /// the fonts that puzzles use are understood: the usual one, where letters are 6 pixels
+    /// high, and the bigger one where they're 10.  It doesn't matter where in the grid the
+    /// letters are, so long as they're all on one line.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's nothing in the grid, if what's there isn't the right
+    /// height for either font, or if any of the letters isn't one we know.
+    pub fn read_letters(&self) -> Result<String, OcrError> {
+        let lit: Vec<Point> = self
+            .iter()
+            .filter(|(_, &lit)| lit)
+            .map(|(point, _)| point)
+            .collect();
+        let first = lit
+            .iter()
+            .map(|point| point.x)
+            .min()
+            .ok_or(OcrError::Blank)?;
+        let last = lit.iter().map(|point| point.x).max().unwrap_or(first);
+        let top = lit.iter().map(|point| point.y).min().unwrap_or(0);
+        let height = lit.iter().map(|point| point.y).max().unwrap_or(top) - top + 1;
+        let font = [&SMALL, &LARGE]
+            .iter()
+            .copied()
+            .find(|font| font.height as i64 == height)
+            .ok_or(OcrError::WrongHeight(height as usize))?;
+
+        let lit_at = |x: i64, y: i64| self.get(Point::new(x, top + y)) == Some(&true);
+        let column_is_blank = |x: i64| (0..height).all(|y| !lit_at(x, y));
+        let (mut text, mut columns) = (String::new(), Vec::new());
+        let mut x = first;
+        while x <= last {
+            if column_is_blank(x) {
+                x += 1;
+                continue;
+            }
+
+            // A letter has to match pixel for pixel, and be followed by a gap if it's
+            // narrower than the space it gets.  Any blank columns it starts with are ones
+            // we've already stepped past.
+            let letter = font.letters.iter().find_map(|&(letter, rows)| {
+                let width = rows[0].len() as i64;
+                let blank = (0..rows[0].len())
+                    .take_while(|&dx| rows.iter().all(|row| row.as_bytes()[dx] == b'.'))
+                    .count();
+                let start = x - blank as i64;
+                let drawn = rows.iter().enumerate().all(|(y, row)| {
+                    row.bytes()
+                        .enumerate()
+                        .all(|(dx, pixel)| lit_at(start + dx as i64, y as i64) == (pixel == b'#'))
+                });
+                let gap = (width..font.pitch as i64).all(|dx| column_is_blank(start + dx));
+                if drawn && gap {
+                    Some((letter, start + width))
+                } else {
+                    None
+                }
+            });
+            match letter {
+                Some((letter, end)) => {
+                    text.push(letter);
+                    x = end;
+                }
+                None => {
+                    text.push('?');
+                    columns.push(x);
+                    while x <= last && !column_is_blank(x) {
+                        x += 1;
+                    }
+                }
+            }
+        }
+
+        if columns.is_empty() {
+            Ok(text)
+        } else {
+            Err(OcrError::Unrecognised { text, columns })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Draws text in a font, with `#` for lit pixels, and with `?` as a letter we don't know.
+    fn draw(font: &Font, text: &str) -> Vec<String> {
+        let mut rows = vec![String::new(); font.height];
+        for c in text.chars() {
+            for (y, row) in rows.iter_mut().enumerate() {
+                let drawn = font
+                    .letters
+                    .iter()
+                    .find(|&&(letter, _)| letter == c)
+                    .map_or_else(
+                        || "#".repeat(font.pitch - 1),
+                        |(_, glyph)| glyph[y].to_string(),
+                    );
+                row.push_str(&format!("{:.<width$}", drawn, width = font.pitch));
+            }
+        }
+        rows
+    }
+
+    fn pixels(rows: &[String]) -> Grid<bool> {
+        Grid::parse(&rows.join("\n")).unwrap().map(|&c| c == '#')
+    }
+
+    #[test]
+    fn small_letters() {
+        let alphabet: String = SMALL.letters.iter().map(|&(letter, _)| letter).collect();
+        assert_eq!(
+            pixels(&draw(&SMALL, &alphabet)).read_letters(),
+            Ok(alphabet)
+        );
+
+        // Y is the full width of a letter, so runs straight into the next one, and I starts
+        // with a blank column, so only its last three columns tell us it's there.
+        assert_eq!(pixels(&draw(&SMALL, "YAY")).read_letters().unwrap(), "YAY");
+        assert_eq!(
+            pixels(&draw(&SMALL, "IHIYI")).read_letters().unwrap(),
+            "IHIYI"
+        );
+    }
+
+    #[test]
+    fn large_letters() {
+        let alphabet: String = LARGE.letters.iter().map(|&(letter, _)| letter).collect();
+        assert_eq!(
+            pixels(&draw(&LARGE, &alphabet)).read_letters(),
+            Ok(alphabet)
+        );
+    }
+
+    #[test]
+    fn anywhere_in_the_grid() {
+        let mut rows = vec![".".repeat(30); 2];
+        rows.extend(
+            draw(&SMALL, "HELLO")
+                .iter()
+                .map(|row| format!("...{}..", row)),
+        );
+        rows.push(".".repeat(30));
+        assert_eq!(pixels(&rows).read_letters().unwrap(), "HELLO");
+    }
+
+    #[test]
+    fn unreadable() {
+        assert_eq!(Grid::new(5, 6, false).read_letters(), Err(OcrError::Blank));
+        let mut short = draw(&SMALL, "AB");
+        short.pop();
+        assert_eq!(pixels(&short).read_letters(), Err(OcrError::WrongHeight(5)));
+
+        let error = pixels(&draw(&SMALL, "A?C?")).read_letters().unwrap_err();
+        assert_eq!(
+            error,
+            OcrError::Unrecognised {
+                text: "A?C?".to_string(),
+                columns: vec![5, 15]
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Couldn't read the letters starting in columns 5, 15 of A?C?"
+        );
+    }
+}
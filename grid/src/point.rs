@@ -0,0 +1,176 @@
+//! Points on a grid.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::Direction;
+
+/// A point on a grid, or the difference between two of them.
+///
+/// `x` increases to the right and `y` increases downwards, so that `(0, 0)` is the top left
+/// of a picture of the grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    /// The point at `(0, 0)`.
+    pub const ORIGIN: Self = Self { x: 0, y: 0 };
+
+    #[must_use]
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// How far apart two points are, moving only horizontally and vertically.
+    #[must_use]
+    pub fn manhattan_distance(self, other: Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The point one step away in the given direction.
+    #[must_use]
+    pub fn step(self, dir: Direction) -> Self {
+        self + dir.offset()
+    }
+
+    /// The four points one step away, clockwise from the one above.
+    pub fn neighbours(self) -> impl Iterator<Item = Self> {
+        Direction::ALL.iter().map(move |&dir| self.step(dir))
+    }
+
+    /// The eight points surrounding this one, diagonals included, clockwise from the one
+    /// above.
+    pub fn all_neighbours(self) -> impl Iterator<Item = Self> {
+        const OFFSETS: [(i64, i64); 8] = [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ];
+        OFFSETS.iter().map(move |&(x, y)| self + Self::new(x, y))
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Self;
+
+    fn mul(self, factor: i64) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+// Adding a direction takes one step that way.
+impl Add<Direction> for Point {
+    type Output = Self;
+
+    fn add(self, dir: Direction) -> Self {
+        self.step(dir)
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, dir: Direction) {
+        *self = self.step(dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut point = Point::new(3, -4);
+        assert_eq!(point + Point::new(1, 1), Point::new(4, -3));
+        assert_eq!(point - Point::new(1, 1), Point::new(2, -5));
+        assert_eq!(-point, Point::new(-3, 4));
+        assert_eq!(point * 3, Point::new(9, -12));
+        point += Direction::Up;
+        assert_eq!(point, Point::new(3, -5));
+        point -= Point::new(3, -5);
+        assert_eq!(point, Point::ORIGIN);
+        assert_eq!(Point::from((2, 7)).to_string(), "2,7");
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(Point::new(3, -4).manhattan_distance(Point::ORIGIN), 7);
+        assert_eq!(Point::new(-1, 5).manhattan_distance(Point::new(2, 1)), 7);
+    }
+
+    #[test]
+    fn neighbours() {
+        let point = Point::new(5, 5);
+        let neighbours: Vec<Point> = point.neighbours().collect();
+        assert_eq!(
+            neighbours,
+            vec![
+                Point::new(5, 4),
+                Point::new(6, 5),
+                Point::new(5, 6),
+                Point::new(4, 5)
+            ]
+        );
+        let all: Vec<Point> = point.all_neighbours().collect();
+        assert_eq!(all.len(), 8);
+        assert!(neighbours.iter().all(|neighbour| all.contains(neighbour)));
+        assert!(all
+            .iter()
+            .all(|neighbour| neighbour.manhattan_distance(point) <= 2 && *neighbour != point));
+    }
+}
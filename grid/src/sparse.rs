@@ -0,0 +1,174 @@
+//! Grids that only have things at some of their points.
+
+use std::collections::hash_map::{Entry, HashMap};
+use std::iter::FromIterator;
+
+use crate::{Bounds, Grid, Point};
+
+/// A grid with things at only some of its points, which can be anywhere at all - the grid
+/// grows in every direction to fit them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> SparseGrid<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+
+    /// How many points have something at them.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Puts something at a point, and returns whatever was there before.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    /// The point's entry, for filling in if there's nothing there yet.
+    pub fn entry(&mut self, point: Point) -> Entry<'_, Point, T> {
+        self.cells.entry(point)
+    }
+
+    /// Every point with something at it, with what's there, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    /// The smallest rectangle with everything in it, or `None` if there's nothing.
+    #[must_use]
+    pub fn bounds(&self) -> Option<Bounds> {
+        Bounds::around(self.cells.keys().copied())
+    }
+
+    /// Turns this into a [`Grid`] just big enough for everything, with `fill` wherever
+    /// there's nothing.  The grid's top left corner is at the origin, so everything is
+    /// moved to fit; what's returned alongside the grid is how far it moved.
+    ///
+    /// [`Grid`]: struct.Grid.html
+    #[must_use]
+    pub fn to_grid(&self, fill: T) -> (Grid<T>, Point)
+    where
+        T: Clone,
+    {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return (Grid::new(0, 0, fill), Point::ORIGIN),
+        };
+        let offset = -bounds.min;
+        let mut grid = Grid::new(bounds.width(), bounds.height(), fill);
+        for (&point, value) in &self.cells {
+            grid[point + offset] = value.clone();
+        }
+        (grid, offset)
+    }
+
+    /// Draws everything in the grid, one string per row, with `f` saying how to draw each
+    /// point - including the points with nothing at them.
+    pub fn render(&self, mut f: impl FnMut(Option<&T>) -> char) -> Vec<String> {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return Vec::new(),
+        };
+        (bounds.min.y..=bounds.max.y)
+            .map(|y| {
+                (bounds.min.x..=bounds.max.x)
+                    .map(|x| f(self.get(Point::new(x, y))))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trail() -> SparseGrid<char> {
+        vec![
+            (Point::new(-1, -1), 'a'),
+            (Point::new(0, 0), 'b'),
+            (Point::new(2, 0), 'c'),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn contents() {
+        let mut grid = trail();
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.get(Point::new(2, 0)), Some(&'c'));
+        assert_eq!(grid.get(Point::new(1, 0)), None);
+        assert_eq!(grid.insert(Point::new(2, 0), 'd'), Some('c'));
+        *grid.entry(Point::new(5, 5)).or_insert('e') = 'f';
+        assert_eq!(grid.get(Point::new(5, 5)), Some(&'f'));
+        assert_eq!(grid.remove(Point::new(5, 5)), Some('f'));
+        assert!(!grid.contains(Point::new(5, 5)));
+        assert!(SparseGrid::<char>::new().is_empty());
+    }
+
+    #[test]
+    fn bounds() {
+        assert_eq!(
+            trail().bounds(),
+            Some(Bounds::new(Point::new(-1, -1), Point::new(2, 0)))
+        );
+        assert_eq!(SparseGrid::<char>::new().bounds(), None);
+    }
+
+    #[test]
+    fn drawing() {
+        let grid = trail();
+        assert_eq!(grid.render(|c| *c.unwrap_or(&'.')), vec!["a...", ".b.c"]);
+        assert!(SparseGrid::<char>::new().render(|_| '#').is_empty());
+
+        let (dense, offset) = grid.to_grid(' ');
+        assert_eq!(offset, Point::new(1, 1));
+        assert_eq!(dense.render(|&c| c), vec!["a   ", " b c"]);
+        assert_eq!(dense[Point::ORIGIN + offset], 'b');
+    }
+}
@@ -0,0 +1,560 @@
+//! A data-driven conformance suite for Intcode implementations.
+//!
+//! The Advent of Code 2019 puzzle texts for days 2, 5 and 9 contain a collection of small
+//! reference programs with known results.  They're all gathered up in [`TEST_CASES`],
+//! alongside a handful of extra programs that fill in the gaps so that every opcode is
+//! exercised in every parameter mode it supports - including relative-mode writes, which
+//! none of the puzzle examples cover.
+//!
+//! The suite is run against an implementation of the [`Backend`] trait.  This crate provides
+//! backends for each of its own computer types, but if you're writing your own Intcode
+//! computer, implement [`Backend`] for it and pass it to [`run_suite`] to check that it
+//! behaves the same as ours.
+//!
+//! ```
+//! use intcode::conformance::{self, SynchronousBackend};
+//!
+//! let report = conformance::run_suite(&SynchronousBackend);
+//! assert!(report.is_success(), "{}", report);
+//! ```
+//!
+//! [`TEST_CASES`]: ./constant.TEST_CASES.html
+//! [`Backend`]: ./trait.Backend.html
+//! [`run_suite`]: ./fn.run_suite.html
+
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+
+use futures::channel::mpsc::unbounded;
+use futures::executor::block_on;
+use futures::StreamExt;
+
+use crate::{
+    AsyncComputeNotification, ChannelIOComputer, StreamingIOComputer, SynchronousComputeResult,
+    SynchronousComputer,
+};
+
+/// What a test case expects to be true once its program has run to completion.
+#[derive(Clone, Copy, Debug)]
+pub enum Expectation {
+    /// The program should have produced exactly these outputs, in this order.
+    Outputs(&'static [i64]),
+
+    /// The program's memory should start with these values.  (Programs are free to use
+    /// memory beyond their own length, so anything after this isn't checked.)
+    Memory(&'static [i64]),
+}
+
+/// A single reference program, together with the inputs to give it and the result it
+/// should produce.
+#[derive(Clone, Copy, Debug)]
+pub struct TestCase {
+    /// A description of the test case, including where it came from.
+    pub name: &'static str,
+
+    /// The Intcode program to run.
+    pub program: &'static [i64],
+
+    /// The inputs to provide to the program.  The program must not need any more than this.
+    pub inputs: &'static [i64],
+
+    /// The result that the program should produce.
+    pub expected: Expectation,
+}
+
+/// The result of running a program to completion on a [`Backend`].
+///
+/// [`Backend`]: ./trait.Backend.html
+pub struct Execution {
+    /// All the outputs that the program produced, in order.
+    pub outputs: Vec<i64>,
+
+    /// The content of the computer's memory once the program ended.  Backends that can't
+    /// provide this should return `None`, in which case test cases that check memory will
+    /// be reported as skipped rather than failed.
+    pub memory: Option<Vec<i64>>,
+}
+
+/// An Intcode implementation that can be validated by the conformance suite.
+pub trait Backend {
+    /// Runs `program` to completion, providing it with `inputs`.
+    ///
+    /// Implementations may panic if anything goes wrong - the suite catches the panic and
+    /// reports it as a failure of the test case concerned.
+    fn execute(&self, program: &[i64], inputs: &[i64]) -> Execution;
+}
+
+/// A test case that didn't produce the expected result.
+#[derive(Debug)]
+pub struct Failure {
+    /// The name of the test case that failed.
+    pub name: &'static str,
+
+    /// A description of what went wrong.
+    pub reason: String,
+}
+
+/// The outcome of running the whole conformance suite against a [`Backend`].
+///
+/// [`Backend`]: ./trait.Backend.html
+#[derive(Debug, Default)]
+pub struct ConformanceReport {
+    /// The names of the test cases that passed.
+    pub passed: Vec<&'static str>,
+
+    /// The names of the test cases that couldn't be checked, because they inspect memory
+    /// and the backend doesn't expose it.
+    pub skipped: Vec<&'static str>,
+
+    /// The test cases that failed, and why.
+    pub failures: Vec<Failure>,
+}
+
+impl ConformanceReport {
+    /// Returns whether every test case that could be checked passed.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} skipped",
+            self.passed.len(),
+            self.failures.len(),
+            self.skipped.len()
+        )?;
+        for failure in &self.failures {
+            write!(f, "\n  {}: {}", failure.name, failure.reason)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs every test case in [`TEST_CASES`] against `backend`.
+///
+/// [`TEST_CASES`]: ./constant.TEST_CASES.html
+pub fn run_suite(backend: &dyn Backend) -> ConformanceReport {
+    let mut report = ConformanceReport::default();
+
+    for case in TEST_CASES {
+        let execution = match panic::catch_unwind(AssertUnwindSafe(|| {
+            backend.execute(case.program, case.inputs)
+        })) {
+            Ok(execution) => execution,
+            Err(panic) => {
+                report.failures.push(Failure {
+                    name: case.name,
+                    reason: format!("backend panicked: {}", panic_message(&*panic)),
+                });
+                continue;
+            }
+        };
+
+        match case.expected {
+            Expectation::Outputs(expected) if execution.outputs == expected => {
+                report.passed.push(case.name);
+            }
+            Expectation::Outputs(expected) => report.failures.push(Failure {
+                name: case.name,
+                reason: format!(
+                    "expected outputs {:?}, got {:?}",
+                    expected, execution.outputs
+                ),
+            }),
+            Expectation::Memory(expected) => match execution.memory {
+                None => report.skipped.push(case.name),
+                Some(memory) if memory.starts_with(expected) => report.passed.push(case.name),
+                Some(memory) => report.failures.push(Failure {
+                    name: case.name,
+                    reason: format!("expected memory {:?}, got {:?}", expected, memory),
+                }),
+            },
+        }
+    }
+
+    report
+}
+
+// Panics usually carry either a `&str` or a `String`; anything else we can't describe.
+fn panic_message(panic: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+/// Runs test cases on a [`SynchronousComputer`](../struct.SynchronousComputer.html).
+pub struct SynchronousBackend;
+
+impl Backend for SynchronousBackend {
+    fn execute(&self, program: &[i64], inputs: &[i64]) -> Execution {
+        let mut computer = SynchronousComputer::new(program);
+        let output = computer.run(inputs);
+        assert!(
+            output.result == SynchronousComputeResult::ProgramEnded,
+            "Program required more inputs than were provided"
+        );
+        Execution {
+            outputs: output.outputs,
+            memory: Some(computer.processor.memory.clone()),
+        }
+    }
+}
+
+/// Runs test cases on a [`ChannelIOComputer`](../struct.ChannelIOComputer.html), on its own
+/// thread.
+pub struct ChannelIOBackend;
+
+impl Backend for ChannelIOBackend {
+    fn execute(&self, program: &[i64], inputs: &[i64]) -> Execution {
+        let (in_sender, in_receiver) = mpsc::channel();
+        let (out_sender, out_receiver) = mpsc::channel();
+        for input in inputs {
+            in_sender.send(*input).unwrap();
+        }
+
+        // Dropping our half of the input channel means that a program that wants more inputs
+        // than it was given panics rather than blocking forever.
+        drop(in_sender);
+
+        let program = program.to_vec();
+        let memory = std::thread::spawn(move || {
+            let mut computer = ChannelIOComputer::new(&program, in_receiver, out_sender);
+            computer.run();
+            computer.processor.memory
+        })
+        .join()
+        .unwrap_or_else(|panic| panic::resume_unwind(panic));
+
+        Execution {
+            outputs: out_receiver.try_iter().collect(),
+            memory: Some(memory),
+        }
+    }
+}
+
+/// Runs test cases on a [`StreamingIOComputer`](../struct.StreamingIOComputer.html), on a
+/// simple single-threaded executor.
+pub struct StreamingIOBackend;
+
+impl Backend for StreamingIOBackend {
+    fn execute(&self, program: &[i64], inputs: &[i64]) -> Execution {
+        let (in_sender, in_receiver) = unbounded();
+        let (out_sender, mut out_receiver) = unbounded();
+        for input in inputs {
+            in_sender.unbounded_send(*input).unwrap();
+        }
+        drop(in_sender);
+
+        let mut computer = StreamingIOComputer::new(program, in_receiver, out_sender);
+        block_on(computer.run());
+
+        // The computer still holds the send half, so close the channel to let us read to
+        // the end of what it sent.
+        out_receiver.close();
+        let outputs = block_on(out_receiver.collect::<Vec<_>>())
+            .into_iter()
+            .filter_map(|notification| match notification {
+                AsyncComputeNotification::Output(output) => Some(output),
+                _ => None,
+            })
+            .collect();
+
+        Execution {
+            outputs,
+            memory: Some(computer.processor.memory),
+        }
+    }
+}
+
+const DAY_9_QUINE: &[i64] = &[
+    109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+];
+
+const DAY_5_COMPARE_TO_8: &[i64] = &[
+    3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+    1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20, 1105,
+    1, 46, 98, 99,
+];
+
+/// The complete set of reference programs that make up the conformance suite.
+pub const TEST_CASES: &[TestCase] = &[
+    // --- Day 2: addition, multiplication and position mode ---
+    TestCase {
+        name: "day 2: worked example",
+        program: &[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50],
+        inputs: &[],
+        expected: Expectation::Memory(&[3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50]),
+    },
+    TestCase {
+        name: "day 2: 1 + 1 = 2",
+        program: &[1, 0, 0, 0, 99],
+        inputs: &[],
+        expected: Expectation::Memory(&[2, 0, 0, 0, 99]),
+    },
+    TestCase {
+        name: "day 2: 3 * 2 = 6",
+        program: &[2, 3, 0, 3, 99],
+        inputs: &[],
+        expected: Expectation::Memory(&[2, 3, 0, 6, 99]),
+    },
+    TestCase {
+        name: "day 2: 99 * 99 = 9801",
+        program: &[2, 4, 4, 5, 99, 0],
+        inputs: &[],
+        expected: Expectation::Memory(&[2, 4, 4, 5, 99, 9801]),
+    },
+    TestCase {
+        name: "day 2: self-modifying program",
+        program: &[1, 1, 1, 4, 99, 5, 6, 0, 99],
+        inputs: &[],
+        expected: Expectation::Memory(&[30, 1, 1, 4, 2, 5, 6, 0, 99]),
+    },
+    // --- Day 5: input, output, immediate mode, comparisons and jumps ---
+    TestCase {
+        name: "day 5: echo input",
+        program: &[3, 0, 4, 0, 99],
+        inputs: &[42],
+        expected: Expectation::Outputs(&[42]),
+    },
+    TestCase {
+        name: "day 5: immediate mode multiply",
+        program: &[1002, 4, 3, 4, 33],
+        inputs: &[],
+        expected: Expectation::Memory(&[1002, 4, 3, 4, 99]),
+    },
+    TestCase {
+        name: "day 5: negative immediate",
+        program: &[1101, 100, -1, 4, 0],
+        inputs: &[],
+        expected: Expectation::Memory(&[1101, 100, -1, 4, 99]),
+    },
+    TestCase {
+        name: "day 5: equal to 8 (position mode), input 8",
+        program: &[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8],
+        inputs: &[8],
+        expected: Expectation::Outputs(&[1]),
+    },
+    TestCase {
+        name: "day 5: equal to 8 (position mode), input 7",
+        program: &[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8],
+        inputs: &[7],
+        expected: Expectation::Outputs(&[0]),
+    },
+    TestCase {
+        name: "day 5: less than 8 (position mode), input 7",
+        program: &[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8],
+        inputs: &[7],
+        expected: Expectation::Outputs(&[1]),
+    },
+    TestCase {
+        name: "day 5: less than 8 (position mode), input 8",
+        program: &[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8],
+        inputs: &[8],
+        expected: Expectation::Outputs(&[0]),
+    },
+    TestCase {
+        name: "day 5: equal to 8 (immediate mode), input 8",
+        program: &[3, 3, 1108, -1, 8, 3, 4, 3, 99],
+        inputs: &[8],
+        expected: Expectation::Outputs(&[1]),
+    },
+    TestCase {
+        name: "day 5: equal to 8 (immediate mode), input 9",
+        program: &[3, 3, 1108, -1, 8, 3, 4, 3, 99],
+        inputs: &[9],
+        expected: Expectation::Outputs(&[0]),
+    },
+    TestCase {
+        name: "day 5: less than 8 (immediate mode), input -3",
+        program: &[3, 3, 1107, -1, 8, 3, 4, 3, 99],
+        inputs: &[-3],
+        expected: Expectation::Outputs(&[1]),
+    },
+    TestCase {
+        name: "day 5: less than 8 (immediate mode), input 8",
+        program: &[3, 3, 1107, -1, 8, 3, 4, 3, 99],
+        inputs: &[8],
+        expected: Expectation::Outputs(&[0]),
+    },
+    TestCase {
+        name: "day 5: jump test (position mode), input 0",
+        program: &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
+        inputs: &[0],
+        expected: Expectation::Outputs(&[0]),
+    },
+    TestCase {
+        name: "day 5: jump test (position mode), input 5",
+        program: &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
+        inputs: &[5],
+        expected: Expectation::Outputs(&[1]),
+    },
+    TestCase {
+        name: "day 5: jump test (immediate mode), input 0",
+        program: &[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1],
+        inputs: &[0],
+        expected: Expectation::Outputs(&[0]),
+    },
+    TestCase {
+        name: "day 5: jump test (immediate mode), input 5",
+        program: &[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1],
+        inputs: &[5],
+        expected: Expectation::Outputs(&[1]),
+    },
+    TestCase {
+        name: "day 5: compare to 8, input 7",
+        program: DAY_5_COMPARE_TO_8,
+        inputs: &[7],
+        expected: Expectation::Outputs(&[999]),
+    },
+    TestCase {
+        name: "day 5: compare to 8, input 8",
+        program: DAY_5_COMPARE_TO_8,
+        inputs: &[8],
+        expected: Expectation::Outputs(&[1000]),
+    },
+    TestCase {
+        name: "day 5: compare to 8, input 9",
+        program: DAY_5_COMPARE_TO_8,
+        inputs: &[9],
+        expected: Expectation::Outputs(&[1001]),
+    },
+    // --- Day 9: relative mode, large numbers and extra memory ---
+    TestCase {
+        name: "day 9: quine",
+        program: DAY_9_QUINE,
+        inputs: &[],
+        expected: Expectation::Outputs(DAY_9_QUINE),
+    },
+    TestCase {
+        name: "day 9: 16-digit output",
+        program: &[1102, 34_915_192, 34_915_192, 7, 4, 7, 99, 0],
+        inputs: &[],
+        expected: Expectation::Outputs(&[1_219_070_632_396_864]),
+    },
+    TestCase {
+        name: "day 9: large number",
+        program: &[104, 1_125_899_906_842_624, 99],
+        inputs: &[],
+        expected: Expectation::Outputs(&[1_125_899_906_842_624]),
+    },
+    // --- Extra coverage: modes that the puzzle examples don't exercise ---
+    TestCase {
+        name: "extra: read beyond end of program",
+        program: &[4, 50, 99],
+        inputs: &[],
+        expected: Expectation::Outputs(&[0]),
+    },
+    TestCase {
+        name: "extra: write beyond end of program",
+        program: &[1101, 5, 6, 100, 4, 100, 99],
+        inputs: &[],
+        expected: Expectation::Outputs(&[11]),
+    },
+    TestCase {
+        name: "extra: add with relative-mode write",
+        program: &[109, 10, 21101, 3, 4, 0, 204, 0, 99],
+        inputs: &[],
+        expected: Expectation::Outputs(&[7]),
+    },
+    TestCase {
+        name: "extra: multiply with relative-mode reads and write",
+        program: &[109, 8, 22202, 0, 1, 2, 204, 2, 99, 6, 7, 0],
+        inputs: &[],
+        expected: Expectation::Outputs(&[594]),
+    },
+    TestCase {
+        name: "extra: input with relative-mode write",
+        program: &[109, 7, 203, 0, 204, 0, 99],
+        inputs: &[42],
+        expected: Expectation::Outputs(&[42]),
+    },
+    TestCase {
+        name: "extra: jump-if-true with relative-mode parameters",
+        program: &[109, 6, 2205, 0, 1, 99, 1, 8, 104, 7, 99],
+        inputs: &[],
+        expected: Expectation::Outputs(&[7]),
+    },
+    TestCase {
+        name: "extra: jump-if-false with relative-mode parameters",
+        program: &[109, 6, 2206, 0, 1, 99, 0, 8, 104, 7, 99],
+        inputs: &[],
+        expected: Expectation::Outputs(&[7]),
+    },
+    TestCase {
+        name: "extra: less-than with relative-mode parameters",
+        program: &[109, 7, 22207, 0, 1, 2, 204, 2, 99],
+        inputs: &[],
+        expected: Expectation::Outputs(&[1]),
+    },
+    TestCase {
+        name: "extra: equals with relative-mode parameters",
+        program: &[109, 9, 22208, 0, 1, 2, 204, 2, 99, 5, 5, 0],
+        inputs: &[],
+        expected: Expectation::Outputs(&[1]),
+    },
+    TestCase {
+        name: "extra: relative base offset in position mode",
+        program: &[9, 5, 204, -2, 99, 3],
+        inputs: &[],
+        expected: Expectation::Outputs(&[5]),
+    },
+    TestCase {
+        name: "extra: relative base offset in relative mode",
+        program: &[109, 4, 209, -1, 204, -3, 99],
+        inputs: &[],
+        expected: Expectation::Outputs(&[109]),
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_conforms(backend: &dyn Backend) {
+        let report = run_suite(backend);
+        assert!(report.is_success(), "{}", report);
+        assert!(report.skipped.is_empty());
+        assert_eq!(report.passed.len(), TEST_CASES.len());
+    }
+
+    #[test]
+    fn synchronous_computer_conforms() {
+        assert_conforms(&SynchronousBackend);
+    }
+
+    #[test]
+    fn channel_io_computer_conforms() {
+        assert_conforms(&ChannelIOBackend);
+    }
+
+    #[test]
+    fn streaming_io_computer_conforms() {
+        assert_conforms(&StreamingIOBackend);
+    }
+
+    #[test]
+    fn broken_backend_fails() {
+        struct NoOutputs;
+        impl Backend for NoOutputs {
+            fn execute(&self, _program: &[i64], _inputs: &[i64]) -> Execution {
+                Execution {
+                    outputs: Vec::new(),
+                    memory: None,
+                }
+            }
+        }
+
+        let report = run_suite(&NoOutputs);
+        assert!(!report.is_success());
+        assert!(!report.skipped.is_empty());
+    }
+}
@@ -12,9 +12,11 @@
 //! for input to be sent on its inbound channel if there isn't any waiting when the
 //! Intcode program requests some, with outputs being sent back on its outbound channel.
 //!
-//! [`StreamingIOComputer`] is designed to run in a Tokio reactor, and communicates with
-//! your code using Tokio MPSC streams.  It works with async code, and will run
-//! continuously but lazily as required by your `await` calls.  `await` will block
+//! [`StreamingIOComputer`] is designed to run as a future, and communicates with your
+//! code using async streams and sinks.  It only depends on the traits from the `futures`
+//! crate, so works with whichever executor you like (Tokio, async-std, or the simple one
+//! in `futures` itself), and will run continuously but lazily as required by your
+//! `await` calls.  `await` will block
 //! forever if the computer can't get enough inputs, so make sure that you've either
 //! sent enough on the inbound stream before `await`ing, or you've got appropriate
 //! futures chaining so that enough inputs will be sent during execution.
@@ -23,12 +25,25 @@
 //! provide it a set of inputs when you execute it, if it needs more after consuming
 //! those, it will return, and you'll need to call it again with further input(s).
 //!
+//! If you've got lots of computers that need to talk to each other, a [`Scheduler`] will
+//! run them all on a single thread, taking turns, with their inputs and outputs wired
+//! together by named pipes.  It'll also tell you if they've all ended up waiting for each
+//! other.
+//!
 //! Helper functions assist with loading programs from file, and executing them via
 //! [`ChannelIOComputer`]s or [`StreamingIOComputer`]s.
 //!
+//! The [`conformance`] module contains the reference programs from the puzzle texts, and
+//! can be used to check that all of the above - or your own Intcode implementation -
+//! execute them correctly.
+//!
 //! Typical usage might look like this:
 //!
-//! ```
+//! ```no_run
+//! use std::process;
+//! use futures::channel::mpsc::unbounded;
+//! use futures::{SinkExt, StreamExt};
+//!
 //! let program = intcode::load_program("path/to/input.txt").unwrap_or_else(|err| {
 //!     println!("Could not load input file!\n{:?}", err);
 //!     process::exit(1);
@@ -38,12 +53,7 @@
 //! let (in_send, in_recv) = std::sync::mpsc::channel();
 //! let (out_send, out_recv) = std::sync::mpsc::channel();
 //! let mut channel_comp = intcode::ChannelIOComputer::new(&program, in_recv, out_send);
-//! std::thread::spawn(move || {
-//!     channel_comp.run().unwrap_or_else(|e| {
-//!         println!("Computer failed: {}", e);
-//!         process::exit(1);
-//!     });
-//! });
+//! std::thread::spawn(move || channel_comp.run());
 //!
 //! // The computer is now executing in parallel, and you can communicate with it
 //! // via its channels.
@@ -51,17 +61,24 @@
 //! println!("{}", out_recv.recv().unwrap());
 //!
 //! // StreamingIOComputer
-//! let (in_send, in_recv) = tokio::sync::mpsc::unbounded_channel();
-//! let (out_send, out_recv) = tokio::sync::mpsc::unbounded_channel();
-//! tokio::spawn(async move {
-//!     intcode::StreamingIOComputer::new(&program, in_recv, out_send).run().await;
+//! let (mut in_send, in_recv) = unbounded();
+//! let (out_send, mut out_recv) = unbounded();
+//! let mut streaming_comp = intcode::StreamingIOComputer::new(&program, in_recv, out_send);
+//! futures::executor::block_on(async {
+//!     // Run the computer alongside the code that talks to it via its streams.
+//!     let talk = async {
+//!         in_send.send(1).await.unwrap();
+//!         while let Some(notification) = out_recv.next().await {
+//!             match notification {
+//!                 intcode::AsyncComputeNotification::Output(output) => println!("{}", output),
+//!                 intcode::AsyncComputeNotification::InputRequired => (),
+//!                 intcode::AsyncComputeNotification::ProgramEnded => break,
+//!             }
+//!         }
+//!     };
+//!     futures::join!(streaming_comp.run(), talk);
 //! });
 //!
-//! // The computer is now executing on the Tokio runtime, and you can communicate
-//! // with it via its streams.
-//! in_send.send(1).unwrap();
-//! println!("{}", out_recv.recv().await.unwrap());
-//!
 //! // SynchronousComputer
 //! let mut sync_comp = intcode::SynchronousComputer::new(&program);
 //! let output = sync_comp.run(&[1]);
@@ -71,6 +88,8 @@
 //! [`ChannelIOComputer`]: ./struct.ChannelIOComputer.html
 //! [`StreamingIOComputer`]: ./struct.StreamingIOComputer.html
 //! [`SynchronousComputer`]: ./struct.SynchronousComputer.html
+//! [`Scheduler`]: ./struct.Scheduler.html
+//! [`conformance`]: ./conformance/index.html
 //!
 
 #![crate_name = "intcode"]
@@ -81,15 +100,26 @@
 #![allow(clippy::cast_possible_wrap)]
 
 use std::collections::VecDeque;
+use std::fmt::Debug;
 use std::fs::File;
 use std::io;
 use std::io::Read;
 use std::iter::FromIterator;
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::mpsc::{
+    self, Receiver, RecvTimeoutError, SendError, Sender, SyncSender, TryRecvError, TrySendError,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::channel::mpsc::unbounded;
+use futures::stream::{self, Stream, StreamExt};
+use futures::{Sink, SinkExt};
 
-extern crate tokio;
-use tokio::stream::StreamExt;
-use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+pub mod conformance;
+mod scheduler;
+
+pub use scheduler::{BlockedMachine, DeadlockReport, RunOutcome, Scheduler};
 
 /// The result of running a `SynchronousComputer` as far as possible.
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -116,6 +146,7 @@ pub struct SynchronousComputeOutput {
 pub struct SynchronousComputer {
     processor: Processor,
     last_result: Option<SynchronousComputeResult>,
+    pending_output: Option<i64>,
 }
 
 impl SynchronousComputer {
@@ -124,13 +155,41 @@ impl SynchronousComputer {
     /// that the program can refer to.
     #[must_use]
     pub fn new(program: &[i64]) -> Self {
-        let processor = Processor::new(program);
+        let processor = Processor::new(program.to_vec());
         Self {
             processor,
             last_result: None,
+            pending_output: None,
         }
     }
 
+    /// Construct a computer in the state captured by `snapshot`.  If the snapshot includes
+    /// an output that hadn't been delivered yet, it'll be the first output from `run()`.
+    #[must_use]
+    pub fn from_snapshot(snapshot: &Snapshot) -> Self {
+        let last_result = if snapshot.halted {
+            Some(SynchronousComputeResult::ProgramEnded)
+        } else if snapshot.input_location.is_some() {
+            Some(SynchronousComputeResult::InputRequired)
+        } else {
+            None
+        };
+        Self {
+            processor: Processor::from_snapshot(snapshot),
+            last_result,
+            pending_output: snapshot.pending_output,
+        }
+    }
+
+    /// Captures the computer's current state.
+    #[must_use]
+    pub fn snapshot(&self) -> Snapshot {
+        self.processor.snapshot(
+            self.pending_output,
+            self.last_result == Some(SynchronousComputeResult::ProgramEnded),
+        )
+    }
+
     /// Executes the program in the computer's memory as far as possible, returning either when
     /// the program completes or if an input is required when all the provided inputs have been
     /// used up.
@@ -141,7 +200,7 @@ impl SynchronousComputer {
     /// program is invalid, or that invalid inputs were provided to it.
     pub fn run(&mut self, inputs: &[i64]) -> SynchronousComputeOutput {
         let mut inputs = VecDeque::from_iter(inputs);
-        let mut outputs = Vec::new();
+        let mut outputs: Vec<i64> = self.pending_output.take().into_iter().collect();
 
         match self.last_result {
             Some(SynchronousComputeResult::ProgramEnded) => {
@@ -206,30 +265,35 @@ pub enum AsyncComputeNotification {
 }
 
 /// A virtual computer whose memory contains an Intcode program and which can execute
-/// said program, where communication with the computer is via Tokio MPSC streams, and
-/// the computer is intended to be executed on a Tokio reactor.
-pub struct StreamingIOComputer {
+/// said program, where communication with the computer is via async streams, and the
+/// computer is intended to be executed as a future.
+///
+/// The computer only relies on the `Stream` and `Sink` traits from the `futures` crate, so
+/// it isn't tied to any particular runtime - it'll happily run on Tokio, async-std or the
+/// simple executor in `futures` itself.  `futures::channel::mpsc` provides suitable streams.
+pub struct StreamingIOComputer<I, O> {
     processor: Processor,
-    in_stream: UnboundedReceiver<i64>,
-    out_stream: UnboundedSender<AsyncComputeNotification>,
+    in_stream: I,
+    out_stream: O,
 }
 
-impl StreamingIOComputer {
+impl<I, O> StreamingIOComputer<I, O>
+where
+    I: Stream<Item = i64> + Unpin,
+    O: Sink<AsyncComputeNotification> + Unpin,
+    O::Error: Debug,
+{
     /// Construct a computer to run `program`.  `program` only needs to be as long as the
     /// instructions and data contained within it; the computer has additional memory available
     /// that the program can refer to.
     ///
-    /// `in_channel` is the receive half of a stream on which you can send runtime inputs to
-    /// the program, and `out_channel` is the send half of a stream on which you can receive
-    /// runtime outputs.
+    /// `in_stream` is a stream of runtime inputs to the program - typically the receive half
+    /// of a channel - and `out_stream` is a sink to which the computer will send its outputs
+    /// and other notifications.
     #[must_use]
-    pub fn new(
-        program: &[i64],
-        in_stream: UnboundedReceiver<i64>,
-        out_stream: UnboundedSender<AsyncComputeNotification>,
-    ) -> Self {
+    pub fn new(program: &[i64], in_stream: I, out_stream: O) -> Self {
         Self {
-            processor: Processor::new(program),
+            processor: Processor::new(program.to_vec()),
             in_stream,
             out_stream,
         }
@@ -239,7 +303,7 @@ impl StreamingIOComputer {
     ///
     /// This is an async function which will run the program through to completion, but may
     /// `await` input on the inbound stream if sufficient inputs are not pre-sent.  Typically
-    /// you would spawn this function onto a Tokio reactor.
+    /// you would spawn this function onto your executor of choice.
     ///
     /// # Panics
     ///
@@ -251,9 +315,7 @@ impl StreamingIOComputer {
             match self.processor.process() {
                 SingleOperationResult::Handled => (),
                 SingleOperationResult::InputRequired => {
-                    self.out_stream
-                        .send(AsyncComputeNotification::InputRequired)
-                        .expect("Intcode computer tried to send an output but channel was closed!");
+                    self.notify(AsyncComputeNotification::InputRequired).await;
                     let input = self
                         .in_stream
                         .next()
@@ -261,28 +323,218 @@ impl StreamingIOComputer {
                         .expect("Intcode computer expected an input but channel was closed!");
                     self.processor.input_available(input);
                 }
-                SingleOperationResult::OutputAvailable(output) => self
-                    .out_stream
-                    .send(AsyncComputeNotification::Output(output))
-                    .expect("Intcode computer tried to send an output but channel was closed!"),
+                SingleOperationResult::OutputAvailable(output) => {
+                    self.notify(AsyncComputeNotification::Output(output)).await;
+                }
                 SingleOperationResult::ProgramEnded => {
-                    self.out_stream
-                        .send(AsyncComputeNotification::ProgramEnded)
-                        .expect("Intcode computer tried to send an output but channel was closed!");
+                    self.notify(AsyncComputeNotification::ProgramEnded).await;
                     break;
                 }
             }
         }
     }
+
+    async fn notify(&mut self, notification: AsyncComputeNotification) {
+        self.out_stream
+            .send(notification)
+            .await
+            .expect("Intcode computer tried to send an output but channel was closed!");
+    }
+}
+
+/// What a `ChannelIOComputer` is doing at the moment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComputerState {
+    /// The computer is executing instructions, or is ready to do so.
+    Running,
+
+    /// The program wants an input, but there isn't one waiting on the input channel.
+    BlockedOnInput,
+
+    /// The program has generated an output, but the (bounded) output channel is full.
+    BlockedOnOutput,
+
+    /// The program has run to completion.
+    Halted,
+
+    /// The computer has been paused via its [`ComputerControl`].
+    ///
+    /// [`ComputerControl`]: ./struct.ComputerControl.html
+    Paused,
+
+    /// The computer was told to stop via its [`ComputerControl`] before the program ended.
+    ///
+    /// [`ComputerControl`]: ./struct.ComputerControl.html
+    Terminated,
+}
+
+impl ComputerState {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => ComputerState::Running,
+            1 => ComputerState::BlockedOnInput,
+            2 => ComputerState::BlockedOnOutput,
+            3 => ComputerState::Halted,
+            4 => ComputerState::Paused,
+            5 => ComputerState::Terminated,
+            _ => unreachable!("Invalid computer state: {}", value),
+        }
+    }
+}
+
+/// A handle for watching the state of a `ChannelIOComputer` from another thread - typically
+/// because the computer itself has been moved onto a thread of its own to run.
+#[derive(Clone)]
+pub struct StateMonitor {
+    state: Arc<AtomicU8>,
+}
+
+impl StateMonitor {
+    /// Returns what the computer is doing right now.  Of course, by the time you look at the
+    /// answer, it may have moved on!
+    #[must_use]
+    pub fn state(&self) -> ComputerState {
+        ComputerState::from_u8(self.state.load(Ordering::SeqCst))
+    }
+}
+
+// How many instructions a computer with a control channel executes between checks for
+// commands, and how long it waits for I/O at a time before checking again.
+const CONTROL_CHECK_INSTRUCTIONS: usize = 1000;
+const CONTROL_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+enum Command {
+    Pause,
+    Resume,
+    Snapshot(Sender<Snapshot>),
+    Terminate,
+}
+
+/// A handle for controlling a `ChannelIOComputer` while it's running on another thread,
+/// obtained from [`ChannelIOComputer::control()`].
+///
+/// Commands are acted on between instructions, or while the computer is waiting for I/O,
+/// so there may be a short delay before they take effect.  They only apply while the
+/// computer is inside [`ChannelIOComputer::run()`]; once that returns, the handle stops
+/// working.
+///
+/// [`ChannelIOComputer::control()`]: ./struct.ChannelIOComputer.html#method.control
+/// [`ChannelIOComputer::run()`]: ./struct.ChannelIOComputer.html#method.run
+pub struct ComputerControl {
+    commands: Sender<Command>,
+}
+
+impl ComputerControl {
+    /// Asks the computer to stop executing until it's told to resume (or terminate).
+    pub fn pause(&self) {
+        let _ = self.commands.send(Command::Pause);
+    }
+
+    /// Asks a paused computer to carry on from where it left off.
+    pub fn resume(&self) {
+        let _ = self.commands.send(Command::Resume);
+    }
+
+    /// Asks the computer for a snapshot of its state, and waits for it.  Returns `None` if
+    /// the computer has finished running.
+    ///
+    /// Snapshots of a computer that isn't paused are of whatever point it had reached when
+    /// it noticed the request, so pause it first if you need to be sure it isn't going to
+    /// move on straight afterwards.
+    #[must_use]
+    pub fn snapshot(&self) -> Option<Snapshot> {
+        let (reply_sender, reply_receiver) = mpsc::channel();
+        self.commands.send(Command::Snapshot(reply_sender)).ok()?;
+        reply_receiver.recv().ok()
+    }
+
+    /// Asks the computer to stop running, even though its program hasn't finished.  Its
+    /// `run()` will then return, with the computer's state left as `Terminated`.
+    pub fn terminate(&self) {
+        let _ = self.commands.send(Command::Terminate);
+    }
+}
+
+/// The complete state of an Intcode computer, from which an equivalent computer can be
+/// rebuilt - whether that's the same kind of computer or not.  So you could, for example,
+/// take a snapshot of a `ChannelIOComputer` that's running on its own thread, poke at it
+/// with a `SynchronousComputer`, and then put it back on a thread.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The contents of the computer's memory.
+    pub memory: Vec<i64>,
+
+    /// The address of the next instruction to be executed.
+    pub instruction_pointer: i64,
+
+    /// The base address for parameters in relative mode.
+    pub relative_base: i64,
+
+    /// If the program is waiting for input, the address at which the input will be stored.
+    pub input_location: Option<i64>,
+
+    /// An output that the program has generated, but which hasn't been delivered yet.
+    pub pending_output: Option<i64>,
+
+    /// Whether the program has run to completion.
+    pub halted: bool,
+}
+
+// The result of attempting I/O on a `ChannelIOComputer`'s channels.
+enum Io<T> {
+    Ready(T),
+    WouldBlock,
+    Terminated,
+}
+
+// The send half of a computer's output channel, which may or may not have a bound.
+enum OutputChannel {
+    Unbounded(Sender<i64>),
+    Bounded(SyncSender<i64>),
+}
+
+impl OutputChannel {
+    fn try_send(&self, output: i64) -> Result<(), TrySendError<i64>> {
+        match self {
+            OutputChannel::Unbounded(sender) => sender
+                .send(output)
+                .map_err(|SendError(output)| TrySendError::Disconnected(output)),
+            OutputChannel::Bounded(sender) => sender.try_send(output),
+        }
+    }
+
+    fn send(&self, output: i64) -> Result<(), SendError<i64>> {
+        match self {
+            OutputChannel::Unbounded(sender) => sender.send(output),
+            OutputChannel::Bounded(sender) => sender.send(output),
+        }
+    }
 }
 
 /// A virtual computer whose memory contains an Intcode program and which can execute
 /// said program, where communication with the computer is via MPSC channels, and the
 /// computer is intended to be executed on its own thread.
+///
+/// Alternatively, the computer can be driven a few instructions at a time without ever
+/// blocking, using [`try_run_for()`] - which allows one thread to look after several
+/// computers, most easily via a [`Select`].
+///
+/// A computer running on its own thread can be paused, inspected and stopped via a
+/// [`ComputerControl`], and its state can be captured as a [`Snapshot`] to be carried on
+/// by another computer later.
+///
+/// [`try_run_for()`]: #method.try_run_for
+/// [`Select`]: ./struct.Select.html
+/// [`ComputerControl`]: ./struct.ComputerControl.html
+/// [`Snapshot`]: ./struct.Snapshot.html
 pub struct ChannelIOComputer {
     processor: Processor,
     in_channel: Receiver<i64>,
-    out_channel: Sender<i64>,
+    out_channel: OutputChannel,
+    state: Arc<AtomicU8>,
+    pending_output: Option<i64>,
+    outputs_sent: usize,
+    control: Option<Receiver<Command>>,
 }
 
 impl ChannelIOComputer {
@@ -294,11 +546,91 @@ impl ChannelIOComputer {
     /// the program, and `out_channel` is the send half of a channel on which you can receive
     /// runtime outputs.
     pub fn new(program: &[i64], in_channel: Receiver<i64>, out_channel: Sender<i64>) -> Self {
+        Self::from_vec(program.to_vec(), in_channel, out_channel)
+    }
+
+    /// Like [`new()`], but takes ownership of `program` rather than copying it, for when
+    /// you've made a copy of your own to patch before running it.
+    ///
+    /// [`new()`]: #method.new
+    pub fn from_vec(
+        program: Vec<i64>,
+        in_channel: Receiver<i64>,
+        out_channel: Sender<i64>,
+    ) -> Self {
         let processor = Processor::new(program);
+        Self::with_output_channel(processor, in_channel, OutputChannel::Unbounded(out_channel))
+    }
+
+    /// Like [`new()`], but outputs are sent on a bounded channel created by
+    /// `std::sync::mpsc::sync_channel`.  If the program generates outputs faster than you
+    /// consume them, the computer will stop and wait for you to catch up once the channel is
+    /// full, rather than using ever more memory.
+    ///
+    /// [`new()`]: #method.new
+    pub fn new_bounded(
+        program: &[i64],
+        in_channel: Receiver<i64>,
+        out_channel: SyncSender<i64>,
+    ) -> Self {
+        let processor = Processor::new(program.to_vec());
+        Self::with_output_channel(processor, in_channel, OutputChannel::Bounded(out_channel))
+    }
+
+    /// Construct a computer in the state captured by `snapshot`, communicating via
+    /// `in_channel` and `out_channel` as for [`new()`].
+    ///
+    /// [`new()`]: #method.new
+    #[must_use]
+    pub fn from_snapshot(
+        snapshot: &Snapshot,
+        in_channel: Receiver<i64>,
+        out_channel: Sender<i64>,
+    ) -> Self {
+        Self::from_snapshot_with_output(snapshot, in_channel, OutputChannel::Unbounded(out_channel))
+    }
+
+    /// Like [`from_snapshot()`], but outputs are sent on a bounded channel, as for
+    /// [`new_bounded()`].
+    ///
+    /// [`from_snapshot()`]: #method.from_snapshot
+    /// [`new_bounded()`]: #method.new_bounded
+    #[must_use]
+    pub fn from_snapshot_bounded(
+        snapshot: &Snapshot,
+        in_channel: Receiver<i64>,
+        out_channel: SyncSender<i64>,
+    ) -> Self {
+        Self::from_snapshot_with_output(snapshot, in_channel, OutputChannel::Bounded(out_channel))
+    }
+
+    fn from_snapshot_with_output(
+        snapshot: &Snapshot,
+        in_channel: Receiver<i64>,
+        out_channel: OutputChannel,
+    ) -> Self {
+        let processor = Processor::from_snapshot(snapshot);
+        let mut computer = Self::with_output_channel(processor, in_channel, out_channel);
+        computer.pending_output = snapshot.pending_output;
+        if snapshot.halted {
+            computer.set_state(ComputerState::Halted);
+        }
+        computer
+    }
+
+    fn with_output_channel(
+        processor: Processor,
+        in_channel: Receiver<i64>,
+        out_channel: OutputChannel,
+    ) -> Self {
         Self {
             processor,
             in_channel,
             out_channel,
+            state: Arc::new(AtomicU8::new(ComputerState::Running as u8)),
+            pending_output: None,
+            outputs_sent: 0,
+            control: None,
         }
     }
 
@@ -306,7 +638,7 @@ impl ChannelIOComputer {
     ///
     /// This will synchronously run the program through to completion on the current thread, but
     /// may block waiting for input on the computer's input channel if sufficient inputs are not
-    /// pre-sent.
+    /// pre-sent, or waiting for space on a bounded output channel.
     ///
     /// # Panics
     ///
@@ -314,25 +646,57 @@ impl ChannelIOComputer {
     /// program is invalid, or that invalid inputs were provided to it, or that the channels were
     /// closed prematurely.
     pub fn run(&mut self) {
-        loop {
-            match self.processor.process() {
-                SingleOperationResult::Handled => (),
-                SingleOperationResult::InputRequired => {
-                    let input = self
-                        .in_channel
-                        .recv()
-                        .expect("Intcode computer expected an input but channel was closed!");
-                    self.processor.input_available(input);
-                }
-                SingleOperationResult::OutputAvailable(output) => self
-                    .out_channel
-                    .send(output)
-                    .expect("Intcode computer tried to send an output but channel was closed!"),
-                SingleOperationResult::ProgramEnded => break,
-            }
+        self.execute(None, true);
+    }
+
+    /// Executes at most `n_instructions` instructions of the program, without ever blocking.
+    /// Returns early if the program needs an input that isn't available yet, if a bounded
+    /// output channel is full, or if the program ends.  Call it again to carry on from where
+    /// it left off.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any problem is hit executing the program, which would indicate either that the
+    /// program is invalid, or that invalid inputs were provided to it, or that the channels were
+    /// closed prematurely.
+    pub fn try_run_for(&mut self, n_instructions: usize) -> ComputerState {
+        self.execute(Some(n_instructions), false)
+    }
+
+    /// Returns what the computer is doing at the moment.
+    #[must_use]
+    pub fn state(&self) -> ComputerState {
+        ComputerState::from_u8(self.state.load(Ordering::SeqCst))
+    }
+
+    /// Returns a handle that can be used to check the computer's state, even once the
+    /// computer has been moved onto its own thread.
+    #[must_use]
+    pub fn monitor(&self) -> StateMonitor {
+        StateMonitor {
+            state: Arc::clone(&self.state),
         }
     }
 
+    /// Returns a handle that can be used to pause, resume, snapshot or terminate the
+    /// computer while it's inside [`run()`] - see [`ComputerControl`].  Only the handle
+    /// returned by the most recent call has any effect.
+    ///
+    /// [`run()`]: #method.run
+    /// [`ComputerControl`]: ./struct.ComputerControl.html
+    pub fn control(&mut self) -> ComputerControl {
+        let (commands, receiver) = mpsc::channel();
+        self.control = Some(receiver);
+        ComputerControl { commands }
+    }
+
+    /// Captures the computer's current state.
+    #[must_use]
+    pub fn snapshot(&self) -> Snapshot {
+        self.processor
+            .snapshot(self.pending_output, self.state() == ComputerState::Halted)
+    }
+
     /// Returns the value currently stored at memory address zero.
     ///
     /// This function shouldn't exist - Intcode programs should output anything that users
@@ -345,6 +709,309 @@ impl ChannelIOComputer {
     pub fn fetch_address_zero(&self) -> i64 {
         self.processor.memory[0]
     }
+
+    fn set_state(&self, state: ComputerState) -> ComputerState {
+        self.state.store(state as u8, Ordering::SeqCst);
+        state
+    }
+
+    // Act on any commands that have arrived on the control channel.  Returns false if we've
+    // been told to terminate.
+    fn check_control(&self) -> bool {
+        if let Some(control) = &self.control {
+            while let Ok(command) = control.try_recv() {
+                match command {
+                    Command::Pause => {
+                        if !self.paused(control) {
+                            return false;
+                        }
+                    }
+                    Command::Resume => (),
+                    Command::Snapshot(reply) => {
+                        let _ = reply.send(self.snapshot());
+                    }
+                    Command::Terminate => return false,
+                }
+            }
+        }
+        true
+    }
+
+    // Wait on the control channel until we're told to resume (returning true) or terminate
+    // (returning false).  If the controller goes away, there's nobody left to resume us, so
+    // we resume ourselves.
+    fn paused(&self, control: &Receiver<Command>) -> bool {
+        let previous_state = self.state();
+        self.set_state(ComputerState::Paused);
+        loop {
+            match control.recv() {
+                Ok(Command::Pause) => (),
+                Ok(Command::Snapshot(reply)) => {
+                    let _ = reply.send(self.snapshot());
+                }
+                Ok(Command::Resume) | Err(_) => {
+                    self.set_state(previous_state);
+                    return true;
+                }
+                Ok(Command::Terminate) => return false,
+            }
+        }
+    }
+
+    // Stop running for good (until the next call to `run`), answering any snapshot requests
+    // that were waiting and cutting off the control channel.
+    fn finish(&mut self, state: ComputerState) -> ComputerState {
+        self.set_state(state);
+        if let Some(control) = self.control.take() {
+            for command in control.try_iter() {
+                if let Command::Snapshot(reply) = command {
+                    let _ = reply.send(self.snapshot());
+                }
+            }
+        }
+        state
+    }
+
+    // The guts of both `run` and `try_run_for`.  Runs until the program ends or, if
+    // `budget` is provided, that many instructions have been executed.  If `block` is false,
+    // we also stop when I/O can't be completed immediately.
+    fn execute(&mut self, mut budget: Option<usize>, block: bool) -> ComputerState {
+        if self.state() == ComputerState::Halted {
+            return self.finish(ComputerState::Halted);
+        }
+        self.set_state(ComputerState::Running);
+        let mut instructions_since_check = 0;
+
+        loop {
+            // Before executing anything else, finish off any I/O left over from last time.
+            if let Some(output) = self.pending_output.take() {
+                match self.deliver_output(output, block) {
+                    Io::Ready(()) => (),
+                    Io::WouldBlock => return self.set_state(ComputerState::BlockedOnOutput),
+                    Io::Terminated => return self.finish(ComputerState::Terminated),
+                }
+            }
+            if self.processor.input_location.is_some() {
+                match self.fetch_input(block) {
+                    Io::Ready(input) => self.processor.input_available(input),
+                    Io::WouldBlock => return self.set_state(ComputerState::BlockedOnInput),
+                    Io::Terminated => return self.finish(ComputerState::Terminated),
+                }
+            }
+
+            if block && self.control.is_some() {
+                instructions_since_check += 1;
+                if instructions_since_check == CONTROL_CHECK_INSTRUCTIONS {
+                    instructions_since_check = 0;
+                    if !self.check_control() {
+                        return self.finish(ComputerState::Terminated);
+                    }
+                }
+            }
+
+            match budget {
+                Some(0) => return self.set_state(ComputerState::Running),
+                Some(ref mut remaining) => *remaining -= 1,
+                None => (),
+            }
+
+            match self.processor.process() {
+                // Input is picked up at the top of the loop.
+                SingleOperationResult::Handled | SingleOperationResult::InputRequired => (),
+                SingleOperationResult::OutputAvailable(output) => {
+                    self.pending_output = Some(output);
+                }
+                SingleOperationResult::ProgramEnded => return self.finish(ComputerState::Halted),
+            }
+        }
+    }
+
+    // Send an output, holding onto it if that can't be done without blocking and we're not
+    // allowed to block, or if we're told to terminate while waiting.
+    fn deliver_output(&mut self, output: i64, block: bool) -> Io<()> {
+        match self.out_channel.try_send(output) {
+            Ok(()) => (),
+            Err(TrySendError::Full(output)) if block => {
+                self.set_state(ComputerState::BlockedOnOutput);
+                if self.control.is_some() {
+                    // Keep an eye on the control channel while we wait.  Bounded senders
+                    // can't time out, so we just have to keep trying.
+                    let mut output = output;
+                    loop {
+                        if !self.check_control() {
+                            self.pending_output = Some(output);
+                            return Io::Terminated;
+                        }
+                        match self.out_channel.try_send(output) {
+                            Ok(()) => break,
+                            Err(TrySendError::Full(unsent)) => {
+                                output = unsent;
+                                std::thread::sleep(CONTROL_POLL_INTERVAL);
+                            }
+                            Err(TrySendError::Disconnected(_)) => panic!(
+                                "Intcode computer tried to send an output but channel was closed!"
+                            ),
+                        }
+                    }
+                } else {
+                    self.out_channel
+                        .send(output)
+                        .expect("Intcode computer tried to send an output but channel was closed!");
+                }
+                self.set_state(ComputerState::Running);
+            }
+            Err(TrySendError::Full(output)) => {
+                self.pending_output = Some(output);
+                return Io::WouldBlock;
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                panic!("Intcode computer tried to send an output but channel was closed!")
+            }
+        }
+        self.outputs_sent += 1;
+        Io::Ready(())
+    }
+
+    // Receive an input, if one is available or we're allowed to block waiting for one.
+    fn fetch_input(&mut self, block: bool) -> Io<i64> {
+        match self.in_channel.try_recv() {
+            Ok(input) => Io::Ready(input),
+            Err(TryRecvError::Empty) if block => {
+                self.set_state(ComputerState::BlockedOnInput);
+                let input = if self.control.is_some() {
+                    // Keep an eye on the control channel while we wait.
+                    loop {
+                        if !self.check_control() {
+                            return Io::Terminated;
+                        }
+                        match self.in_channel.recv_timeout(CONTROL_POLL_INTERVAL) {
+                            Ok(input) => break input,
+                            Err(RecvTimeoutError::Timeout) => (),
+                            Err(RecvTimeoutError::Disconnected) => {
+                                panic!("Intcode computer expected an input but channel was closed!")
+                            }
+                        }
+                    }
+                } else {
+                    self.in_channel
+                        .recv()
+                        .expect("Intcode computer expected an input but channel was closed!")
+                };
+                self.set_state(ComputerState::Running);
+                Io::Ready(input)
+            }
+            Err(TryRecvError::Empty) => Io::WouldBlock,
+            Err(TryRecvError::Disconnected) => {
+                panic!("Intcode computer expected an input but channel was closed!")
+            }
+        }
+    }
+}
+
+/// Returned by [`Select::select()`] to say which computer needs your attention.
+///
+/// [`Select::select()`]: ./struct.Select.html#method.select
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Selected {
+    /// The index of the computer, as returned by [`Select::add()`].
+    ///
+    /// [`Select::add()`]: ./struct.Select.html#method.add
+    pub index: usize,
+
+    /// The computer's state.  If it's `Running`, the computer has sent at least one output.
+    pub state: ComputerState,
+}
+
+/// Multiplexes several `ChannelIOComputer`s on a single thread, in the style of crossbeam's
+/// `select!`.  Each call to [`select()`] runs the computers in turn, a quantum of
+/// instructions at a time, until one of them has sent some output, is blocked, or has
+/// halted - so that you can go and service its channels.
+///
+/// ```
+/// use std::sync::mpsc;
+/// use intcode::{ChannelIOComputer, ComputerState, Select};
+///
+/// let mut select = Select::new(100);
+/// let (in_send, in_recv) = mpsc::channel();
+/// let (out_send, out_recv) = mpsc::channel();
+/// let echo = select.add(ChannelIOComputer::new(&[3, 0, 4, 0, 99], in_recv, out_send));
+///
+/// let selected = select.select().unwrap();
+/// assert_eq!((selected.index, selected.state), (echo, ComputerState::BlockedOnInput));
+/// in_send.send(5).unwrap();
+/// select.select().unwrap();
+/// assert_eq!(out_recv.recv(), Ok(5));
+/// ```
+///
+/// [`select()`]: #method.select
+pub struct Select {
+    computers: Vec<ChannelIOComputer>,
+    halt_reported: Vec<bool>,
+    quantum: usize,
+    next: usize,
+}
+
+impl Select {
+    /// Creates an empty `Select`, which will run each computer for up to `quantum`
+    /// instructions before moving on to the next.
+    #[must_use]
+    pub fn new(quantum: usize) -> Self {
+        Self {
+            computers: Vec::new(),
+            halt_reported: Vec::new(),
+            quantum,
+            next: 0,
+        }
+    }
+
+    /// Adds a computer, returning the index by which [`select()`] will refer to it.
+    ///
+    /// [`select()`]: #method.select
+    pub fn add(&mut self, computer: ChannelIOComputer) -> usize {
+        self.computers.push(computer);
+        self.halt_reported.push(false);
+        self.computers.len() - 1
+    }
+
+    /// Returns the computer with the given index.
+    #[must_use]
+    pub fn computer(&self, index: usize) -> &ChannelIOComputer {
+        &self.computers[index]
+    }
+
+    /// Runs the computers in turn until one of them needs attention, and returns which one.
+    /// Computers are visited round-robin, starting after the one returned last time, so a
+    /// computer that stays blocked can't starve the others.  Each computer is reported as
+    /// halted only once; returns `None` when every computer has halted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the computers panics - see [`ChannelIOComputer::try_run_for()`].
+    ///
+    /// [`ChannelIOComputer::try_run_for()`]: ./struct.ChannelIOComputer.html#method.try_run_for
+    pub fn select(&mut self) -> Option<Selected> {
+        loop {
+            if self.halt_reported.iter().all(|reported| *reported) {
+                return None;
+            }
+
+            for offset in 0..self.computers.len() {
+                let index = (self.next + offset) % self.computers.len();
+                if self.halt_reported[index] {
+                    continue;
+                }
+
+                let computer = &mut self.computers[index];
+                let outputs_before = computer.outputs_sent;
+                let state = computer.try_run_for(self.quantum);
+                if state != ComputerState::Running || computer.outputs_sent > outputs_before {
+                    self.halt_reported[index] = state == ComputerState::Halted;
+                    self.next = index + 1;
+                    return Some(Selected { index, state });
+                }
+            }
+        }
+    }
 }
 
 // The result of executing a single operation on a computer.
@@ -462,9 +1129,9 @@ struct Processor {
 }
 
 impl Processor {
-    pub fn new(program: &[i64]) -> Self {
+    pub fn new(memory: Vec<i64>) -> Self {
         Self {
-            memory: program.into(),
+            memory,
             instruction_pointer: 0,
             relative_base: 0,
             input_location: None,
@@ -479,6 +1146,31 @@ impl Processor {
         self.execute_operation(&operation)
     }
 
+    fn from_snapshot(snapshot: &Snapshot) -> Self {
+        Self {
+            memory: snapshot.memory.clone(),
+            instruction_pointer: snapshot.instruction_pointer,
+            relative_base: snapshot.relative_base,
+            input_location: snapshot.input_location,
+            stored_inputs: VecDeque::new(),
+        }
+    }
+
+    fn snapshot(&self, pending_output: Option<i64>, halted: bool) -> Snapshot {
+        assert!(
+            self.stored_inputs.is_empty(),
+            "Can't snapshot a computer with stored inputs"
+        );
+        Snapshot {
+            memory: self.memory.clone(),
+            instruction_pointer: self.instruction_pointer,
+            relative_base: self.relative_base,
+            input_location: self.input_location,
+            pending_output,
+            halted,
+        }
+    }
+
     fn input_available(&mut self, input: i64) {
         if let Some(location) = self.input_location {
             // We've previously evaluated an Input operation when we had no input available,
@@ -653,7 +1345,7 @@ pub fn run_parallel_computer(program: &[i64], inputs: &[i64]) -> Vec<i64> {
         in_sender.send(*input).unwrap();
     }
 
-    ChannelIOComputer::new(&program, in_receiver, out_sender).run();
+    ChannelIOComputer::new(program, in_receiver, out_sender).run();
 
     let mut outputs = Vec::new();
     while let Ok(output) = out_receiver.try_recv() {
@@ -663,30 +1355,31 @@ pub fn run_parallel_computer(program: &[i64], inputs: &[i64]) -> Vec<i64> {
 }
 
 /// Helper function for running an Intcode program that can run all the way to
-/// completion with a predetermined set of inputs (including no inputs), on a
-/// futures executor (e.g. a Tokio reactor).
+/// completion with a predetermined set of inputs (including no inputs), on any
+/// futures executor.
 ///
 /// # Panics
 ///
 /// Panics if the supplied program is invalid, or if insufficient inputs were
 /// provided to run it to completion.
 pub async fn run_async_computer(program: &[i64], inputs: &[i64]) -> Vec<i64> {
-    let (in_sender, in_receiver) = unbounded_channel();
-    let (out_sender, out_receiver) = unbounded_channel();
-
-    for input in inputs {
-        in_sender.send(*input).unwrap();
-    }
+    let (out_sender, out_receiver) = unbounded();
 
-    StreamingIOComputer::new(&program, in_receiver, out_sender)
+    StreamingIOComputer::new(program, stream::iter(inputs.to_vec()), out_sender)
         .run()
         .await;
 
-    out_receiver.filter_map(|notification| match notification {
-        AsyncComputeNotification::InputRequired => panic!("Insufficient inputs given to program!"),
-        AsyncComputeNotification::ProgramEnded => None,
-        AsyncComputeNotification::Output(output) => Some(output),
-    }).collect().await
+    // The computer tells us whenever it wants input, but as we've supplied it all up front,
+    // we only care about the outputs.
+    out_receiver
+        .filter_map(|notification| async move {
+            match notification {
+                AsyncComputeNotification::Output(output) => Some(output),
+                _ => None,
+            }
+        })
+        .collect()
+        .await
 }
 
 /// Loads an Intcode program from the file at `path`.
@@ -699,7 +1392,19 @@ pub fn load_program(path: &str) -> Result<Vec<i64>, io::Error> {
     let mut input_file = File::open(path)?;
     let mut input = String::new();
     input_file.read_to_string(&mut input)?;
+    parse_program(&input)
+}
+
+/// Parses an Intcode program from text - comma-separated integers, optionally followed by
+/// a newline.
+///
+/// # Errors
+///
+/// Returns an error if the text is syntactically invalid, but doesn't detect if the
+/// program itself is invalid.
+pub fn parse_program(input: &str) -> Result<Vec<i64>, io::Error> {
     input
+        .trim_end()
         .split(',')
         .map(|number| {
             number
@@ -708,3 +1413,194 @@ pub fn load_program(path: &str) -> Result<Vec<i64>, io::Error> {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Outputs 1, 2 and 3, then halts.
+    const COUNT_TO_THREE: [i64; 7] = [104, 1, 104, 2, 104, 3, 99];
+
+    // Reads an input and outputs it, forever.
+    const ECHO_FOREVER: [i64; 6] = [3, 10, 4, 10, 1105, 1];
+
+    #[test]
+    fn parse_program_accepts_trailing_newline() {
+        assert_eq!(parse_program("104,1,99\n").unwrap(), vec![104, 1, 99]);
+        assert!(parse_program("104,x,99").is_err());
+    }
+
+    #[test]
+    fn run_async_computer_collects_outputs() {
+        let outputs = futures::executor::block_on(run_async_computer(&[3, 0, 4, 0, 99], &[9]));
+        assert_eq!(outputs, vec![9]);
+    }
+
+    #[test]
+    fn bounded_output_applies_backpressure() {
+        let (_in_send, in_recv) = mpsc::channel();
+        let (out_send, out_recv) = mpsc::sync_channel(1);
+        let mut computer = ChannelIOComputer::new_bounded(&COUNT_TO_THREE, in_recv, out_send);
+
+        assert_eq!(computer.try_run_for(100), ComputerState::BlockedOnOutput);
+        assert_eq!(out_recv.try_recv(), Ok(1));
+        assert_eq!(computer.try_run_for(100), ComputerState::BlockedOnOutput);
+        assert_eq!(out_recv.try_recv(), Ok(2));
+        assert_eq!(computer.try_run_for(100), ComputerState::Halted);
+        assert_eq!(out_recv.try_recv(), Ok(3));
+        assert_eq!(computer.try_run_for(100), ComputerState::Halted);
+    }
+
+    #[test]
+    fn snapshots_keep_output_bounded() {
+        let (_in_send, in_recv) = mpsc::channel();
+        let (out_send, out_recv) = mpsc::sync_channel(1);
+        let mut computer = ChannelIOComputer::new_bounded(&COUNT_TO_THREE, in_recv, out_send);
+        assert_eq!(computer.try_run_for(100), ComputerState::BlockedOnOutput);
+        assert_eq!(out_recv.try_recv(), Ok(1));
+        let snapshot = computer.snapshot();
+
+        // The 2 that was waiting to go is sent first, and then the 3 has to wait its turn.
+        let (_in_send, in_recv) = mpsc::channel();
+        let (out_send, out_recv) = mpsc::sync_channel(1);
+        let mut computer = ChannelIOComputer::from_snapshot_bounded(&snapshot, in_recv, out_send);
+        assert_eq!(computer.try_run_for(100), ComputerState::BlockedOnOutput);
+        assert_eq!(out_recv.try_recv(), Ok(2));
+        assert_eq!(out_recv.try_recv(), Err(TryRecvError::Empty));
+        assert_eq!(computer.try_run_for(100), ComputerState::Halted);
+        assert_eq!(out_recv.try_recv(), Ok(3));
+    }
+
+    #[test]
+    fn try_run_for_respects_budget_and_input() {
+        let (in_send, in_recv) = mpsc::channel();
+        let (out_send, out_recv) = mpsc::channel();
+        let mut computer = ChannelIOComputer::new(&ECHO_FOREVER, in_recv, out_send);
+
+        assert_eq!(computer.try_run_for(0), ComputerState::Running);
+        assert_eq!(computer.try_run_for(10), ComputerState::BlockedOnInput);
+        in_send.send(42).unwrap();
+        assert_eq!(computer.try_run_for(1), ComputerState::Running);
+        assert_eq!(out_recv.try_recv(), Ok(42));
+        assert_eq!(computer.try_run_for(10), ComputerState::BlockedOnInput);
+        assert_eq!(out_recv.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn monitor_tracks_blocked_thread() {
+        let (in_send, in_recv) = mpsc::channel();
+        let (out_send, out_recv) = mpsc::channel();
+        let mut computer = ChannelIOComputer::new(&[3, 0, 4, 0, 99], in_recv, out_send);
+        let monitor = computer.monitor();
+        let handle = std::thread::spawn(move || computer.run());
+
+        while monitor.state() != ComputerState::BlockedOnInput {
+            std::thread::yield_now();
+        }
+        in_send.send(7).unwrap();
+        assert_eq!(out_recv.recv(), Ok(7));
+        handle.join().unwrap();
+        assert_eq!(monitor.state(), ComputerState::Halted);
+    }
+
+    #[test]
+    fn snapshots_move_between_computers() {
+        // Adds each input to a running total, and outputs the total.
+        let accumulate = [3, 21, 1, 20, 21, 20, 4, 20, 1105, 1, 0];
+
+        let mut sync_computer = SynchronousComputer::new(&accumulate);
+        assert_eq!(sync_computer.run(&[3, 4]).outputs, vec![3, 7]);
+        let snapshot = sync_computer.snapshot();
+        assert_eq!(snapshot.input_location, Some(21));
+
+        let (in_send, in_recv) = mpsc::channel();
+        let (out_send, out_recv) = mpsc::channel();
+        let mut channel_computer = ChannelIOComputer::from_snapshot(&snapshot, in_recv, out_send);
+        in_send.send(5).unwrap();
+        assert_eq!(
+            channel_computer.try_run_for(100),
+            ComputerState::BlockedOnInput
+        );
+        assert_eq!(out_recv.try_recv(), Ok(12));
+
+        let mut sync_computer = SynchronousComputer::from_snapshot(&channel_computer.snapshot());
+        let output = sync_computer.run(&[1]);
+        assert!(output.result == SynchronousComputeResult::InputRequired);
+        assert_eq!(output.outputs, vec![13]);
+    }
+
+    #[test]
+    fn control_pauses_snapshots_and_terminates() {
+        let (in_send, in_recv) = mpsc::channel();
+        let (out_send, out_recv) = mpsc::channel();
+        let mut computer = ChannelIOComputer::new(&ECHO_FOREVER, in_recv, out_send);
+        let control = computer.control();
+        let monitor = computer.monitor();
+        let handle = std::thread::spawn(move || {
+            computer.run();
+            computer
+        });
+
+        in_send.send(3).unwrap();
+        assert_eq!(out_recv.recv(), Ok(3));
+
+        control.pause();
+        let snapshot = control.snapshot().unwrap();
+        assert_eq!(monitor.state(), ComputerState::Paused);
+        assert_eq!(snapshot.input_location, Some(10));
+        assert_eq!(snapshot.memory[10], 3);
+
+        // Inputs sent while paused are only processed once we resume.
+        in_send.send(4).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        assert_eq!(out_recv.try_recv(), Err(TryRecvError::Empty));
+        control.resume();
+        assert_eq!(out_recv.recv(), Ok(4));
+
+        control.terminate();
+        let computer = handle.join().unwrap();
+        assert_eq!(computer.state(), ComputerState::Terminated);
+        assert_eq!(control.snapshot(), None);
+        assert!(!computer.snapshot().halted);
+    }
+
+    #[test]
+    fn select_round_robins_and_reports_halts_once() {
+        let mut select = Select::new(10);
+        let (_in_send_a, in_recv_a) = mpsc::channel();
+        let (out_send_a, out_recv_a) = mpsc::sync_channel(1);
+        let a = select.add(ChannelIOComputer::new_bounded(
+            &COUNT_TO_THREE,
+            in_recv_a,
+            out_send_a,
+        ));
+        let (in_send_b, in_recv_b) = mpsc::channel();
+        let (out_send_b, out_recv_b) = mpsc::channel();
+        let b = select.add(ChannelIOComputer::new(&ECHO_FOREVER, in_recv_b, out_send_b));
+
+        let mut outputs_a = Vec::new();
+        let mut halted = Vec::new();
+        while let Some(selected) = select.select() {
+            if selected.index == a {
+                outputs_a.extend(out_recv_a.try_iter());
+                if selected.state == ComputerState::Halted {
+                    halted.push(a);
+                }
+            } else {
+                assert_eq!(selected.index, b);
+                if selected.state == ComputerState::BlockedOnInput {
+                    if outputs_a.len() == 3 {
+                        break;
+                    }
+                    in_send_b.send(outputs_a.len() as i64).unwrap();
+                }
+            }
+        }
+
+        outputs_a.extend(out_recv_a.try_iter());
+        assert_eq!(outputs_a, vec![1, 2, 3]);
+        assert_eq!(halted, vec![a]);
+        assert_eq!(select.computer(a).state(), ComputerState::Halted);
+        assert!(out_recv_b.try_iter().count() > 0);
+    }
+}
@@ -0,0 +1,504 @@
+//! # Scheduler
+//!
+//! Runs many Intcode machines on a single thread, taking turns.
+//!
+//! Machines talk to each other through named pipes - first-in first-out queues of
+//! values.  Each machine reads from one pipe and writes to another, so wiring up a
+//! topology is just a matter of choosing pipe names: a ring of machines where each
+//! writes to the next one's input is a feedback loop, for example.  You can also send
+//! values into pipes, and take them out again, from outside the scheduler.
+//!
+//! Where machines exchange multi-value packets rather than single values, a pipe can be
+//! replaced by a router, which gathers up each machine's output into fixed-size packets
+//! and decides where each one goes.
+//!
+//! The day 7 feedback loop looks like this:
+//!
+//! ```no_run
+//! use intcode::{RunOutcome, Scheduler};
+//!
+//! let program = intcode::load_program("day7/input.txt").unwrap();
+//!
+//! let amplifiers = ["A", "B", "C", "D", "E"];
+//! let mut scheduler = Scheduler::new(1000);
+//! for (index, (name, phase)) in amplifiers.iter().zip(&[9, 8, 7, 6, 5]).enumerate() {
+//!     scheduler.send(name, *phase);
+//!     scheduler.add_machine(name, &program, name, amplifiers[(index + 1) % 5]);
+//! }
+//! scheduler.send("A", 0);
+//! assert_eq!(scheduler.run(), Ok(RunOutcome::AllHalted));
+//!
+//! // E's last output is left in A's input pipe.
+//! let thruster_signal = scheduler.drain("A").pop();
+//! ```
+//!
+//! See [`Scheduler`] for how a run ends.
+//!
+//! [`Scheduler`]: ./struct.Scheduler.html
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+use crate::{Processor, SingleOperationResult};
+
+// How many turns in a row a machine with an idle input has to spend asking for input and
+// being given the idle value, without producing any output, before we consider it idle.
+const IDLE_POLLS: usize = 2;
+
+/// How a call to [`Scheduler::run()`] finished, if it didn't deadlock.
+///
+/// [`Scheduler::run()`]: ./struct.Scheduler.html#method.run
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// Every machine's program has run to completion.
+    AllHalted,
+
+    /// Every machine that's still running is waiting for input that isn't there, but at
+    /// least one of them is being kept going by its idle input.  Send something to one of
+    /// the pipes and call `run()` again to carry on.
+    Idle,
+}
+
+/// A machine that was waiting for input when the scheduler deadlocked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockedMachine {
+    /// The machine's name.
+    pub machine: String,
+
+    /// The pipe that the machine is waiting to read from.
+    pub pipe: String,
+}
+
+/// Returned by [`Scheduler::run()`] if every machine that's still running is waiting for
+/// input, and none of them can ever get it.
+///
+/// [`Scheduler::run()`]: ./struct.Scheduler.html#method.run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeadlockReport {
+    /// The machines that were blocked, in the order they were added to the scheduler.
+    pub blocked: Vec<BlockedMachine>,
+
+    /// The machines that had already halted.
+    pub halted: Vec<String>,
+}
+
+impl fmt::Display for DeadlockReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Deadlock - ")?;
+        let blocked: Vec<String> = self
+            .blocked
+            .iter()
+            .map(|blocked| format!("{} waiting on {}", blocked.machine, blocked.pipe))
+            .collect();
+        write!(f, "{}", blocked.join(", "))?;
+        if !self.halted.is_empty() {
+            write!(f, " (halted: {})", self.halted.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for DeadlockReport {}
+
+// What happened during a machine's turn.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Turn {
+    Preempted,
+    Blocked,
+    Polled,
+    Halted,
+}
+
+struct Machine {
+    name: String,
+    processor: Processor,
+    input: String,
+    output: String,
+    idle_input: Option<i64>,
+    partial_packet: Vec<i64>,
+    empty_polls: usize,
+    halted: bool,
+}
+
+type Route = Box<dyn FnMut(&[i64]) -> (String, Vec<i64>)>;
+
+struct Router {
+    packet_length: usize,
+    route: Route,
+}
+
+/// Owns a collection of Intcode machines, and runs them round-robin on the current thread,
+/// with each getting a turn of up to `quantum` instructions before moving on to the next.
+/// A machine's turn also ends early if it needs input that isn't there yet.
+///
+/// [`run()`] carries on until one of:
+/// -  every machine has halted
+/// -  every machine that hasn't halted is waiting for input, but at least one of them is
+///    being fed an idle input (see [`set_idle_input()`]) - so the system has gone quiet, but
+///    might do something if you give it a nudge
+/// -  every machine that hasn't halted is waiting for input and none of them has an idle
+///    input, so nothing can ever happen again - in which case you get a report of who's
+///    waiting for what.
+///
+/// [`run()`]: #method.run
+/// [`set_idle_input()`]: #method.set_idle_input
+pub struct Scheduler {
+    machines: Vec<Machine>,
+    machine_indices: HashMap<String, usize>,
+    pipes: HashMap<String, VecDeque<i64>>,
+    routers: HashMap<String, Router>,
+    quantum: usize,
+}
+
+impl Scheduler {
+    /// Creates an empty scheduler, which will give each machine up to `quantum` instructions
+    /// per turn.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `quantum` is zero.
+    #[must_use]
+    pub fn new(quantum: usize) -> Self {
+        assert!(quantum > 0, "Scheduler quantum must be positive");
+        Self {
+            machines: Vec::new(),
+            machine_indices: HashMap::new(),
+            pipes: HashMap::new(),
+            routers: HashMap::new(),
+            quantum,
+        }
+    }
+
+    /// Adds a machine called `name` to run `program`, reading its inputs from the pipe
+    /// called `input` and writing its outputs to `output`, which may be a pipe or a router.
+    /// Pipes are created as required.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there's already a machine called `name`.
+    pub fn add_machine(&mut self, name: &str, program: &[i64], input: &str, output: &str) {
+        assert!(
+            !self.machine_indices.contains_key(name),
+            "Scheduler already has a machine called {}",
+            name
+        );
+        self.pipes.entry(input.to_string()).or_default();
+        if !self.routers.contains_key(output) {
+            self.pipes.entry(output.to_string()).or_default();
+        }
+
+        self.machine_indices
+            .insert(name.to_string(), self.machines.len());
+        self.machines.push(Machine {
+            name: name.to_string(),
+            processor: Processor::new(program.to_vec()),
+            input: input.to_string(),
+            output: output.to_string(),
+            idle_input: None,
+            partial_packet: Vec::new(),
+            empty_polls: 0,
+            halted: false,
+        });
+    }
+
+    /// Whenever the machine called `name` wants input and its pipe is empty, give it
+    /// `value` rather than waiting - like the NICs on day 23, which are given -1 when there
+    /// are no packets for them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there's no machine called `name`.
+    pub fn set_idle_input(&mut self, name: &str, value: i64) {
+        let index = self.machine_index(name);
+        self.machines[index].idle_input = Some(value);
+    }
+
+    /// Adds a router called `name`.  Machines that output to it have their outputs
+    /// gathered into packets of `packet_length` values (separately for each machine, so
+    /// that packets can't get interleaved), and `route` is called with each packet to
+    /// decide which pipe it goes to, and what's actually written to that pipe.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is already in use as a pipe or router, or `packet_length` is zero.
+    pub fn add_router<F>(&mut self, name: &str, packet_length: usize, route: F)
+    where
+        F: FnMut(&[i64]) -> (String, Vec<i64>) + 'static,
+    {
+        assert!(
+            packet_length > 0,
+            "Router packets must have a positive length"
+        );
+        assert!(
+            !self.pipes.contains_key(name) && !self.routers.contains_key(name),
+            "Scheduler already has a pipe or router called {}",
+            name
+        );
+        self.routers.insert(
+            name.to_string(),
+            Router {
+                packet_length,
+                route: Box::new(route),
+            },
+        );
+    }
+
+    /// Writes `value` to the end of the pipe called `pipe`, creating it if it doesn't
+    /// exist yet.
+    pub fn send(&mut self, pipe: &str, value: i64) {
+        self.pipes
+            .entry(pipe.to_string())
+            .or_default()
+            .push_back(value);
+    }
+
+    /// Takes the value from the front of the pipe called `pipe`, if there is one.
+    pub fn receive(&mut self, pipe: &str) -> Option<i64> {
+        self.pipes.get_mut(pipe).and_then(VecDeque::pop_front)
+    }
+
+    /// Takes everything in the pipe called `pipe`.
+    pub fn drain(&mut self, pipe: &str) -> Vec<i64> {
+        self.pipes
+            .get_mut(pipe)
+            .map(|queue| queue.drain(..).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns whether the machine called `name` has run its program to completion.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there's no machine called `name`.
+    #[must_use]
+    pub fn is_halted(&self, name: &str) -> bool {
+        self.machines[self.machine_index(name)].halted
+    }
+
+    /// Runs the machines until they've all halted, gone idle, or deadlocked, as described
+    /// above.  Can be called again after sending more input.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `DeadlockReport` if every machine still running is waiting for input that
+    /// will never come.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any problem is hit executing a machine's program, which would indicate
+    /// either that the program is invalid, or that invalid inputs were provided to it.
+    pub fn run(&mut self) -> Result<RunOutcome, DeadlockReport> {
+        loop {
+            let mut turns = Vec::with_capacity(self.machines.len());
+            for index in 0..self.machines.len() {
+                if !self.machines[index].halted {
+                    turns.push((index, self.take_turn(index)));
+                }
+            }
+
+            let live: Vec<(usize, Turn)> = turns
+                .into_iter()
+                .filter(|(_, turn)| *turn != Turn::Halted)
+                .collect();
+            if live.is_empty() {
+                return Ok(RunOutcome::AllHalted);
+            }
+
+            // Anything that was waiting might have been sent something by a machine that
+            // took its turn later on.
+            let quiet = live.iter().all(|(index, turn)| {
+                let machine = &self.machines[*index];
+                let waiting = match turn {
+                    Turn::Blocked => true,
+                    Turn::Polled => machine.empty_polls >= IDLE_POLLS,
+                    _ => false,
+                };
+                waiting && self.pipes[&machine.input].is_empty()
+            });
+            if !quiet {
+                continue;
+            }
+
+            if live.iter().any(|(_, turn)| *turn == Turn::Polled) {
+                return Ok(RunOutcome::Idle);
+            }
+            return Err(self.deadlock_report());
+        }
+    }
+
+    fn machine_index(&self, name: &str) -> usize {
+        *self
+            .machine_indices
+            .get(name)
+            .unwrap_or_else(|| panic!("Scheduler has no machine called {}", name))
+    }
+
+    fn take_turn(&mut self, index: usize) -> Turn {
+        let machine = &mut self.machines[index];
+        for _ in 0..self.quantum {
+            if machine.processor.input_location.is_some() {
+                let pipe = self.pipes.get_mut(&machine.input).unwrap();
+                if let Some(input) = pipe.pop_front() {
+                    machine.empty_polls = 0;
+                    machine.processor.input_available(input);
+                } else if let Some(input) = machine.idle_input {
+                    machine.empty_polls += 1;
+                    machine.processor.input_available(input);
+                    return Turn::Polled;
+                } else {
+                    return Turn::Blocked;
+                }
+            }
+
+            match machine.processor.process() {
+                // Input is picked up at the top of the loop.
+                SingleOperationResult::Handled | SingleOperationResult::InputRequired => (),
+                SingleOperationResult::OutputAvailable(output) => {
+                    machine.empty_polls = 0;
+                    Self::deliver(&mut self.pipes, &mut self.routers, machine, output);
+                }
+                SingleOperationResult::ProgramEnded => {
+                    machine.halted = true;
+                    return Turn::Halted;
+                }
+            }
+        }
+        Turn::Preempted
+    }
+
+    fn deliver(
+        pipes: &mut HashMap<String, VecDeque<i64>>,
+        routers: &mut HashMap<String, Router>,
+        machine: &mut Machine,
+        output: i64,
+    ) {
+        if let Some(router) = routers.get_mut(&machine.output) {
+            machine.partial_packet.push(output);
+            if machine.partial_packet.len() == router.packet_length {
+                let (destination, values) = (router.route)(&machine.partial_packet);
+                machine.partial_packet.clear();
+                pipes.entry(destination).or_default().extend(values);
+            }
+        } else {
+            pipes.get_mut(&machine.output).unwrap().push_back(output);
+        }
+    }
+
+    fn deadlock_report(&self) -> DeadlockReport {
+        let (halted, blocked): (Vec<&Machine>, Vec<&Machine>) =
+            self.machines.iter().partition(|machine| machine.halted);
+        DeadlockReport {
+            blocked: blocked
+                .into_iter()
+                .map(|machine| BlockedMachine {
+                    machine: machine.name.clone(),
+                    pipe: machine.input.clone(),
+                })
+                .collect(),
+            halted: halted
+                .into_iter()
+                .map(|machine| machine.name.clone())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reads a value, adds one, outputs it, halts.
+    const ADD_ONE: [i64; 9] = [3, 0, 1001, 0, 1, 0, 4, 0, 99];
+
+    // Reads values and outputs them forever.
+    const ECHO_FOREVER: [i64; 6] = [3, 10, 4, 10, 1105, 1];
+
+    // Like `ECHO_FOREVER`, but ignores -1s.
+    const FORWARD: [i64; 14] = [3, 20, 1008, 20, -1, 21, 1005, 21, 0, 4, 20, 1105, 1, 0];
+
+    #[test]
+    fn chain_runs_to_completion() {
+        let mut scheduler = Scheduler::new(1);
+        scheduler.add_machine("first", &ADD_ONE, "in", "middle");
+        scheduler.add_machine("second", &ADD_ONE, "middle", "out");
+        scheduler.send("in", 40);
+
+        assert_eq!(scheduler.run(), Ok(RunOutcome::AllHalted));
+        assert_eq!(scheduler.drain("out"), vec![42]);
+        assert!(scheduler.is_halted("first"));
+        assert!(scheduler.is_halted("second"));
+    }
+
+    #[test]
+    fn day_7_feedback_loop() {
+        let program = [
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+        let amplifiers = ["A", "B", "C", "D", "E"];
+        let mut scheduler = Scheduler::new(7);
+        for (index, (name, phase)) in amplifiers.iter().zip(&[9, 8, 7, 6, 5]).enumerate() {
+            scheduler.send(name, *phase);
+            scheduler.add_machine(name, &program, name, amplifiers[(index + 1) % 5]);
+        }
+        scheduler.send("A", 0);
+
+        assert_eq!(scheduler.run(), Ok(RunOutcome::AllHalted));
+        assert_eq!(scheduler.drain("A"), vec![139_629_729]);
+    }
+
+    #[test]
+    fn ring_deadlock_is_reported() {
+        let mut scheduler = Scheduler::new(10);
+        scheduler.add_machine("a", &ECHO_FOREVER, "to_a", "to_b");
+        scheduler.add_machine("b", &ECHO_FOREVER, "to_b", "to_c");
+        scheduler.add_machine("c", &ADD_ONE, "to_c", "to_a");
+        scheduler.send("to_a", 1);
+
+        // The value goes round once, c halts, and a and b are left waiting.
+        let report = scheduler.run().unwrap_err();
+        assert_eq!(
+            report.blocked,
+            vec![
+                BlockedMachine {
+                    machine: "a".to_string(),
+                    pipe: "to_a".to_string()
+                },
+                BlockedMachine {
+                    machine: "b".to_string(),
+                    pipe: "to_b".to_string()
+                },
+            ]
+        );
+        assert_eq!(report.halted, vec!["c".to_string()]);
+        assert_eq!(
+            report.to_string(),
+            "Deadlock - a waiting on to_a, b waiting on to_b (halted: c)"
+        );
+    }
+
+    #[test]
+    fn idle_inputs_and_routers() {
+        let mut scheduler = Scheduler::new(100);
+        scheduler.add_router("bus", 2, |packet| {
+            (format!("to_{}", packet[0]), vec![packet[1]])
+        });
+        scheduler.add_machine("x", &FORWARD, "to_x", "bus");
+        scheduler.add_machine("y", &ECHO_FOREVER, "to_y", "bus");
+        scheduler.set_idle_input("x", -1);
+
+        // x keeps being given -1 and doing nothing with it; y blocks.
+        assert_eq!(scheduler.run(), Ok(RunOutcome::Idle));
+
+        // Send y a packet for x, which x will then bounce to "to_5".
+        scheduler.send("to_y", 120);
+        scheduler.send("to_y", 5);
+        scheduler.send("to_x", 5);
+        scheduler.send("to_x", 7);
+        assert_eq!(scheduler.run(), Ok(RunOutcome::Idle));
+        assert_eq!(scheduler.receive("to_120"), Some(5));
+        assert_eq!(scheduler.receive("to_5"), Some(7));
+        assert_eq!(scheduler.receive("to_5"), None);
+    }
+}
@@ -0,0 +1,239 @@
+//! Dijkstra's algorithm and A*, for graphs where some steps cost more than others.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::tree::Tree;
+
+/// A search that always carries on from the cheapest node it hasn't visited yet, which is an
+/// iterator over every node it can reach, cheapest first, along with the cost of getting
+/// there.
+///
+/// Made by [`dijkstra()`], that's Dijkstra's algorithm.  Made by [`astar()`], each node's
+/// cost is topped up with an estimate of how much more it'll cost to get from there to
+/// wherever we're going, so the search heads that way first - that's A*, and the nodes come
+/// out in order of cost plus estimate instead.  Either way, a node's cost is the cheapest
+/// there is by the time it comes out.
+///
+/// Like [`Bfs`], nothing is searched until the iterator is asked for something, so stop as
+/// soon as you've found what you're after, and then ask the search how it got there.
+///
+/// Costs can be any type that adds up and compares, where `C::default()` costs nothing - so
+/// all the integer types will do.  Steps that cost nothing are fine too, but negative ones
+/// aren't.
+///
+/// [`dijkstra()`]: fn.dijkstra.html
+/// [`astar()`]: fn.astar.html
+/// [`Bfs`]: struct.Bfs.html
+pub struct BestFirst<N, C, F, H> {
+    tree: Tree<N, C>,
+    visited: Vec<bool>,
+    queue: BinaryHeap<Reverse<(C, usize)>>,
+    neighbours: F,
+    heuristic: H,
+    unexpanded: Option<usize>,
+}
+
+/// Searches outwards from `start` with Dijkstra's algorithm, where `neighbours` gives the
+/// nodes one step away from any node, and how much each step costs.
+pub fn dijkstra<N, C, F, I>(start: N, neighbours: F) -> BestFirst<N, C, F, fn(&N) -> C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    BestFirst::new(std::iter::once(start), neighbours, |_| C::default())
+}
+
+/// Searches outwards from `start` with A*, where `neighbours` gives the nodes one step away
+/// from any node, and how much each step costs, and `heuristic` estimates how much it'll
+/// cost to get from any node to the goal.
+///
+/// The estimate mustn't ever be more than the real cost, and mustn't drop by more than the
+/// cost of a step when taking that step - distances as the crow flies (or as the rook moves,
+/// on a grid) are the usual choice.
+pub fn astar<N, C, F, I, H>(start: N, neighbours: F, heuristic: H) -> BestFirst<N, C, F, H>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+{
+    BestFirst::new(std::iter::once(start), neighbours, heuristic)
+}
+
+impl<N, C, F, I, H> BestFirst<N, C, F, H>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+{
+    /// Searches outwards from all of the `starts` at once, so each node's cost is from
+    /// whichever start is cheapest.  For Dijkstra's algorithm, use a `heuristic` that always
+    /// says zero.
+    pub fn new(starts: impl IntoIterator<Item = N>, neighbours: F, mut heuristic: H) -> Self {
+        let mut tree = Tree::new();
+        let mut queue = BinaryHeap::new();
+        for start in starts {
+            if tree.index_of(&start).is_none() {
+                let estimate = heuristic(&start);
+                queue.push(Reverse((estimate, tree.add(start, None, C::default()))));
+            }
+        }
+        Self {
+            visited: vec![false; tree.len()],
+            tree,
+            queue,
+            neighbours,
+            heuristic,
+            unexpanded: None,
+        }
+    }
+
+    /// The cheapest way the search knows to get to a node, or `None` if it hasn't found it
+    /// yet.  That's only sure to be the cheapest there is once the node has come out of the
+    /// search.
+    pub fn cost_to(&self, node: &N) -> Option<C> {
+        self.tree.cost_to(node)
+    }
+
+    /// The cheapest path the search knows to a node, from the start to the node itself, or
+    /// `None` if it hasn't found it yet.  As with [`cost_to()`], that's only sure to be the
+    /// cheapest there is once the node has come out of the search.
+    ///
+    /// [`cost_to()`]: #method.cost_to
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.tree.path_to(node)
+    }
+
+    fn expand(&mut self, index: usize) {
+        let (node, cost) = match self.tree.get(index) {
+            Some(entry) => (entry.node.clone(), entry.cost),
+            None => return,
+        };
+        for (neighbour, step) in (self.neighbours)(&node) {
+            let cost = cost + step;
+            let neighbour_index = match self.tree.index_of(&neighbour) {
+                Some(known) => {
+                    let cheaper = self.tree.get(known).is_some_and(|entry| entry.cost <= cost);
+                    if self.visited[known] || cheaper {
+                        continue;
+                    }
+                    self.tree.improve(known, index, cost);
+                    known
+                }
+                None => {
+                    self.visited.push(false);
+                    self.tree.add(neighbour.clone(), Some(index), cost)
+                }
+            };
+
+            // Anything already queued for this node is now too expensive, so will come out
+            // after this and be skipped.
+            let estimate = (self.heuristic)(&neighbour);
+            self.queue.push(Reverse((cost + estimate, neighbour_index)));
+        }
+    }
+}
+
+impl<N, C, F, I, H> Iterator for BestFirst<N, C, F, H>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+{
+    type Item = (N, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(index) = self.unexpanded.take() {
+            self.expand(index);
+        }
+        loop {
+            let Reverse((_, index)) = self.queue.pop()?;
+            if self.visited[index] {
+                continue;
+            }
+            self.visited[index] = true;
+            self.unexpanded = Some(index);
+            return self
+                .tree
+                .get(index)
+                .map(|entry| (entry.node.clone(), entry.cost));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Getting from A to D is cheaper the long way round.
+    //
+    //   A --1-- B --1-- C
+    //    \             /
+    //     -----5------D--1-- E
+    fn roads(&node: &char) -> Vec<(char, u32)> {
+        match node {
+            'A' => vec![('B', 1), ('D', 5)],
+            'B' => vec![('A', 1), ('C', 1)],
+            'C' => vec![('B', 1), ('D', 1)],
+            'D' => vec![('A', 5), ('C', 1), ('E', 1)],
+            _ => vec![('D', 1)],
+        }
+    }
+
+    #[test]
+    fn cheapest_first() {
+        let visited: Vec<(char, u32)> = dijkstra('A', roads).collect();
+        assert_eq!(
+            visited,
+            vec![('A', 0), ('B', 1), ('C', 2), ('D', 3), ('E', 4)]
+        );
+
+        let mut search = dijkstra('A', roads);
+        assert_eq!(search.find(|&(node, _)| node == 'E'), Some(('E', 4)));
+        assert_eq!(search.path_to(&'E'), Some(vec!['A', 'B', 'C', 'D', 'E']));
+        assert_eq!(search.cost_to(&'C'), Some(2));
+
+        let mut search = BestFirst::new(vec!['A', 'E'], roads, |_| 0);
+        assert_eq!(search.by_ref().last(), Some(('C', 2)));
+        assert_eq!(search.path_to(&'D'), Some(vec!['E', 'D']));
+    }
+
+    #[test]
+    fn heading_for_the_goal() {
+        // Walking along the number line towards 10, with steps of 1 or 3, and 3s costing 2.
+        let steps = |&n: &i32| vec![(n - 1, 1), (n + 1, 1), (n - 3, 2), (n + 3, 2)];
+        let estimate = |&n: &i32| (10 - n).abs() * 2 / 3;
+        let mut visited = Vec::new();
+        let mut search = astar(0, steps, estimate).inspect(|&(n, _)| visited.push(n));
+        assert_eq!(search.find(|&(n, _)| n == 10), Some((10, 7)));
+        drop(search);
+
+        // Nothing was visited that was going the wrong way.
+        assert!(visited.iter().all(|&n| n >= 0));
+        assert!(dijkstra(0, steps)
+            .take_while(|&(n, _)| n != 10)
+            .any(|(n, _)| n < 0));
+    }
+
+    #[test]
+    fn free_steps() {
+        let steps = |&n: &u8| {
+            vec![(n + 1, 1), (n * 2, 0)]
+                .into_iter()
+                .filter(|&(n, _)| n <= 20)
+        };
+        let mut search = dijkstra(1, steps);
+        assert_eq!(search.find(|&(n, _)| n == 20), Some((20, 1)));
+        assert_eq!(search.path_to(&20), Some(vec![1, 2, 4, 5, 10, 20]));
+    }
+}
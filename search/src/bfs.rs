@@ -0,0 +1,172 @@
+//! Breadth-first search, for graphs where every step costs the same.
+
+use std::hash::Hash;
+
+use crate::tree::Tree;
+
+/// A breadth-first search, which is an iterator over every node it can reach, nearest first,
+/// along with how many steps away each one is.
+///
+/// Nothing is searched until the iterator is asked for something, and each node's neighbours
+/// are only looked for when the iterator moves past it, so stopping as soon as we find what
+/// we're after (with [`find()`], say) doesn't do any more work than needed.  Afterwards, the
+/// search can still be asked how it got to anywhere it found.
+///
+/// [`find()`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.find
+pub struct Bfs<N, F> {
+    tree: Tree<N, usize>,
+    neighbours: F,
+    next: usize,
+    unexpanded: Option<usize>,
+}
+
+/// Searches outwards from `start`, where `neighbours` gives the nodes one step away from any
+/// node.
+pub fn bfs<N, F, I>(start: N, neighbours: F) -> Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    Bfs::new(std::iter::once(start), neighbours)
+}
+
+impl<N, F, I> Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    /// Searches outwards from all of the `starts` at once, so each node's distance is from
+    /// whichever start is nearest.
+    pub fn new(starts: impl IntoIterator<Item = N>, neighbours: F) -> Self {
+        let mut tree = Tree::new();
+        for start in starts {
+            if tree.index_of(&start).is_none() {
+                tree.add(start, None, 0);
+            }
+        }
+        Self {
+            tree,
+            neighbours,
+            next: 0,
+            unexpanded: None,
+        }
+    }
+
+    /// How many steps it takes to get to a node, or `None` if the search hasn't found it yet.
+    pub fn distance_to(&self, node: &N) -> Option<usize> {
+        self.tree.cost_to(node)
+    }
+
+    /// One of the shortest paths to a node, from the start to the node itself, or `None` if
+    /// the search hasn't found it yet.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.tree.path_to(node)
+    }
+
+    fn expand(&mut self, index: usize) {
+        let (node, distance) = match self.tree.get(index) {
+            Some(entry) => (entry.node.clone(), entry.cost + 1),
+            None => return,
+        };
+        for neighbour in (self.neighbours)(&node) {
+            if self.tree.index_of(&neighbour).is_none() {
+                self.tree.add(neighbour, Some(index), distance);
+            }
+        }
+    }
+}
+
+impl<N, F, I> Iterator for Bfs<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The nodes are found in the order they're visited, so the next one to visit is
+        // simply the next one along.
+        if let Some(index) = self.unexpanded.take() {
+            self.expand(index);
+        }
+        if self.next >= self.tree.len() {
+            return None;
+        }
+        let index = self.next;
+        self.next += 1;
+        self.unexpanded = Some(index);
+        self.tree
+            .get(index)
+            .map(|entry| (entry.node.clone(), entry.cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A line of the numbers 0 to 9.
+    fn line(&n: &i32) -> Vec<i32> {
+        vec![n - 1, n + 1]
+            .into_iter()
+            .filter(|n| (0..10).contains(n))
+            .collect()
+    }
+
+    #[test]
+    fn nearest_first() {
+        let visited: Vec<(i32, usize)> = bfs(4, line).collect();
+        assert_eq!(
+            visited,
+            vec![
+                (4, 0),
+                (3, 1),
+                (5, 1),
+                (2, 2),
+                (6, 2),
+                (1, 3),
+                (7, 3),
+                (0, 4),
+                (8, 4),
+                (9, 5)
+            ]
+        );
+
+        let mut search = Bfs::new(vec![0, 9, 0], line);
+        assert_eq!(search.by_ref().last(), Some((5, 4)));
+        assert_eq!(search.distance_to(&7), Some(2));
+    }
+
+    #[test]
+    fn stopping_early() {
+        let mut asked = Vec::new();
+        let mut search = bfs(4, |&n: &i32| {
+            asked.push(n);
+            line(&n)
+        });
+        assert_eq!(search.find(|&(n, _)| n == 6), Some((6, 2)));
+        assert_eq!(search.distance_to(&6), Some(2));
+        assert_eq!(search.distance_to(&8), None);
+        drop(search);
+        assert_eq!(asked, vec![4, 3, 5, 2]);
+    }
+
+    #[test]
+    fn paths() {
+        let mut search = bfs((0, 0), |&(x, y): &(i32, i32)| {
+            vec![(x + 1, y), (x, y + 1)]
+                .into_iter()
+                .filter(|&(x, y)| x <= 2 && y <= 1)
+        });
+        assert_eq!(search.find(|&(node, _)| node == (2, 1)), Some(((2, 1), 3)));
+        assert_eq!(
+            search.path_to(&(2, 1)),
+            Some(vec![(0, 0), (1, 0), (2, 0), (2, 1)])
+        );
+        assert_eq!(search.path_to(&(0, 0)), Some(vec![(0, 0)]));
+        assert_eq!(search.path_to(&(5, 5)), None);
+    }
+}
@@ -0,0 +1,52 @@
+//! # Search
+//!
+//! `search` finds the shortest way from one place to another, for all the days where we're
+//! finding our way through a maze, or anything else that can be thought of as one.  There
+//! are no graphs to build: the graph is just a function that says where you can get to from
+//! any node - a point on a map, say, or a point along with which keys you're holding.
+//!
+//! - [`bfs()`] is breadth-first search, for when every step costs the same.
+//! - [`dijkstra()`] is Dijkstra's algorithm, for when some steps cost more than others.
+//! - [`astar()`] is A*, which is Dijkstra's algorithm with an estimate of how far there is
+//!   to go, to head towards the goal first.
+//!
+//! Each search is an iterator over the nodes it reaches, nearest (or cheapest) first, and
+//! only does as much searching as it's asked for - so to find the shortest way to one
+//! particular node, `find()` it, and to find how far it is to the furthest, take the `last()`.
+//! Afterwards, the search knows the path it took to anywhere it's been.  To search from lots
+//! of places at once, start with [`Bfs::new()`] or [`BestFirst::new()`].
+//!
+//! Nodes can be anything that can be cloned, compared and hashed - and the search only tells
+//! nodes apart by those, so there's no need for them to carry round how far they've come.
+//!
+//! # Examples
+//!
+//! ```
+//! // The quickest way to get from 1 to 100, by either adding 1 or doubling, without going
+//! // past 100.
+//! let steps = |&n: &u32| vec![n + 1, n * 2].into_iter().filter(|&n| n <= 100);
+//! let mut search = search::bfs(1, steps);
+//! assert_eq!(search.find(|&(n, _)| n == 100), Some((100, 8)));
+//! assert_eq!(
+//!     search.path_to(&100),
+//!     Some(vec![1, 2, 3, 6, 12, 24, 25, 50, 100])
+//! );
+//!
+//! // Doubling for free makes it cheaper to go the long way round.
+//! let steps = |&n: &u32| vec![(n + 1, 1), (n * 2, 0)].into_iter().filter(|&(n, _)| n <= 100);
+//! let mut search = search::dijkstra(1, steps);
+//! assert_eq!(search.find(|&(n, _)| n == 100), Some((100, 2)));
+//! ```
+//!
+//! [`bfs()`]: fn.bfs.html
+//! [`dijkstra()`]: fn.dijkstra.html
+//! [`astar()`]: fn.astar.html
+//! [`Bfs::new()`]: struct.Bfs.html#method.new
+//! [`BestFirst::new()`]: struct.BestFirst.html#method.new
+
+mod best_first;
+mod bfs;
+mod tree;
+
+pub use best_first::{astar, dijkstra, BestFirst};
+pub use bfs::{bfs, Bfs};
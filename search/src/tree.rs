@@ -0,0 +1,73 @@
+//! The record of where a search has been.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Every node a search has found, in the order it found them, with the cheapest way it knows
+// to each one - the node it came from, and how much it cost to get there.  Following those
+// back from any node gives the path to it.
+pub(crate) struct Tree<N, C> {
+    entries: Vec<Entry<N, C>>,
+    indices: HashMap<N, usize>,
+}
+
+pub(crate) struct Entry<N, C> {
+    pub node: N,
+    pub parent: Option<usize>,
+    pub cost: C,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Tree<N, C> {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn index_of(&self, node: &N) -> Option<usize> {
+        self.indices.get(node).copied()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Entry<N, C>> {
+        self.entries.get(index)
+    }
+
+    // Records a node that hasn't been found before, and returns its index.
+    pub fn add(&mut self, node: N, parent: Option<usize>, cost: C) -> usize {
+        let index = self.entries.len();
+        self.indices.insert(node.clone(), index);
+        self.entries.push(Entry { node, parent, cost });
+        index
+    }
+
+    // Records a cheaper way to a node that's been found before.
+    pub fn improve(&mut self, index: usize, parent: usize, cost: C) {
+        let entry = &mut self.entries[index];
+        entry.parent = Some(parent);
+        entry.cost = cost;
+    }
+
+    pub fn cost_to(&self, node: &N) -> Option<C> {
+        self.index_of(node).map(|index| self.entries[index].cost)
+    }
+
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut index = self.index_of(node);
+        let mut path = Vec::new();
+        while let Some(current) = index {
+            let entry = &self.entries[current];
+            path.push(entry.node.clone());
+            index = entry.parent;
+        }
+        if path.is_empty() {
+            return None;
+        }
+        path.reverse();
+        Some(path)
+    }
+}